@@ -0,0 +1,197 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    errors::MerkleError,
+    merkle_tree::{MerklePath, MerkleTreeDigest},
+    traits::MerkleParameters,
+};
+use snarkvm_utilities::ToBytes;
+use std::sync::Arc;
+
+///
+/// An append-only Merkle tree, which produces the same roots and Merkle paths as a `MerkleTree`
+/// constructed from the same leaves, using the same `MerkleParameters`.
+///
+/// Appending a leaf only hashes the O(depth) nodes on the path from the new leaf to the root.
+/// Each level stores its completed nodes (the frontier of the tree), so nodes that are not
+/// complete are either empty subtrees or are recomputed from the rightmost nodes on demand.
+///
+#[derive(Clone)]
+pub struct IncrementalMerkleTree<P: MerkleParameters> {
+    /// The completed nodes at each level of the tree, from the hashed leaves upwards.
+    levels: Vec<Vec<MerkleTreeDigest<P>>>,
+    /// The roots of empty subtrees at each level, from the empty leaf upwards.
+    empty_roots: Vec<MerkleTreeDigest<P>>,
+    /// The computed root of the tree.
+    root: MerkleTreeDigest<P>,
+    /// The Merkle tree parameters (e.g. the hash function).
+    parameters: Arc<P>,
+}
+
+impl<P: MerkleParameters + Send + Sync> IncrementalMerkleTree<P> {
+    pub const DEPTH: usize = P::DEPTH;
+
+    /// Initializes a new tree with the given leaves.
+    pub fn new<L: ToBytes>(parameters: Arc<P>, leaves: &[L]) -> Result<Self, MerkleError> {
+        // Compute the roots of empty subtrees for every level.
+        let mut empty_roots = Vec::with_capacity(Self::DEPTH + 1);
+        empty_roots.push(parameters.hash_empty()?);
+        for level in 0..Self::DEPTH {
+            empty_roots.push(parameters.hash_inner_node(&empty_roots[level], &empty_roots[level])?);
+        }
+
+        let mut tree = Self {
+            levels: vec![vec![]; Self::DEPTH + 1],
+            root: empty_roots[0],
+            empty_roots,
+            parameters,
+        };
+        tree.root = tree.compute_root()?;
+        tree.append_all(leaves)?;
+
+        Ok(tree)
+    }
+
+    /// Appends the given leaf to the tree, returning its index in the tree.
+    pub fn append<L: ToBytes>(&mut self, leaf: &L) -> Result<usize, MerkleError> {
+        self.append_all(&[leaf]).map(|(start_index, _)| start_index)
+    }
+
+    /// Appends the given leaves to the tree, returning the range of their indices in the tree.
+    pub fn append_all<L: ToBytes>(&mut self, leaves: &[L]) -> Result<(usize, usize), MerkleError> {
+        let start_index = self.num_leaves();
+        let num_leaves = start_index + leaves.len();
+
+        // Ensure the tree does not exceed its maximum capacity.
+        let tree_depth = Self::tree_depth(num_leaves);
+        if tree_depth > Self::DEPTH {
+            return Err(MerkleError::InvalidTreeDepth(tree_depth, Self::DEPTH));
+        }
+
+        let leaf_hashes = leaves
+            .iter()
+            .map(|leaf| self.parameters.hash_leaf(leaf))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for leaf_hash in leaf_hashes {
+            let mut index = self.levels[0].len();
+            self.levels[0].push(leaf_hash);
+
+            // Hash upwards for as long as the new node completes a pair.
+            let mut level = 0;
+            while index % 2 == 1 {
+                let node = self
+                    .parameters
+                    .hash_inner_node(&self.levels[level][index - 1], &self.levels[level][index])?;
+                level += 1;
+                index /= 2;
+                self.levels[level].push(node);
+            }
+        }
+
+        self.root = self.compute_root()?;
+
+        Ok((start_index, num_leaves.saturating_sub(1)))
+    }
+
+    /// Removes all leaves at or after the given index from the tree.
+    pub fn truncate(&mut self, num_leaves: usize) -> Result<(), MerkleError> {
+        for (level, nodes) in self.levels.iter_mut().enumerate() {
+            nodes.truncate(num_leaves >> level);
+        }
+        self.root = self.compute_root()?;
+        Ok(())
+    }
+
+    /// Returns the root of the tree.
+    #[inline]
+    pub fn root(&self) -> &MerkleTreeDigest<P> {
+        &self.root
+    }
+
+    /// Returns the number of leaves in the tree.
+    #[inline]
+    pub fn num_leaves(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Returns the hashed leaves of the tree.
+    #[inline]
+    pub fn hashed_leaves(&self) -> &[MerkleTreeDigest<P>] {
+        &self.levels[0]
+    }
+
+    /// Returns the Merkle path for the given leaf at the given index.
+    pub fn generate_proof<L: ToBytes>(&self, index: usize, leaf: &L) -> Result<MerklePath<P>, MerkleError> {
+        // Check that the given index corresponds to the correct leaf.
+        match self.levels[0].get(index) {
+            Some(leaf_hash) if *leaf_hash == self.parameters.hash_leaf(leaf)? => (),
+            _ => return Err(MerkleError::IncorrectLeafIndex(index)),
+        }
+
+        // Collect the siblings from the leaf up to the root of the populated subtree.
+        let tree_depth = Self::tree_depth(self.num_leaves());
+        let mut path = Vec::with_capacity(Self::DEPTH);
+        for level in 0..tree_depth {
+            path.push(self.node(level, (index >> level) ^ 1)?);
+        }
+
+        // The remaining siblings, up to the full depth, are padded with the empty hash.
+        path.resize(Self::DEPTH, self.empty_roots[0]);
+
+        Ok(MerklePath {
+            parameters: self.parameters.clone(),
+            path,
+            leaf_index: index as u64,
+        })
+    }
+
+    /// Returns the root of the tree, by padding the root of the populated subtree up to the full depth.
+    fn compute_root(&self) -> Result<MerkleTreeDigest<P>, MerkleError> {
+        let tree_depth = Self::tree_depth(self.num_leaves());
+
+        let mut root = self.node(tree_depth, 0)?;
+        for _ in tree_depth..Self::DEPTH {
+            root = self.parameters.hash_inner_node(&root, &self.empty_roots[0])?;
+        }
+        Ok(root)
+    }
+
+    /// Returns the node at the given level and index. Nodes that are not yet complete are
+    /// either the root of an empty subtree, or are recomputed from their children.
+    fn node(&self, level: usize, index: usize) -> Result<MerkleTreeDigest<P>, MerkleError> {
+        if let Some(node) = self.levels[level].get(index) {
+            return Ok(*node);
+        }
+
+        // Check if the subtree under this node is empty.
+        if (index << level) >= self.num_leaves() || level == 0 {
+            return Ok(self.empty_roots[level]);
+        }
+
+        // Otherwise, the subtree under this node is partially filled.
+        let left = self.node(level - 1, 2 * index)?;
+        let right = self.node(level - 1, 2 * index + 1)?;
+        self.parameters.hash_inner_node(&left, &right)
+    }
+
+    /// Returns the depth of the populated subtree, which is the smallest power of two that fits the given number of leaves.
+    #[inline]
+    fn tree_depth(num_leaves: usize) -> usize {
+        num_leaves.next_power_of_two().trailing_zeros() as usize
+    }
+}
@@ -18,7 +18,7 @@ use crate::{
     errors::MerkleError,
     traits::{MerkleParameters, CRH},
 };
-use snarkvm_utilities::{FromBytes, ToBytes};
+use snarkvm_utilities::{error, FromBytes, ToBytes};
 
 use std::{
     io::{Read, Result as IoResult, Write},
@@ -108,16 +108,21 @@ impl<P: MerkleParameters> FromBytes for MerklePath<P> {
         let parameters = {
             let setup_message_length: u64 = FromBytes::read_le(&mut reader)?;
 
-            let mut setup_message_bytes = vec![0u8; setup_message_length as usize];
-            reader.read_exact(&mut setup_message_bytes)?;
-            let setup_message =
-                String::from_utf8(setup_message_bytes).expect("Failed to parse setup message for Merkle parameters");
+            let mut setup_message_bytes = Vec::new();
+            (&mut reader)
+                .take(setup_message_length)
+                .read_to_end(&mut setup_message_bytes)?;
+            if setup_message_bytes.len() as u64 != setup_message_length {
+                return Err(error("Failed to read setup message for Merkle parameters"));
+            }
+            let setup_message = String::from_utf8(setup_message_bytes)
+                .map_err(|_| error("Failed to parse setup message for Merkle parameters"))?;
 
             Arc::new(P::setup(&setup_message))
         };
 
         let path_length: u64 = FromBytes::read_le(&mut reader)?;
-        let mut path = Vec::with_capacity(path_length as usize);
+        let mut path = Vec::new();
         for _ in 0..path_length {
             path.push(FromBytes::read_le(&mut reader)?);
         }
@@ -16,7 +16,7 @@
 
 use crate::{
     crh::{PedersenCRH, PedersenCompressedCRH},
-    merkle_tree::{MerkleTree, MerkleTreeParameters},
+    merkle_tree::{IncrementalMerkleTree, MerkleTree, MerkleTreeParameters},
     traits::{MerkleParameters, CRH},
 };
 use snarkvm_utilities::{to_bytes_le, ToBytes};
@@ -152,6 +152,51 @@ fn padded_merkle_tree_test<P: MerkleParameters>() {
     assert_eq!(merkle_tree_root, &expected_root);
 }
 
+/// Checks the incremental Merkle tree produces the same roots and Merkle paths as the Merkle tree.
+fn incremental_merkle_tree_test<P: MerkleParameters + Send + Sync>(num_leaves: usize) {
+    let parameters = Arc::new(P::setup("merkle_tree_test"));
+    let leaves = generate_random_leaves!(num_leaves, 32);
+
+    let mut incremental_tree = IncrementalMerkleTree::<P>::new(parameters.clone(), &Vec::<[u8; 32]>::new()).unwrap();
+    for (i, leaf) in leaves.iter().enumerate() {
+        assert_eq!(i, incremental_tree.append(leaf).unwrap());
+
+        let tree = MerkleTree::<P>::new(parameters.clone(), &leaves[..=i]).unwrap();
+        assert_eq!(tree.root(), incremental_tree.root());
+        assert_eq!(tree.hashed_leaves()[..=i], incremental_tree.hashed_leaves()[..]);
+
+        for (j, leaf) in leaves[..=i].iter().enumerate() {
+            let expected = tree.generate_proof(j, leaf).unwrap();
+            let candidate = incremental_tree.generate_proof(j, leaf).unwrap();
+            assert_eq!(expected.path, candidate.path);
+            assert_eq!(expected.leaf_index, candidate.leaf_index);
+            assert!(candidate.verify(incremental_tree.root(), leaf).unwrap());
+        }
+    }
+
+    // Ensure constructing the tree from all leaves at once is equivalent.
+    let batch_tree = IncrementalMerkleTree::<P>::new(parameters.clone(), &leaves).unwrap();
+    assert_eq!(incremental_tree.root(), batch_tree.root());
+
+    // Ensure truncating the tree is equivalent to a tree with fewer leaves.
+    for i in (0..num_leaves).rev() {
+        incremental_tree.truncate(i).unwrap();
+        let tree = MerkleTree::<P>::new(parameters.clone(), &leaves[..i]).unwrap();
+        assert_eq!(tree.root(), incremental_tree.root());
+    }
+}
+
+/// Checks the incremental Merkle tree rejects leaves beyond its capacity.
+fn incremental_merkle_tree_capacity_test<P: MerkleParameters + Send + Sync>() {
+    let parameters = Arc::new(P::setup("merkle_tree_test"));
+    let leaves = generate_random_leaves!(1 << P::DEPTH, 8);
+
+    let mut tree = IncrementalMerkleTree::<P>::new(parameters.clone(), &leaves).unwrap();
+    assert_eq!(MerkleTree::<P>::new(parameters, &leaves).unwrap().root(), tree.root());
+    assert!(tree.append(&[0u8; 8]).is_err());
+    assert_eq!(1 << P::DEPTH, tree.num_leaves());
+}
+
 mod pedersen_crh_on_projective {
     use super::*;
     use snarkvm_curves::edwards_bls12::EdwardsProjective as Edwards;
@@ -229,6 +274,19 @@ mod pedersen_compressed_crh_on_projective {
         padded_merkle_tree_test::<MTParameters>();
     }
 
+    #[test]
+    fn incremental_merkle_tree_matches_merkle_tree_test() {
+        type MTParameters = MerkleTreeParameters<PedersenCompressedCRH<Edwards, NUM_WINDOWS, WINDOW_SIZE>, 32>;
+        incremental_merkle_tree_test::<MTParameters>(17);
+    }
+
+    #[test]
+    fn incremental_merkle_tree_full_depth_test() {
+        type MTParameters = MerkleTreeParameters<PedersenCompressedCRH<Edwards, NUM_WINDOWS, WINDOW_SIZE>, 3>;
+        incremental_merkle_tree_test::<MTParameters>(8);
+        incremental_merkle_tree_capacity_test::<MTParameters>();
+    }
+
     #[test]
     fn merkle_tree_rebuild_test() {
         type MTParameters = MerkleTreeParameters<PedersenCompressedCRH<Edwards, NUM_WINDOWS, WINDOW_SIZE>, 32>;
@@ -55,6 +55,7 @@ mod bls12_377 {
         generate_random_parameters,
         prepare_verifying_key,
         verify_proof,
+        verify_proofs_batch,
         Proof,
     };
     use snarkvm_curves::bls12_377::{Bls12_377, Fr};
@@ -80,6 +81,44 @@ mod bls12_377 {
         }
     }
 
+    #[test]
+    fn prove_and_verify_batch() {
+        let rng = &mut thread_rng();
+        let parameters =
+            generate_random_parameters::<Bls12_377, _, _>(&MySillyCircuit { a: None, b: None }, rng).unwrap();
+        let pvk = prepare_verifying_key::<Bls12_377>(parameters.vk.clone());
+
+        let mut proofs = Vec::new();
+        let mut inputs = Vec::new();
+        for _ in 0..10 {
+            let (a, b) = (Fr::rand(rng), Fr::rand(rng));
+            proofs.push(create_random_proof(&MySillyCircuit { a: Some(a), b: Some(b) }, &parameters, rng).unwrap());
+            inputs.push(vec![a * b]);
+        }
+
+        let batch = proofs
+            .iter()
+            .zip(inputs.iter())
+            .map(|(proof, input)| (proof, &input[..]))
+            .collect::<Vec<_>>();
+        assert!(verify_proofs_batch(&pvk, &batch, rng).unwrap());
+        assert!(verify_proofs_batch(&pvk, &batch[..1], rng).unwrap());
+        assert!(verify_proofs_batch(&pvk, &[], rng).unwrap());
+
+        // Ensure a single invalid input fails the batch.
+        let invalid_input = vec![Fr::rand(rng)];
+        let mut invalid_batch = batch.clone();
+        invalid_batch[5].1 = &invalid_input[..];
+        assert!(!verify_proofs_batch(&pvk, &invalid_batch, rng).unwrap());
+
+        // Ensure swapping the proofs of two inputs fails the batch.
+        let mut swapped_batch = batch.clone();
+        swapped_batch.swap(0, 1);
+        swapped_batch[0].1 = batch[0].1;
+        swapped_batch[1].1 = batch[1].1;
+        assert!(!verify_proofs_batch(&pvk, &swapped_batch, rng).unwrap());
+    }
+
     #[test]
     fn test_serde_json() {
         let expected_proof = {
@@ -16,9 +16,12 @@
 
 use super::{PreparedVerifyingKey, Proof, VerifyingKey};
 use snarkvm_curves::traits::{PairingCurve, PairingEngine};
+use snarkvm_fields::{One, Zero};
 use snarkvm_r1cs::errors::SynthesisError;
+use snarkvm_utilities::UniformRand;
 
 use core::ops::{AddAssign, Mul, Neg};
+use rand::Rng;
 
 pub fn prepare_verifying_key<E: PairingEngine>(vk: VerifyingKey<E>) -> PreparedVerifyingKey<E> {
     let alpha_g1_beta_g2 = E::pairing(vk.alpha_g1, vk.beta_g2);
@@ -64,3 +67,69 @@ pub fn verify_proof<E: PairingEngine>(
 
     Ok(test == pvk.alpha_g1_beta_g2)
 }
+
+///
+/// Verifies a batch of proofs under the same verifying key, returning `true` if every proof is valid.
+///
+/// Each verification equation is scaled by a random scalar, and the scaled equations are combined
+/// into one product of pairings, so the batch requires a single final exponentiation.
+///
+pub fn verify_proofs_batch<E: PairingEngine, R: Rng>(
+    pvk: &PreparedVerifyingKey<E>,
+    proofs_and_inputs: &[(&Proof<E>, &[E::Fr])],
+    rng: &mut R,
+) -> Result<bool, SynthesisError> {
+    if proofs_and_inputs.is_empty() {
+        return Ok(true);
+    }
+
+    let mut a_prepared = Vec::with_capacity(proofs_and_inputs.len());
+    let mut b_prepared = Vec::with_capacity(proofs_and_inputs.len());
+    let mut g_ic_sum = E::G1Affine::zero();
+    let mut c_sum = E::G1Affine::zero();
+    let mut r_sum = E::Fr::zero();
+
+    for (proof, public_inputs) in proofs_and_inputs {
+        if (public_inputs.len() + 1) != pvk.gamma_abc_g1().len() {
+            return Err(SynthesisError::MalformedVerifyingKey(
+                public_inputs.len() + 1,
+                pvk.gamma_abc_g1().len(),
+            ));
+        }
+
+        let mut g_ic = pvk.gamma_abc_g1()[0];
+        for (i, b) in public_inputs.iter().zip(pvk.gamma_abc_g1().iter().skip(1)) {
+            g_ic.add_assign(b.mul(*i));
+        }
+
+        // Scale the verification equation of this proof by a random scalar.
+        let r = E::Fr::rand(rng);
+        a_prepared.push(proof.a.mul(r).prepare());
+        b_prepared.push(proof.b.prepare());
+        g_ic_sum.add_assign(g_ic.mul(r));
+        c_sum.add_assign(proof.c.mul(r));
+        r_sum.add_assign(r);
+    }
+
+    // Move the `e(alpha, beta)^{sum(r)}` term to the product of pairings, so the product is one if the batch is valid.
+    let g_ic_prepared = g_ic_sum.prepare();
+    let c_prepared = c_sum.prepare();
+    let alpha_prepared = pvk.vk.alpha_g1.mul(r_sum).neg().prepare();
+    let beta_prepared = pvk.vk.beta_g2.prepare();
+
+    let qap = E::miller_loop(
+        a_prepared.iter().zip(b_prepared.iter()).chain(
+            [
+                (&g_ic_prepared, &pvk.gamma_g2_neg_pc),
+                (&c_prepared, &pvk.delta_g2_neg_pc),
+                (&alpha_prepared, &beta_prepared),
+            ]
+            .iter()
+            .copied(),
+        ),
+    );
+
+    let test = E::final_exponentiation(&qap).ok_or(SynthesisError::UnexpectedIdentity)?;
+
+    Ok(test.is_one())
+}
@@ -99,4 +99,29 @@ pub trait SNARK: Clone + Debug {
         let processed_verifying_key = verifying_key.prepare();
         Self::verify_prepared(&processed_verifying_key, input, proof)
     }
+
+    /// Returns `true` if every proof in the batch is valid for its input.
+    /// By default, each proof is verified individually. SNARKs that support
+    /// batch verification may override this to amortize the cost of the batch.
+    fn verify_batch_prepared<R: Rng + CryptoRng>(
+        prepared_verifying_key: &Self::PreparedVerifyingKey,
+        inputs_and_proofs: &[(&Self::VerifierInput, &Self::Proof)],
+        _rng: &mut R,
+    ) -> Result<bool, SNARKError> {
+        for (input, proof) in inputs_and_proofs {
+            if !Self::verify_prepared(prepared_verifying_key, input, proof)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn verify_batch<R: Rng + CryptoRng>(
+        verifying_key: &Self::VerifyingKey,
+        inputs_and_proofs: &[(&Self::VerifierInput, &Self::Proof)],
+        rng: &mut R,
+    ) -> Result<bool, SNARKError> {
+        let processed_verifying_key = verifying_key.prepare();
+        Self::verify_batch_prepared(&processed_verifying_key, inputs_and_proofs, rng)
+    }
 }
@@ -14,17 +14,24 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{AccountError, Network, PrivateKey};
+use crate::{account_format, AccountError, Network, PrivateKey};
 use snarkvm_algorithms::SignatureSchemeOperations;
 use snarkvm_curves::AffineCurve;
-use snarkvm_utilities::{FromBytes, ToBytes};
+use snarkvm_utilities::{FromBytes, FromBytesDeserializer, ToBytes, ToBytesSerializer};
 
+use base58::{FromBase58, ToBase58};
+use blake2::{Blake2s, Digest};
 use rand::thread_rng;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     fmt,
     io::{Read, Result as IoResult, Write},
+    str::FromStr,
 };
 
+/// The number of checksum bytes in the string representation of a compute key.
+const CHECKSUM_SIZE_IN_BYTES: usize = 4;
+
 #[derive(Derivative)]
 #[derivative(
     Clone(bound = "N: Network"),
@@ -106,14 +113,41 @@ impl<N: Network> ComputeKey<N> {
 
         self.pk_sig + self.pr_sig + pk_prf
     }
+
+    /// Returns the point in the prime-order subgroup with the given x-coordinate.
+    fn point_from_x_coordinate(x_coordinate: N::ProgramBaseField) -> Result<N::ProgramAffineCurve, AccountError> {
+        for greatest in [true, false].iter() {
+            if let Some(point) = N::ProgramAffineCurve::from_x_coordinate(x_coordinate, *greatest) {
+                if point.is_in_correct_subgroup_assuming_on_curve() {
+                    return Ok(point);
+                }
+            }
+        }
+        Err(AccountError::Message("Invalid compute key point".to_string()))
+    }
+
+    /// Returns the checksum of the given compute key string bytes.
+    fn checksum(bytes: &[u8]) -> [u8; CHECKSUM_SIZE_IN_BYTES] {
+        let mut checksum = [0u8; CHECKSUM_SIZE_IN_BYTES];
+        checksum.copy_from_slice(&Blake2s::digest(bytes)[..CHECKSUM_SIZE_IN_BYTES]);
+        checksum
+    }
 }
 
 impl<N: Network> FromBytes for ComputeKey<N> {
     /// Reads in an account compute key buffer.
     #[inline]
     fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
-        let pk_sig = FromBytes::read_le(&mut reader)?;
-        let pr_sig = FromBytes::read_le(&mut reader)?;
+        let pk_sig: N::ProgramAffineCurve = FromBytes::read_le(&mut reader)?;
+        let pr_sig: N::ProgramAffineCurve = FromBytes::read_le(&mut reader)?;
+
+        // Ensure the points are in the prime-order subgroup, as they may come from an untrusted source.
+        for point in [pk_sig, pr_sig].iter() {
+            if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+                return Err(AccountError::Message("Invalid compute key point".to_string()).into());
+            }
+        }
+
         Ok(Self::new(pk_sig, pr_sig))
     }
 }
@@ -125,6 +159,72 @@ impl<N: Network> ToBytes for ComputeKey<N> {
     }
 }
 
+impl<N: Network> FromStr for ComputeKey<N> {
+    type Err = AccountError;
+
+    /// Reads in an account compute key string.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let prefix_length = account_format::COMPUTE_KEY_PREFIX.len();
+        let data = s.from_base58()?;
+        if data.len() != prefix_length + 64 + CHECKSUM_SIZE_IN_BYTES {
+            return Err(AccountError::InvalidByteLength(data.len()));
+        }
+
+        if data[0..prefix_length] != account_format::COMPUTE_KEY_PREFIX {
+            return Err(AccountError::InvalidPrefixBytes(data[0..prefix_length].to_vec()));
+        }
+
+        let (payload, checksum) = data.split_at(data.len() - CHECKSUM_SIZE_IN_BYTES);
+        if Self::checksum(payload) != checksum {
+            return Err(AccountError::InvalidChecksum);
+        }
+
+        // Recover the points from their x-coordinates.
+        let pk_sig = Self::point_from_x_coordinate(FromBytes::read_le(&payload[prefix_length..prefix_length + 32])?)?;
+        let pr_sig = Self::point_from_x_coordinate(FromBytes::read_le(&payload[prefix_length + 32..])?)?;
+        Ok(Self::new(pk_sig, pr_sig))
+    }
+}
+
+impl<N: Network> fmt::Display for ComputeKey<N> {
+    /// Writes the compute key as a base58 string of its prefix, the x-coordinates of its points, and a checksum.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut compute_key = account_format::COMPUTE_KEY_PREFIX.to_vec();
+        self.pk_sig
+            .to_x_coordinate()
+            .write_le(&mut compute_key)
+            .expect("compute key formatting failed");
+        self.pr_sig
+            .to_x_coordinate()
+            .write_le(&mut compute_key)
+            .expect("compute key formatting failed");
+        let checksum = Self::checksum(&compute_key);
+        compute_key.extend_from_slice(&checksum);
+
+        write!(f, "{}", compute_key.to_base58())
+    }
+}
+
+impl<N: Network> Serialize for ComputeKey<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => serializer.collect_str(self),
+            false => ToBytesSerializer::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de, N: Network> Deserialize<'de> for ComputeKey<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => FromStr::from_str(&String::deserialize(deserializer)?).map_err(de::Error::custom),
+            false => {
+                FromBytesDeserializer::<Self>::deserialize(deserializer, "compute key", N::COMPUTE_KEY_SIZE_IN_BYTES)
+            }
+        }
+    }
+}
+
 impl<N: Network> fmt::Debug for ComputeKey<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -140,3 +240,54 @@ impl<N: Network> Default for ComputeKey<N> {
         PrivateKey::new(&mut thread_rng()).to_compute_key()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testnet2::Testnet2;
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_serde_json() {
+        let rng = &mut thread_rng();
+
+        let private_key = PrivateKey::new(rng);
+        let expected_compute_key: ComputeKey<Testnet2> = private_key.to_compute_key();
+
+        // Serialize
+        let expected_string = &expected_compute_key.to_string();
+        let candidate_string = serde_json::to_string(&expected_compute_key).unwrap();
+        assert_eq!(
+            expected_string,
+            serde_json::Value::from_str(&candidate_string)
+                .unwrap()
+                .as_str()
+                .unwrap()
+        );
+
+        // Deserialize
+        assert_eq!(expected_compute_key, ComputeKey::from_str(expected_string).unwrap());
+        assert_eq!(expected_compute_key, serde_json::from_str(&candidate_string).unwrap());
+    }
+
+    #[test]
+    fn test_bincode() {
+        let rng = &mut thread_rng();
+
+        let private_key = PrivateKey::new(rng);
+        let expected_compute_key: ComputeKey<Testnet2> = private_key.to_compute_key();
+
+        // Serialize
+        let expected_bytes = expected_compute_key.to_bytes_le().unwrap();
+        assert_eq!(Testnet2::COMPUTE_KEY_SIZE_IN_BYTES, expected_bytes.len());
+        assert_eq!(
+            &expected_bytes[..],
+            &bincode::serialize(&expected_compute_key).unwrap()[..]
+        );
+
+        // Deserialize
+        assert_eq!(expected_compute_key, ComputeKey::read_le(&expected_bytes[..]).unwrap());
+        assert_eq!(expected_compute_key, bincode::deserialize(&expected_bytes[..]).unwrap());
+    }
+}
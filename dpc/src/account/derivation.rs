@@ -0,0 +1,123 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{AccountError, Network, ACCOUNT_SEED_CHILD_DOMAIN};
+use snarkvm_algorithms::traits::PRF;
+use snarkvm_fields::PrimeField;
+use snarkvm_utilities::{FromBytes, ToBytes};
+
+use std::{fmt, str::FromStr};
+
+/// The maximum number of indices in a derivation path.
+pub const MAXIMUM_DERIVATION_DEPTH: usize = 255;
+
+///
+/// A path of child indices, from a master account seed to a descendant account seed.
+///
+/// Every step of the derivation is hardened, as the child seed is computed as
+/// `PRF(parent_seed, [domain, index])`. Consequently, child private keys and child view keys
+/// may only be derived by a holder of the parent seed, and a child view key may not be
+/// derived from a parent view key alone. A private key is fully determined by its seed,
+/// and the view key is a non-linear function of the private key components, so there is
+/// no tweak of a parent view key that would yield the view key of a child.
+///
+/// The string representation is of the form `m/0/1/2`, where `m` denotes the master seed.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// Initializes a new derivation path from the given child indices.
+    pub fn new(indices: Vec<u32>) -> Result<Self, AccountError> {
+        match indices.len() <= MAXIMUM_DERIVATION_DEPTH {
+            true => Ok(Self(indices)),
+            false => Err(AccountError::InvalidDerivationDepth(indices.len())),
+        }
+    }
+
+    /// Returns the derivation path of the master seed.
+    pub fn master() -> Self {
+        Self(vec![])
+    }
+
+    /// Returns the derivation path of the child at the given index, under this path.
+    pub fn child(&self, index: u32) -> Result<Self, AccountError> {
+        let mut indices = self.0.clone();
+        indices.push(index);
+        Self::new(indices)
+    }
+
+    /// Returns the child indices of the derivation path.
+    pub fn indices(&self) -> &[u32] {
+        &self.0
+    }
+
+    /// Returns the number of indices in the derivation path.
+    pub fn depth(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the account seed at this derivation path, from the given master seed.
+    pub fn derive_seed<N: Network>(&self, master_seed: &N::AccountSeed) -> Result<N::AccountSeed, AccountError> {
+        self.0
+            .iter()
+            .try_fold(master_seed.clone(), |seed, index| derive_child_seed::<N>(&seed, *index))
+    }
+}
+
+/// Returns the child account seed at the given index, from the given parent account seed.
+pub fn derive_child_seed<N: Network>(parent_seed: &N::AccountSeed, index: u32) -> Result<N::AccountSeed, AccountError> {
+    // Construct the child domain separator.
+    let child_domain = N::ProgramScalarField::from_bytes_le_mod_order(ACCOUNT_SEED_CHILD_DOMAIN.as_bytes());
+    let child_index = N::ProgramScalarField::from(index as u64);
+
+    // Compute the child seed as PRF(parent_seed, [child_domain, index]).
+    let child_seed = N::AccountSeedPRF::evaluate(parent_seed, &vec![child_domain, child_index])?;
+    Ok(FromBytes::read_le(&child_seed.to_bytes_le()?[..])?)
+}
+
+impl FromStr for DerivationPath {
+    type Err = AccountError;
+
+    /// Reads in a derivation path string, of the form `m/0/1/2`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut components = s.split('/');
+        if components.next() != Some("m") {
+            return Err(AccountError::InvalidDerivationPath(s.to_string()));
+        }
+
+        // Only plain decimal indices are accepted, as every step of the derivation is hardened.
+        let indices = components
+            .map(|index| match index.chars().all(|c| c.is_ascii_digit()) {
+                true => index.parse::<u32>().ok(),
+                false => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| AccountError::InvalidDerivationPath(s.to_string()))?;
+
+        Self::new(indices)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            write!(f, "/{}", index)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,442 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{AccountError, Address, Network, PrivateKey};
+use snarkvm_utilities::{FromBytes, ToBytes};
+
+use blake2::{Blake2s, Digest};
+use chacha20poly1305::{
+    aead::{Aead, NewAead},
+    ChaCha20Poly1305,
+    Key,
+    Nonce,
+};
+use rand::{CryptoRng, Rng};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use zeroize::{Zeroize, Zeroizing};
+
+/// The current version of the keystore format.
+pub const KEYSTORE_VERSION: u16 = 1;
+
+const KEYSTORE_KDF: &str = "scrypt";
+const KEYSTORE_CIPHER: &str = "chacha20poly1305";
+
+const SALT_SIZE_IN_BYTES: usize = 32;
+const NONCE_SIZE_IN_BYTES: usize = 12;
+const TAG_SIZE_IN_BYTES: usize = 16;
+
+/// The maximum scrypt cost parameter accepted when loading a keystore.
+const MAXIMUM_SCRYPT_LOG_N: u8 = 20;
+/// The maximum scrypt work `r * p * 2^log_n` accepted when loading a keystore, to bound the time and memory use.
+/// The memory use is `128 * r * 2^log_n` bytes, so this bounds it to 1 GiB.
+const MAXIMUM_SCRYPT_WORK: u128 = 8 << MAXIMUM_SCRYPT_LOG_N;
+
+/// The scrypt parameters used to derive the encryption key from a password.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScryptParameters {
+    /// The base-2 logarithm of the CPU/memory cost.
+    pub log_n: u8,
+    /// The block size.
+    pub r: u32,
+    /// The parallelization.
+    pub p: u32,
+}
+
+impl ScryptParameters {
+    /// Returns the work of the parameters, as `r * p * 2^log_n`.
+    fn work(&self) -> u128 {
+        (self.r as u128 * self.p as u128) << self.log_n
+    }
+}
+
+impl Default for ScryptParameters {
+    fn default() -> Self {
+        Self { log_n: 15, r: 8, p: 1 }
+    }
+}
+
+///
+/// A password-encrypted keystore for an account private key.
+///
+/// The account seed is encrypted with ChaCha20-Poly1305, under a key derived from the password with scrypt.
+/// The keystore also records the account address in plaintext, and a checksum over its contents.
+/// This allows a corrupted keystore to be told apart from an incorrect password.
+///
+#[derive(Derivative, Serialize, Deserialize)]
+#[derivative(
+    Clone(bound = "N: Network"),
+    Debug(bound = "N: Network"),
+    PartialEq(bound = "N: Network"),
+    Eq(bound = "N: Network")
+)]
+#[serde(bound = "")]
+pub struct Keystore<N: Network> {
+    version: u16,
+    address: Address<N>,
+    kdf: String,
+    kdf_parameters: ScryptParameters,
+    salt: String,
+    cipher: String,
+    nonce: String,
+    ciphertext: String,
+    checksum: String,
+}
+
+/// The decoded contents of a keystore.
+struct KeystoreContents {
+    salt: Vec<u8>,
+    nonce: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+impl<N: Network> Keystore<N> {
+    /// Encrypts the given private key under the given password, with the default scrypt parameters.
+    pub fn new<R: Rng + CryptoRng>(
+        private_key: &PrivateKey<N>,
+        password: &str,
+        rng: &mut R,
+    ) -> Result<Self, AccountError> {
+        Self::with_parameters(private_key, password, ScryptParameters::default(), rng)
+    }
+
+    /// Encrypts the given private key under the given password, with the given scrypt parameters.
+    pub fn with_parameters<R: Rng + CryptoRng>(
+        private_key: &PrivateKey<N>,
+        password: &str,
+        kdf_parameters: ScryptParameters,
+        rng: &mut R,
+    ) -> Result<Self, AccountError> {
+        let salt: [u8; SALT_SIZE_IN_BYTES] = rng.gen();
+        let nonce: [u8; NONCE_SIZE_IN_BYTES] = rng.gen();
+
+        // Encrypt the account seed.
+        let key = Self::derive_key(password, &salt, &kdf_parameters)?;
+        let plaintext = Zeroizing::new(private_key.seed.to_bytes_le()?);
+        let ciphertext = ChaCha20Poly1305::new(Key::from_slice(&key[..]))
+            .encrypt(Nonce::from_slice(&nonce), &plaintext[..])
+            .map_err(|_| AccountError::Message("Failed to encrypt the account seed".to_string()))?;
+
+        let mut keystore = Self {
+            version: KEYSTORE_VERSION,
+            address: private_key.to_address(),
+            kdf: KEYSTORE_KDF.to_string(),
+            kdf_parameters,
+            salt: hex::encode(salt),
+            cipher: KEYSTORE_CIPHER.to_string(),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+            checksum: String::new(),
+        };
+        keystore.checksum = keystore.compute_checksum()?;
+        Ok(keystore)
+    }
+
+    /// Returns the version of the keystore format.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Returns the address of the encrypted account.
+    pub fn address(&self) -> Address<N> {
+        self.address
+    }
+
+    /// Returns the scrypt parameters of the keystore.
+    pub fn kdf_parameters(&self) -> &ScryptParameters {
+        &self.kdf_parameters
+    }
+
+    /// Decrypts the private key with the given password.
+    ///
+    /// Returns `AccountError::IncorrectPassword` if the password is incorrect,
+    /// and `AccountError::InvalidKeystore` if the keystore is corrupted.
+    pub fn to_private_key(&self, password: &str) -> Result<PrivateKey<N>, AccountError> {
+        let KeystoreContents { salt, nonce, ciphertext } = self.decode()?;
+
+        // As the contents match the checksum, a failed decryption is due to the password.
+        let key = Self::derive_key(password, &salt, &self.kdf_parameters)?;
+        let plaintext = Zeroizing::new(
+            ChaCha20Poly1305::new(Key::from_slice(&key[..]))
+                .decrypt(Nonce::from_slice(&nonce), &ciphertext[..])
+                .map_err(|_| AccountError::IncorrectPassword)?,
+        );
+
+        let mut seed = N::AccountSeed::read_le(&plaintext[..])
+            .map_err(|_| AccountError::InvalidKeystore("invalid account seed".to_string()))?;
+        let private_key = PrivateKey::from(&seed);
+        seed.zeroize();
+
+        match private_key.to_address() == self.address {
+            true => Ok(private_key),
+            false => Err(AccountError::InvalidKeystore("mismatching address".to_string())),
+        }
+    }
+
+    /// Returns a new keystore for the same private key, encrypted under the new password.
+    pub fn change_password<R: Rng + CryptoRng>(
+        &self,
+        old_password: &str,
+        new_password: &str,
+        rng: &mut R,
+    ) -> Result<Self, AccountError> {
+        let private_key = self.to_private_key(old_password)?;
+        Self::with_parameters(&private_key, new_password, self.kdf_parameters.clone(), rng)
+    }
+
+    /// Returns the salt, nonce, and ciphertext of the keystore, after checking its contents are well-formed.
+    fn decode(&self) -> Result<KeystoreContents, AccountError> {
+        let invalid = |message: &str| AccountError::InvalidKeystore(message.to_string());
+
+        if self.version != KEYSTORE_VERSION {
+            return Err(invalid(&format!("unsupported version {}", self.version)));
+        }
+        if self.kdf != KEYSTORE_KDF {
+            return Err(invalid(&format!("unsupported kdf {}", self.kdf)));
+        }
+        if self.cipher != KEYSTORE_CIPHER {
+            return Err(invalid(&format!("unsupported cipher {}", self.cipher)));
+        }
+        if self.kdf_parameters.log_n > MAXIMUM_SCRYPT_LOG_N {
+            return Err(invalid(&format!("scrypt cost exceeds 2^{}", MAXIMUM_SCRYPT_LOG_N)));
+        }
+        if self.kdf_parameters.work() > MAXIMUM_SCRYPT_WORK {
+            return Err(invalid(&format!("scrypt work exceeds {}", MAXIMUM_SCRYPT_WORK)));
+        }
+
+        let salt = hex::decode(&self.salt).map_err(|_| invalid("invalid salt"))?;
+        let nonce = hex::decode(&self.nonce).map_err(|_| invalid("invalid nonce"))?;
+        let ciphertext = hex::decode(&self.ciphertext).map_err(|_| invalid("invalid ciphertext"))?;
+        if salt.len() != SALT_SIZE_IN_BYTES
+            || nonce.len() != NONCE_SIZE_IN_BYTES
+            || ciphertext.len() <= TAG_SIZE_IN_BYTES
+        {
+            return Err(invalid("invalid length"));
+        }
+
+        match self.compute_checksum()? == self.checksum {
+            true => Ok(KeystoreContents { salt, nonce, ciphertext }),
+            false => Err(invalid("mismatching checksum")),
+        }
+    }
+
+    /// Returns the checksum over the contents of the keystore.
+    fn compute_checksum(&self) -> Result<String, AccountError> {
+        let mut hasher = Blake2s::new();
+        hasher.update(self.version.to_le_bytes());
+        hasher.update(self.address.to_bytes_le()?);
+        hasher.update(self.kdf.as_bytes());
+        hasher.update([self.kdf_parameters.log_n]);
+        hasher.update(self.kdf_parameters.r.to_le_bytes());
+        hasher.update(self.kdf_parameters.p.to_le_bytes());
+        hasher.update(self.salt.as_bytes());
+        hasher.update(self.cipher.as_bytes());
+        hasher.update(self.nonce.as_bytes());
+        hasher.update(self.ciphertext.as_bytes());
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    /// Returns the encryption key for the given password, derived with scrypt.
+    fn derive_key(
+        password: &str,
+        salt: &[u8],
+        kdf_parameters: &ScryptParameters,
+    ) -> Result<Zeroizing<[u8; 32]>, AccountError> {
+        let ScryptParameters { log_n, r, p } = *kdf_parameters;
+        let parameters = scrypt::Params::new(log_n, r, p)
+            .map_err(|_| AccountError::InvalidKeystore("invalid scrypt parameters".to_string()))?;
+
+        let mut key = Zeroizing::new([0u8; 32]);
+        scrypt::scrypt(password.as_bytes(), salt, &parameters, &mut key[..])
+            .map_err(|_| AccountError::Message("Failed to derive the keystore key".to_string()))?;
+        Ok(key)
+    }
+}
+
+impl<N: Network> FromStr for Keystore<N> {
+    type Err = AccountError;
+
+    /// Reads in a keystore JSON string, and checks it is well-formed.
+    fn from_str(keystore: &str) -> Result<Self, Self::Err> {
+        let keystore: Self =
+            serde_json::from_str(keystore).map_err(|error| AccountError::InvalidKeystore(error.to_string()))?;
+        keystore.decode()?;
+        Ok(keystore)
+    }
+}
+
+impl<N: Network> fmt::Display for Keystore<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(self).map_err(|_| fmt::Error)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testnet2::Testnet2;
+
+    use rand::thread_rng;
+
+    const PASSWORD: &str = "correct horse battery staple";
+
+    /// Lighter scrypt parameters, to keep the tests fast.
+    fn test_parameters() -> ScryptParameters {
+        ScryptParameters { log_n: 10, r: 8, p: 1 }
+    }
+
+    fn sample_keystore() -> (PrivateKey<Testnet2>, Keystore<Testnet2>) {
+        let rng = &mut thread_rng();
+        let private_key = PrivateKey::new(rng);
+        let keystore = Keystore::with_parameters(&private_key, PASSWORD, test_parameters(), rng).unwrap();
+        (private_key, keystore)
+    }
+
+    #[test]
+    fn test_keystore_round_trip() {
+        let rng = &mut thread_rng();
+        let private_key = PrivateKey::<Testnet2>::new(rng);
+        let keystore = Keystore::new(&private_key, PASSWORD, rng).unwrap();
+        assert_eq!(KEYSTORE_VERSION, keystore.version());
+        assert_eq!(&ScryptParameters::default(), keystore.kdf_parameters());
+        assert_eq!(private_key.to_address(), keystore.address());
+
+        // Check the keystore is recovered from its string representation.
+        let candidate = Keystore::<Testnet2>::from_str(&keystore.to_string()).unwrap();
+        assert_eq!(keystore, candidate);
+        assert_eq!(private_key, candidate.to_private_key(PASSWORD).unwrap());
+    }
+
+    #[test]
+    fn test_keystore_json() {
+        let (private_key, keystore) = sample_keystore();
+
+        let json = serde_json::Value::from_str(&keystore.to_string()).unwrap();
+        assert_eq!(KEYSTORE_VERSION as u64, json["version"].as_u64().unwrap());
+        assert_eq!(private_key.to_address().to_string(), json["address"].as_str().unwrap());
+        assert_eq!(KEYSTORE_KDF, json["kdf"].as_str().unwrap());
+        assert_eq!(KEYSTORE_CIPHER, json["cipher"].as_str().unwrap());
+
+        // Check the private key does not appear in the keystore.
+        assert!(!keystore.to_string().contains(&private_key.to_string()));
+        assert!(!keystore
+            .to_string()
+            .contains(&hex::encode(private_key.seed.to_bytes_le().unwrap())));
+    }
+
+    #[test]
+    fn test_keystore_incorrect_password() {
+        let (_, keystore) = sample_keystore();
+
+        assert!(matches!(
+            keystore.to_private_key(""),
+            Err(AccountError::IncorrectPassword)
+        ));
+        assert!(matches!(
+            keystore.to_private_key("correct horse battery stapler"),
+            Err(AccountError::IncorrectPassword)
+        ));
+    }
+
+    #[test]
+    fn test_keystore_corrupted() {
+        let (_, keystore) = sample_keystore();
+        let json = keystore.to_string();
+
+        // Check a truncated keystore.
+        assert!(matches!(
+            Keystore::<Testnet2>::from_str(&json[..json.len() / 2]),
+            Err(AccountError::InvalidKeystore(_))
+        ));
+
+        // Check a modified ciphertext is detected by the checksum, rather than reported as an incorrect password.
+        let mut corrupted = keystore.clone();
+        let last = corrupted.ciphertext.pop().unwrap();
+        corrupted.ciphertext.push(if last == '0' { '1' } else { '0' });
+        assert!(matches!(
+            corrupted.to_private_key(PASSWORD),
+            Err(AccountError::InvalidKeystore(_))
+        ));
+        assert!(matches!(
+            Keystore::<Testnet2>::from_str(&corrupted.to_string()),
+            Err(AccountError::InvalidKeystore(_))
+        ));
+
+        // Check an unsupported version.
+        let mut corrupted = keystore.clone();
+        corrupted.version = KEYSTORE_VERSION + 1;
+        assert!(matches!(
+            corrupted.to_private_key(PASSWORD),
+            Err(AccountError::InvalidKeystore(_))
+        ));
+
+        // Check a replaced address, even with a recomputed checksum.
+        let mut corrupted = keystore;
+        corrupted.address = PrivateKey::<Testnet2>::new(&mut thread_rng()).to_address();
+        corrupted.checksum = corrupted.compute_checksum().unwrap();
+        assert!(matches!(
+            corrupted.to_private_key(PASSWORD),
+            Err(AccountError::InvalidKeystore(_))
+        ));
+    }
+
+    #[test]
+    fn test_keystore_oversized_scrypt_parameters() {
+        let (_, keystore) = sample_keystore();
+
+        // Check each oversized parameter is rejected before deriving the key, even with a recomputed checksum.
+        for kdf_parameters in [
+            ScryptParameters { log_n: 21, r: 8, p: 1 },
+            ScryptParameters { log_n: 10, r: u32::MAX, p: 1 },
+            ScryptParameters { log_n: 10, r: 8, p: u32::MAX },
+            ScryptParameters { log_n: 20, r: 9, p: 1 },
+        ] {
+            let mut oversized = keystore.clone();
+            oversized.kdf_parameters = kdf_parameters;
+            oversized.checksum = oversized.compute_checksum().unwrap();
+            assert!(matches!(
+                oversized.to_private_key(PASSWORD),
+                Err(AccountError::InvalidKeystore(_))
+            ));
+            assert!(matches!(
+                Keystore::<Testnet2>::from_str(&oversized.to_string()),
+                Err(AccountError::InvalidKeystore(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_keystore_change_password() {
+        let rng = &mut thread_rng();
+        let (private_key, keystore) = sample_keystore();
+
+        // Check the old password is required.
+        assert!(matches!(
+            keystore.change_password("", "new password", rng),
+            Err(AccountError::IncorrectPassword)
+        ));
+
+        let candidate = keystore.change_password(PASSWORD, "new password", rng).unwrap();
+        assert_eq!(keystore.address(), candidate.address());
+        assert_eq!(keystore.kdf_parameters(), candidate.kdf_parameters());
+        assert_ne!(keystore.ciphertext, candidate.ciphertext);
+        assert!(matches!(
+            candidate.to_private_key(PASSWORD),
+            Err(AccountError::IncorrectPassword)
+        ));
+        assert_eq!(private_key, candidate.to_private_key("new password").unwrap());
+    }
+}
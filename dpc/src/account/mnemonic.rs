@@ -0,0 +1,320 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{AccountError, Network};
+use snarkvm_fields::PrimeField;
+use snarkvm_utilities::{FromBytes, ToBytes};
+
+use hmac::Hmac;
+use once_cell::sync::Lazy;
+use rand::{CryptoRng, Rng};
+use sha2::{Digest, Sha256, Sha512};
+use std::{fmt, str::FromStr};
+use unicode_normalization::UnicodeNormalization;
+use zeroize::{Zeroize, Zeroizing};
+
+/// The English wordlist of BIP-39, sorted in lexicographic order.
+static WORDLIST: Lazy<Vec<&'static str>> =
+    Lazy::new(|| include_str!("wordlists/english.txt").split_whitespace().collect());
+
+/// The number of bits encoded by each word of a mnemonic phrase.
+const BITS_PER_WORD: usize = 11;
+/// The number of PBKDF2 rounds used to stretch a mnemonic phrase into a seed.
+const PBKDF2_ROUNDS: u32 = 2048;
+
+///
+/// A BIP-39 mnemonic phrase, encoding between 128 and 256 bits of entropy with a checksum.
+///
+/// The account seed is derived from the phrase and an optional passphrase, following the BIP-39
+/// seed derivation. As the derivation is one-way, an existing `PrivateKey` may not be converted
+/// into a mnemonic phrase, and a different passphrase recovers a different account.
+///
+/// The phrase and entropy are redacted from the `Debug` output, and the entropy is zeroized on drop.
+///
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mnemonic {
+    entropy: Vec<u8>,
+}
+
+impl Mnemonic {
+    /// Samples a new mnemonic phrase with the given number of words.
+    pub fn new<R: Rng + CryptoRng>(num_words: usize, rng: &mut R) -> Result<Self, AccountError> {
+        if !Self::is_valid_num_words(num_words) {
+            return Err(AccountError::InvalidMnemonicLength(num_words));
+        }
+
+        let entropy = Zeroizing::new((0..(num_words * 4 / 3)).map(|_| rng.gen()).collect::<Vec<u8>>());
+        Self::from_entropy(&entropy)
+    }
+
+    /// Initializes a mnemonic phrase from the given entropy, which must be 16, 20, 24, 28, or 32 bytes.
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, AccountError> {
+        if !(16..=32).contains(&entropy.len()) || entropy.len() % 4 != 0 {
+            return Err(AccountError::InvalidEntropyLength(entropy.len()));
+        }
+
+        Ok(Self {
+            entropy: entropy.to_vec(),
+        })
+    }
+
+    /// Returns the entropy encoded by the mnemonic phrase.
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    /// Returns the words of the mnemonic phrase.
+    pub fn words(&self) -> Vec<&'static str> {
+        // Append the checksum bits to the entropy bits.
+        let checksum = Self::checksum(&self.entropy);
+        let num_checksum_bits = self.entropy.len() / 4;
+        let bits = to_bits(&self.entropy)
+            .chain(to_bits(&[checksum]).take(num_checksum_bits))
+            .collect::<Vec<_>>();
+
+        bits.chunks(BITS_PER_WORD)
+            .map(|chunk| WORDLIST[chunk.iter().fold(0, |index, bit| (index << 1) | *bit as usize)])
+            .collect()
+    }
+
+    /// Returns the seed of the mnemonic phrase, as defined in BIP-39.
+    pub fn to_seed(&self, passphrase: &str) -> [u8; 64] {
+        let phrase = Zeroizing::new(self.to_string());
+        let salt = Zeroizing::new(format!("mnemonic{}", passphrase).nfkd().collect::<String>());
+
+        let mut seed = [0u8; 64];
+        pbkdf2::pbkdf2::<Hmac<Sha512>>(phrase.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
+        seed
+    }
+
+    /// Returns the account seed of the mnemonic phrase, for the given passphrase.
+    pub fn to_account_seed<N: Network>(&self, passphrase: &str) -> Result<N::AccountSeed, AccountError> {
+        let seed = N::ProgramScalarField::from_bytes_le_mod_order(&Zeroizing::new(self.to_seed(passphrase))[..]);
+        Ok(FromBytes::read_le(&seed.to_bytes_le()?[..])?)
+    }
+
+    /// Returns `true` if a mnemonic phrase may have the given number of words.
+    fn is_valid_num_words(num_words: usize) -> bool {
+        (12..=24).contains(&num_words) && num_words % 3 == 0
+    }
+
+    /// Returns the checksum byte of the given entropy.
+    fn checksum(entropy: &[u8]) -> u8 {
+        Sha256::digest(entropy)[0]
+    }
+}
+
+/// Returns the bits of the given bytes, in big-endian order.
+fn to_bits(bytes: &[u8]) -> impl Iterator<Item = bool> + '_ {
+    bytes
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+}
+
+impl Drop for Mnemonic {
+    fn drop(&mut self) {
+        self.entropy.zeroize();
+    }
+}
+
+impl FromStr for Mnemonic {
+    type Err = AccountError;
+
+    /// Reads in a mnemonic phrase, and checks its checksum.
+    fn from_str(phrase: &str) -> Result<Self, Self::Err> {
+        let phrase = Zeroizing::new(phrase.nfkd().collect::<String>());
+        let words = phrase.split_whitespace().collect::<Vec<_>>();
+        if !Self::is_valid_num_words(words.len()) {
+            return Err(AccountError::InvalidMnemonicLength(words.len()));
+        }
+
+        // Recover the bits encoded by the words.
+        let mut bits = Zeroizing::new(Vec::with_capacity(words.len() * BITS_PER_WORD));
+        for word in words {
+            let index = WORDLIST
+                .binary_search(&word)
+                .map_err(|_| AccountError::InvalidMnemonicWord(word.to_string()))?;
+            bits.extend((0..BITS_PER_WORD).rev().map(|i| (index >> i) & 1 == 1));
+        }
+
+        // Split the bits into the entropy and the checksum.
+        let num_entropy_bits = bits.len() * 32 / 33;
+        let (entropy_bits, checksum_bits) = bits.split_at(num_entropy_bits);
+        let entropy = Zeroizing::new(
+            entropy_bits
+                .chunks(8)
+                .map(|chunk| chunk.iter().fold(0u8, |byte, bit| (byte << 1) | *bit as u8))
+                .collect::<Vec<_>>(),
+        );
+
+        // Ensure the checksum matches the entropy.
+        let expected_checksum_bits = to_bits(&[Self::checksum(&entropy)])
+            .take(checksum_bits.len())
+            .collect::<Vec<_>>();
+        if checksum_bits != expected_checksum_bits.as_slice() {
+            return Err(AccountError::InvalidMnemonicChecksum);
+        }
+
+        Self::from_entropy(&entropy)
+    }
+}
+
+impl fmt::Display for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.words().join(" "))
+    }
+}
+
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Mnemonic {{ num_words: {} }}", self.entropy.len() * 3 / 4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testnet2::Testnet2, PrivateKey};
+
+    use rand::thread_rng;
+
+    const PASSPHRASE: &str = "TREZOR";
+
+    /// The (entropy, mnemonic, seed) test vectors of BIP-39, for the passphrase "TREZOR".
+    const TEST_VECTORS: [(&str, &str, &str); 4] = [
+        (
+            "00000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+        ),
+        (
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+        ),
+        (
+            "9e885d952ad362caeb4efe34a8e91bd2",
+            "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic",
+            "274ddc525802f7c828d8ef7ddbcdc5304e87ac3535913611fbbfa986d0c9e5476c91689f9c8a54fd55bd38606aa6a8595ad213d4c9c9f9aca3fb217069a41028",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+            "dd48c104698c30cfe2b6142103248622fb7bb0ff692eebb00089b32d22484e1613912f0a5b694407be899ffd31ed3992c456cdf60f5d4564b8ba3f05a69890ad",
+        ),
+    ];
+
+    #[test]
+    fn test_wordlist() {
+        assert_eq!(2048, WORDLIST.len());
+        assert!(WORDLIST.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_mnemonic_test_vectors() {
+        for (entropy, phrase, seed) in TEST_VECTORS.iter() {
+            let mnemonic = Mnemonic::from_entropy(&hex::decode(entropy).unwrap()).unwrap();
+            assert_eq!(*phrase, mnemonic.to_string());
+            assert_eq!(*seed, hex::encode(mnemonic.to_seed(PASSPHRASE)));
+
+            let candidate = Mnemonic::from_str(phrase).unwrap();
+            assert_eq!(mnemonic, candidate);
+            assert_eq!(*entropy, hex::encode(candidate.entropy()));
+        }
+    }
+
+    #[test]
+    fn test_mnemonic_round_trip() {
+        let rng = &mut thread_rng();
+
+        for num_words in [12, 15, 18, 21, 24] {
+            let mnemonic = Mnemonic::new(num_words, rng).unwrap();
+            assert_eq!(num_words, mnemonic.words().len());
+            assert_eq!(mnemonic, Mnemonic::from_str(&mnemonic.to_string()).unwrap());
+
+            // Check the recovered account matches the original account.
+            let private_key = PrivateKey::<Testnet2>::from_mnemonic(&mnemonic, PASSPHRASE).unwrap();
+            let candidate = Mnemonic::from_str(&mnemonic.to_string()).unwrap();
+            assert_eq!(private_key, PrivateKey::from_mnemonic(&candidate, PASSPHRASE).unwrap());
+
+            // Check a different passphrase recovers a different account.
+            assert_ne!(private_key, PrivateKey::from_mnemonic(&candidate, "").unwrap());
+        }
+    }
+
+    #[test]
+    fn test_mnemonic_account_seed() {
+        let mnemonic = Mnemonic::from_str(TEST_VECTORS[0].1).unwrap();
+        let seed = mnemonic.to_account_seed::<Testnet2>(PASSPHRASE).unwrap();
+        assert_eq!(
+            PrivateKey::<Testnet2>::from(&seed),
+            PrivateKey::from_mnemonic(&mnemonic, PASSPHRASE).unwrap()
+        );
+        assert_eq!(
+            "APrivateKey1zkp66EdbdaeA4GWq2KQ1nP6GGw18jt6gksi1278m4JmnDUE",
+            PrivateKey::<Testnet2>::from(&seed).to_string()
+        );
+    }
+
+    #[test]
+    fn test_mnemonic_debug_is_redacted() {
+        let mnemonic = Mnemonic::from_str(TEST_VECTORS[0].1).unwrap();
+        let debug = format!("{:?}", mnemonic);
+        assert_eq!("Mnemonic { num_words: 12 }", debug);
+        assert!(mnemonic.words().iter().all(|word| !debug.contains(word)));
+    }
+
+    #[test]
+    fn test_mnemonic_invalid() {
+        let rng = &mut thread_rng();
+
+        // Check the number of words and the entropy length.
+        assert!(matches!(
+            Mnemonic::new(13, rng),
+            Err(AccountError::InvalidMnemonicLength(13))
+        ));
+        assert!(matches!(
+            Mnemonic::new(27, rng),
+            Err(AccountError::InvalidMnemonicLength(27))
+        ));
+        assert!(matches!(
+            Mnemonic::from_entropy(&[0u8; 15]),
+            Err(AccountError::InvalidEntropyLength(15))
+        ));
+        assert!(matches!(
+            Mnemonic::from_entropy(&[0u8; 36]),
+            Err(AccountError::InvalidEntropyLength(36))
+        ));
+        assert!(matches!(
+            Mnemonic::from_str(""),
+            Err(AccountError::InvalidMnemonicLength(0))
+        ));
+
+        // Check an unknown word.
+        let phrase = TEST_VECTORS[0].1.replace("about", "aleo");
+        assert!(matches!(
+            Mnemonic::from_str(&phrase),
+            Err(AccountError::InvalidMnemonicWord(_))
+        ));
+
+        // Check a mistyped word fails the checksum.
+        let phrase = TEST_VECTORS[0].1.replace("about", "above");
+        assert!(matches!(
+            Mnemonic::from_str(&phrase),
+            Err(AccountError::InvalidMnemonicChecksum)
+        ));
+    }
+}
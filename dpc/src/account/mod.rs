@@ -28,6 +28,15 @@ pub use address::*;
 pub mod compute_key;
 pub use compute_key::*;
 
+pub mod derivation;
+pub use derivation::*;
+
+pub mod keystore;
+pub use keystore::*;
+
+pub mod mnemonic;
+pub use mnemonic::*;
+
 pub mod private_key;
 pub use private_key::*;
 
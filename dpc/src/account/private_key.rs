@@ -16,9 +16,12 @@
 
 use crate::{
     account_format,
+    derive_child_seed,
     AccountError,
     Address,
     ComputeKey,
+    DerivationPath,
+    Mnemonic,
     Network,
     ACCOUNT_SEED_R_SIG_DOMAIN,
     ACCOUNT_SEED_SK_SIG_DOMAIN,
@@ -30,6 +33,7 @@ use snarkvm_utilities::{FromBytes, ToBytes, UniformRand};
 use base58::{FromBase58, ToBase58};
 use rand::{CryptoRng, Rng};
 use std::{fmt, str::FromStr};
+use zeroize::Zeroize;
 
 #[derive(Derivative)]
 #[derivative(
@@ -38,7 +42,7 @@ use std::{fmt, str::FromStr};
     Eq(bound = "N: Network")
 )]
 pub struct PrivateKey<N: Network> {
-    seed: N::AccountSeed,
+    pub(super) seed: N::AccountSeed,
     pub(super) sk_sig: N::ProgramScalarField,
     pub(super) r_sig: N::ProgramScalarField,
 }
@@ -50,6 +54,21 @@ impl<N: Network> PrivateKey<N> {
         Self::from(&N::AccountSeed::rand(rng))
     }
 
+    /// Recovers the account private key from a mnemonic phrase and an optional passphrase.
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str) -> Result<Self, AccountError> {
+        Ok(Self::from(&mnemonic.to_account_seed::<N>(passphrase)?))
+    }
+
+    /// Derives the account private key at the given derivation path, from a master account seed.
+    pub fn derive(master_seed: &N::AccountSeed, path: &DerivationPath) -> Result<Self, AccountError> {
+        Ok(Self::from(&path.derive_seed::<N>(master_seed)?))
+    }
+
+    /// Derives the account private key of the child at the given index, from this private key.
+    pub fn derive_child(&self, index: u32) -> Result<Self, AccountError> {
+        Ok(Self::from(&derive_child_seed::<N>(&self.seed, index)?))
+    }
+
     /// Returns `true` if the private key is well-formed. Otherwise, returns `false`.
     pub fn is_valid(&self) -> bool {
         self.to_compute_key().is_valid()
@@ -99,6 +118,21 @@ impl<N: Network> From<&N::AccountSeed> for PrivateKey<N> {
     }
 }
 
+impl<N: Network> Zeroize for PrivateKey<N> {
+    /// Overwrites the account seed and the private key components with zeros.
+    fn zeroize(&mut self) {
+        self.seed.zeroize();
+        self.sk_sig.zeroize();
+        self.r_sig.zeroize();
+    }
+}
+
+impl<N: Network> Drop for PrivateKey<N> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 impl<N: Network> FromStr for PrivateKey<N> {
     type Err = AccountError;
 
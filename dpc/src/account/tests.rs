@@ -16,19 +16,53 @@
 
 #[cfg(test)]
 mod testnet1 {
-    use crate::{testnet1::Testnet1, Account, AccountScheme, Address, Network, PrivateKey, ViewKey};
+    use crate::{
+        testnet1::Testnet1,
+        Account,
+        AccountError,
+        AccountScheme,
+        Address,
+        ComputeKey,
+        DerivationPath,
+        Network,
+        PrivateKey,
+        ViewKey,
+    };
     use snarkvm_algorithms::prelude::*;
     use snarkvm_curves::AffineCurve;
-    use snarkvm_utilities::{FromBytes, ToBytes};
+    use snarkvm_utilities::{FromBytes, ToBytes, UniformRand};
 
     use rand::{thread_rng, Rng, SeedableRng};
     use rand_chacha::ChaChaRng;
     use std::{convert::TryInto, str::FromStr};
 
     const ALEO_TESTNET1_PRIVATE_KEY: &str = "APrivateKey1zkp8cC4jgHEBnbtu3xxs1Ndja2EMizcvTRDq5Nikdkukg1p";
+    const ALEO_TESTNET1_COMPUTE_KEY: &str =
+        "AComputeKey11GfKYC1WTpy9ZPMbEtR8VGyu3wyeyTAX5gkXdETSKbUJ4NdJCvzgomzV9BTDM5MCNRbPPWP8hSaob6nLxsiGPKrhjzntbo";
     const ALEO_TESTNET1_VIEW_KEY: &str = "AViewKey1iAf6a7fv6ELA4ECwAth1hDNUJJNNoWNThmREjpybqder";
     const ALEO_TESTNET1_ADDRESS: &str = "aleo1d5hg2z3ma00382pngntdp68e74zv54jdxy249qhaujhks9c72yrs33ddah";
 
+    const ALEO_TESTNET1_DERIVATION_VECTORS: [(&str, &str, &str, &str); 3] = [
+        (
+            "m/0",
+            "APrivateKey1zkpG88wvcQPAhTvzd2NRgtXzq6qPYhoGKetXs4ekvpegoJh",
+            "AViewKey1iSeysw3BUAprh4o8k8SrZsTi1WBXDBVMukbEHyT6My7P",
+            "aleo1zzh8farqghtnpt8cmhlkmdlwcdfut5gcq4yq37y6mjjytsp9ayrs67cklc",
+        ),
+        (
+            "m/1",
+            "APrivateKey1zkp7YN8VNPxYttXtHYW7zbZSthemVmkGW5Hxna5VBsAgeQs",
+            "AViewKey1mw9tTJj1Q3mp5HR2ueWnfe3HBSaZRx1EWMR7UMxmqdNK",
+            "aleo1nv9wkd2lkrpyuarq88h3mtmcgmadntw3yvm5ueypgravxff4agyshtajqy",
+        ),
+        (
+            "m/0/1",
+            "APrivateKey1zkpAakLR8FomnXiRXLCooHGUeW69am3GrEs6FySCSYAn9KP",
+            "AViewKey1dKYAxYpcoJ6ioQPpxtJ9hPQWy2gbEYMt2LsWgpGaTsYs",
+            "aleo1dzf8pje2mj9wfra8lhwtpjpmcagsl09syn68q5txvsqyyr599vpskz3h9a",
+        ),
+    ];
+
     const ITERATIONS: usize = 1000;
 
     #[test]
@@ -68,6 +102,70 @@ mod testnet1 {
         assert_eq!(ALEO_TESTNET1_ADDRESS, address.to_string());
     }
 
+    #[test]
+    fn test_hierarchical_derivation() {
+        let mut rng = ChaChaRng::seed_from_u64(1231275789u64);
+        let master_seed = <Testnet1 as Network>::AccountSeed::rand(&mut rng);
+
+        // Check the master path derives the master account.
+        let master = PrivateKey::<Testnet1>::derive(&master_seed, &DerivationPath::master()).unwrap();
+        assert_eq!(ALEO_TESTNET1_PRIVATE_KEY, master.to_string());
+
+        // Check the seeded derivations match the hardcoded values, as a sanity check.
+        for (path, expected_private_key, expected_view_key, expected_address) in &ALEO_TESTNET1_DERIVATION_VECTORS {
+            let path = DerivationPath::from_str(path).unwrap();
+            let private_key = PrivateKey::<Testnet1>::derive(&master_seed, &path).unwrap();
+            let view_key = ViewKey::<Testnet1>::derive(&master_seed, &path).unwrap();
+            assert_eq!(*expected_private_key, private_key.to_string());
+            assert_eq!(*expected_view_key, view_key.to_string());
+            assert_eq!(*expected_address, private_key.to_address().to_string());
+            assert!(private_key.is_valid());
+        }
+    }
+
+    #[test]
+    fn test_derivation_path_from_str() {
+        for path in ["m", "m/0", "m/0/1", "m/4294967295/7"] {
+            assert_eq!(path, DerivationPath::from_str(path).unwrap().to_string());
+        }
+        assert_eq!(&[0, 1], DerivationPath::from_str("m/0/1").unwrap().indices());
+        assert_eq!(DerivationPath::master(), DerivationPath::from_str("m").unwrap());
+
+        assert!(DerivationPath::from_str("").is_err());
+        assert!(DerivationPath::from_str("0/1").is_err());
+        assert!(DerivationPath::from_str("m/").is_err());
+        assert!(DerivationPath::from_str("m/0'").is_err());
+        assert!(DerivationPath::from_str("m/+1").is_err());
+        assert!(DerivationPath::from_str("m/-1").is_err());
+        assert!(DerivationPath::from_str("m/4294967296").is_err());
+        assert!(DerivationPath::new(vec![0; 256]).is_err());
+    }
+
+    #[test]
+    fn test_hierarchical_derivation_child() {
+        let mut rng = ChaChaRng::seed_from_u64(1231275789u64);
+        let master_seed = <Testnet1 as Network>::AccountSeed::rand(&mut rng);
+        let master = PrivateKey::<Testnet1>::derive(&master_seed, &DerivationPath::master()).unwrap();
+
+        // Check deriving one index at a time matches deriving the full path.
+        let path = DerivationPath::from_str("m/7/0/4294967295").unwrap();
+        let candidate = master
+            .derive_child(7)
+            .unwrap()
+            .derive_child(0)
+            .unwrap()
+            .derive_child(u32::MAX)
+            .unwrap();
+        assert_eq!(PrivateKey::<Testnet1>::derive(&master_seed, &path).unwrap(), candidate);
+
+        // Check sibling and nested children are distinct accounts.
+        let first = master.derive_child(0).unwrap();
+        let second = master.derive_child(1).unwrap();
+        assert_ne!(master, first);
+        assert_ne!(first, second);
+        assert_ne!(first, first.derive_child(0).unwrap());
+    }
+
     #[test]
     fn test_private_key_from_str() {
         let private_key = PrivateKey::<Testnet1>::from_str(ALEO_TESTNET1_PRIVATE_KEY).unwrap();
@@ -120,6 +218,53 @@ mod testnet1 {
         assert_eq!(ALEO_TESTNET1_ADDRESS, address.to_string());
     }
 
+    #[test]
+    fn test_compute_key_from_str() {
+        let private_key = PrivateKey::<Testnet1>::from_str(ALEO_TESTNET1_PRIVATE_KEY).unwrap();
+        assert_eq!(ALEO_TESTNET1_COMPUTE_KEY, private_key.to_compute_key().to_string());
+
+        let compute_key = ComputeKey::<Testnet1>::from_str(ALEO_TESTNET1_COMPUTE_KEY).unwrap();
+        assert_eq!(private_key.to_compute_key(), compute_key);
+        assert_eq!(ALEO_TESTNET1_COMPUTE_KEY, compute_key.to_string());
+    }
+
+    #[test]
+    fn test_compute_key_from_invalid_str() {
+        assert!(ComputeKey::<Testnet1>::from_str(ALEO_TESTNET1_PRIVATE_KEY).is_err());
+        assert!(ComputeKey::<Testnet1>::from_str(ALEO_TESTNET1_VIEW_KEY).is_err());
+        assert!(ComputeKey::<Testnet1>::from_str(ALEO_TESTNET1_ADDRESS).is_err());
+        assert!(ComputeKey::<Testnet1>::from_str("AComputeKey1abcdefghijklmnopqrstuvwxyz").is_err());
+        assert!(ComputeKey::<Testnet1>::from_str("AComputeKey1").is_err());
+        assert!(ComputeKey::<Testnet1>::from_str("").is_err());
+
+        // Check a mistyped character fails the checksum.
+        let mut characters = ALEO_TESTNET1_COMPUTE_KEY.chars().collect::<Vec<_>>();
+        characters[50] = if characters[50] == 'a' { 'b' } else { 'a' };
+        let mistyped = characters.into_iter().collect::<String>();
+        assert!(matches!(
+            ComputeKey::<Testnet1>::from_str(&mistyped),
+            Err(AccountError::InvalidChecksum)
+        ));
+    }
+
+    #[test]
+    fn test_compute_key_str_into_address() {
+        // Check the address is derived from the compute key string, without the private key.
+        let compute_key = ComputeKey::<Testnet1>::from_str(ALEO_TESTNET1_COMPUTE_KEY).unwrap();
+        assert!(compute_key.is_valid());
+        assert_eq!(
+            ALEO_TESTNET1_ADDRESS,
+            Address::from_compute_key(&compute_key).to_string()
+        );
+
+        // Check the round trip for sampled accounts.
+        for _ in 0..ITERATIONS / 10 {
+            let private_key = PrivateKey::<Testnet1>::new(&mut thread_rng());
+            let compute_key = ComputeKey::<Testnet1>::from_str(&private_key.to_compute_key().to_string()).unwrap();
+            assert_eq!(private_key.to_address(), Address::from_compute_key(&compute_key));
+        }
+    }
+
     #[test]
     fn test_view_key_into_address() {
         let view_key = ViewKey::<Testnet1>::from_str(ALEO_TESTNET1_VIEW_KEY).unwrap();
@@ -290,19 +435,53 @@ mod testnet1 {
 
 #[cfg(test)]
 mod testnet2 {
-    use crate::{testnet2::Testnet2, Account, AccountScheme, Address, Network, PrivateKey, ViewKey};
+    use crate::{
+        testnet2::Testnet2,
+        Account,
+        AccountError,
+        AccountScheme,
+        Address,
+        ComputeKey,
+        DerivationPath,
+        Network,
+        PrivateKey,
+        ViewKey,
+    };
     use snarkvm_algorithms::prelude::*;
     use snarkvm_curves::AffineCurve;
-    use snarkvm_utilities::{FromBytes, ToBytes};
+    use snarkvm_utilities::{FromBytes, ToBytes, UniformRand};
 
     use rand::{thread_rng, Rng, SeedableRng};
     use rand_chacha::ChaChaRng;
     use std::str::FromStr;
 
     const ALEO_TESTNET2_PRIVATE_KEY: &str = "APrivateKey1zkp8cC4jgHEBnbtu3xxs1Ndja2EMizcvTRDq5Nikdkukg1p";
+    const ALEO_TESTNET2_COMPUTE_KEY: &str =
+        "AComputeKey11GfKYC1WTpy9ZPMbEtR8VGyu3wyeyTAX5gkXdETSKbUJ4NdJCvzgomzV9BTDM5MCNRbPPWP8hSaob6nLxsiGPKrhjzntbo";
     const ALEO_TESTNET2_VIEW_KEY: &str = "AViewKey1iAf6a7fv6ELA4ECwAth1hDNUJJNNoWNThmREjpybqder";
     const ALEO_TESTNET2_ADDRESS: &str = "aleo1d5hg2z3ma00382pngntdp68e74zv54jdxy249qhaujhks9c72yrs33ddah";
 
+    const ALEO_TESTNET2_DERIVATION_VECTORS: [(&str, &str, &str, &str); 3] = [
+        (
+            "m/0",
+            "APrivateKey1zkpG88wvcQPAhTvzd2NRgtXzq6qPYhoGKetXs4ekvpegoJh",
+            "AViewKey1iSeysw3BUAprh4o8k8SrZsTi1WBXDBVMukbEHyT6My7P",
+            "aleo1zzh8farqghtnpt8cmhlkmdlwcdfut5gcq4yq37y6mjjytsp9ayrs67cklc",
+        ),
+        (
+            "m/1",
+            "APrivateKey1zkp7YN8VNPxYttXtHYW7zbZSthemVmkGW5Hxna5VBsAgeQs",
+            "AViewKey1mw9tTJj1Q3mp5HR2ueWnfe3HBSaZRx1EWMR7UMxmqdNK",
+            "aleo1nv9wkd2lkrpyuarq88h3mtmcgmadntw3yvm5ueypgravxff4agyshtajqy",
+        ),
+        (
+            "m/0/1",
+            "APrivateKey1zkpAakLR8FomnXiRXLCooHGUeW69am3GrEs6FySCSYAn9KP",
+            "AViewKey1dKYAxYpcoJ6ioQPpxtJ9hPQWy2gbEYMt2LsWgpGaTsYs",
+            "aleo1dzf8pje2mj9wfra8lhwtpjpmcagsl09syn68q5txvsqyyr599vpskz3h9a",
+        ),
+    ];
+
     const ITERATIONS: usize = 1000;
 
     #[test]
@@ -342,6 +521,52 @@ mod testnet2 {
         assert_eq!(ALEO_TESTNET2_ADDRESS, address.to_string());
     }
 
+    #[test]
+    fn test_hierarchical_derivation() {
+        let mut rng = ChaChaRng::seed_from_u64(1231275789u64);
+        let master_seed = <Testnet2 as Network>::AccountSeed::rand(&mut rng);
+
+        // Check the master path derives the master account.
+        let master = PrivateKey::<Testnet2>::derive(&master_seed, &DerivationPath::master()).unwrap();
+        assert_eq!(ALEO_TESTNET2_PRIVATE_KEY, master.to_string());
+
+        // Check the seeded derivations match the hardcoded values, as a sanity check.
+        for (path, expected_private_key, expected_view_key, expected_address) in &ALEO_TESTNET2_DERIVATION_VECTORS {
+            let path = DerivationPath::from_str(path).unwrap();
+            let private_key = PrivateKey::<Testnet2>::derive(&master_seed, &path).unwrap();
+            let view_key = ViewKey::<Testnet2>::derive(&master_seed, &path).unwrap();
+            assert_eq!(*expected_private_key, private_key.to_string());
+            assert_eq!(*expected_view_key, view_key.to_string());
+            assert_eq!(*expected_address, private_key.to_address().to_string());
+            assert!(private_key.is_valid());
+        }
+    }
+
+    #[test]
+    fn test_hierarchical_derivation_child() {
+        let mut rng = ChaChaRng::seed_from_u64(1231275789u64);
+        let master_seed = <Testnet2 as Network>::AccountSeed::rand(&mut rng);
+        let master = PrivateKey::<Testnet2>::derive(&master_seed, &DerivationPath::master()).unwrap();
+
+        // Check deriving one index at a time matches deriving the full path.
+        let path = DerivationPath::from_str("m/7/0/4294967295").unwrap();
+        let candidate = master
+            .derive_child(7)
+            .unwrap()
+            .derive_child(0)
+            .unwrap()
+            .derive_child(u32::MAX)
+            .unwrap();
+        assert_eq!(PrivateKey::<Testnet2>::derive(&master_seed, &path).unwrap(), candidate);
+
+        // Check sibling and nested children are distinct accounts.
+        let first = master.derive_child(0).unwrap();
+        let second = master.derive_child(1).unwrap();
+        assert_ne!(master, first);
+        assert_ne!(first, second);
+        assert_ne!(first, first.derive_child(0).unwrap());
+    }
+
     #[test]
     fn test_private_key_from_str() {
         let private_key = PrivateKey::<Testnet2>::from_str(ALEO_TESTNET2_PRIVATE_KEY).unwrap();
@@ -394,6 +619,53 @@ mod testnet2 {
         assert_eq!(ALEO_TESTNET2_ADDRESS, address.to_string());
     }
 
+    #[test]
+    fn test_compute_key_from_str() {
+        let private_key = PrivateKey::<Testnet2>::from_str(ALEO_TESTNET2_PRIVATE_KEY).unwrap();
+        assert_eq!(ALEO_TESTNET2_COMPUTE_KEY, private_key.to_compute_key().to_string());
+
+        let compute_key = ComputeKey::<Testnet2>::from_str(ALEO_TESTNET2_COMPUTE_KEY).unwrap();
+        assert_eq!(private_key.to_compute_key(), compute_key);
+        assert_eq!(ALEO_TESTNET2_COMPUTE_KEY, compute_key.to_string());
+    }
+
+    #[test]
+    fn test_compute_key_from_invalid_str() {
+        assert!(ComputeKey::<Testnet2>::from_str(ALEO_TESTNET2_PRIVATE_KEY).is_err());
+        assert!(ComputeKey::<Testnet2>::from_str(ALEO_TESTNET2_VIEW_KEY).is_err());
+        assert!(ComputeKey::<Testnet2>::from_str(ALEO_TESTNET2_ADDRESS).is_err());
+        assert!(ComputeKey::<Testnet2>::from_str("AComputeKey1abcdefghijklmnopqrstuvwxyz").is_err());
+        assert!(ComputeKey::<Testnet2>::from_str("AComputeKey1").is_err());
+        assert!(ComputeKey::<Testnet2>::from_str("").is_err());
+
+        // Check a mistyped character fails the checksum.
+        let mut characters = ALEO_TESTNET2_COMPUTE_KEY.chars().collect::<Vec<_>>();
+        characters[50] = if characters[50] == 'a' { 'b' } else { 'a' };
+        let mistyped = characters.into_iter().collect::<String>();
+        assert!(matches!(
+            ComputeKey::<Testnet2>::from_str(&mistyped),
+            Err(AccountError::InvalidChecksum)
+        ));
+    }
+
+    #[test]
+    fn test_compute_key_str_into_address() {
+        // Check the address is derived from the compute key string, without the private key.
+        let compute_key = ComputeKey::<Testnet2>::from_str(ALEO_TESTNET2_COMPUTE_KEY).unwrap();
+        assert!(compute_key.is_valid());
+        assert_eq!(
+            ALEO_TESTNET2_ADDRESS,
+            Address::from_compute_key(&compute_key).to_string()
+        );
+
+        // Check the round trip for sampled accounts.
+        for _ in 0..ITERATIONS / 10 {
+            let private_key = PrivateKey::<Testnet2>::new(&mut thread_rng());
+            let compute_key = ComputeKey::<Testnet2>::from_str(&private_key.to_compute_key().to_string()).unwrap();
+            assert_eq!(private_key.to_address(), Address::from_compute_key(&compute_key));
+        }
+    }
+
     #[test]
     fn test_view_key_into_address() {
         let view_key = ViewKey::<Testnet2>::from_str(ALEO_TESTNET2_VIEW_KEY).unwrap();
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{account_format, AccountError, Network, PrivateKey};
+use crate::{account_format, AccountError, DerivationPath, Network, PrivateKey};
 use snarkvm_algorithms::EncryptionScheme;
 use snarkvm_utilities::{FromBytes, ToBytes};
 
@@ -25,6 +25,7 @@ use std::{
     ops::Deref,
     str::FromStr,
 };
+use zeroize::Zeroize;
 
 #[derive(Derivative)]
 #[derivative(
@@ -40,6 +41,14 @@ impl<N: Network> ViewKey<N> {
     pub fn from_private_key(private_key: &PrivateKey<N>) -> Self {
         Self(private_key.to_decryption_key())
     }
+
+    /// Derives the account view key at the given derivation path, from a master account seed.
+    ///
+    /// This allows a holder of the master seed to export view keys for watch-only children.
+    /// Note that child view keys may not be derived from a parent view key, see [`DerivationPath`].
+    pub fn derive(master_seed: &N::AccountSeed, path: &DerivationPath) -> Result<Self, AccountError> {
+        Ok(Self::from_private_key(&PrivateKey::derive(master_seed, path)?))
+    }
 }
 
 impl<N: Network> From<PrivateKey<N>> for ViewKey<N> {
@@ -56,6 +65,19 @@ impl<N: Network> From<&PrivateKey<N>> for ViewKey<N> {
     }
 }
 
+impl<N: Network> Zeroize for ViewKey<N> {
+    /// Overwrites the decryption key with zeros.
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<N: Network> Drop for ViewKey<N> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 impl<N: Network> FromStr for ViewKey<N> {
     type Err = AccountError;
 
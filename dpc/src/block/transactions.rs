@@ -14,11 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{AleoAmount, BlockError, Network, Transaction};
+use crate::{AleoAmount, BlockError, Deployment, Network, Transaction};
 use snarkvm_algorithms::merkle_tree::*;
 use snarkvm_utilities::{has_duplicates, FromBytes, FromBytesDeserializer, ToBytes, ToBytesSerializer};
 
 use anyhow::{anyhow, Result};
+use rand::thread_rng;
 use rayon::prelude::*;
 use serde::{
     de,
@@ -86,12 +87,18 @@ impl<N: Network> Transactions<N> {
             .transactions
             .as_parallel_slice()
             .par_iter()
-            .all(Transaction::is_valid)
+            .all(Transaction::is_well_formed)
         {
             eprintln!("Invalid transaction found in the transactions list");
             return false;
         }
 
+        // Ensure the transition proofs of all transactions are valid.
+        if let Err(error) = self.verify_transition_proofs() {
+            eprintln!("Invalid transaction found in the transactions list: {}", error);
+            return false;
+        }
+
         // Ensure there are no duplicate serial numbers.
         if has_duplicates(self.transactions.iter().flat_map(Transaction::serial_numbers)) {
             eprintln!("Found duplicate serial numbers in the transactions list");
@@ -104,6 +111,17 @@ impl<N: Network> Transactions<N> {
             return false;
         }
 
+        // Ensure there are no duplicate program deployments.
+        if has_duplicates(
+            self.transactions
+                .iter()
+                .flat_map(Transaction::deployments)
+                .map(Deployment::program_id),
+        ) {
+            eprintln!("Found duplicate program deployments in the transactions list");
+            return false;
+        }
+
         // Ensure there is 1 coinbase transaction.
         let num_coinbase = self
             .transactions
@@ -118,6 +136,21 @@ impl<N: Network> Transactions<N> {
         true
     }
 
+    ///
+    /// Verifies the transition proofs of all transactions, by splitting the transactions
+    /// into one batch per thread and verifying the batches in parallel.
+    ///
+    /// If a batch is invalid, the returned error names the offending transaction.
+    ///
+    pub fn verify_transition_proofs(&self) -> Result<()> {
+        let num_threads = rayon::current_num_threads();
+        let batch_size = (self.transactions.len() + num_threads - 1) / num_threads;
+        self.transactions
+            .as_parallel_slice()
+            .par_chunks(batch_size.max(1))
+            .try_for_each(|batch| Transaction::verify_transition_proofs(batch, &mut thread_rng()))
+    }
+
     /// Returns the transaction IDs, by constructing a flattened list of transaction IDs from all transactions.
     pub fn transaction_ids(&self) -> impl Iterator<Item = N::TransactionID> + '_ {
         self.transactions.iter().map(Transaction::transaction_id)
@@ -182,7 +215,7 @@ impl<N: Network> FromBytes for Transactions<N> {
     #[inline]
     fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
         let num_transactions: u16 = FromBytes::read_le(&mut reader)?;
-        let mut transactions = Vec::with_capacity(num_transactions as usize);
+        let mut transactions = Vec::new();
         for _ in 0..num_transactions {
             transactions.push(FromBytes::read_le(&mut reader)?);
         }
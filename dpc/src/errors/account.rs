@@ -30,12 +30,39 @@ pub enum AccountError {
     #[error("{}", _0)]
     EncryptionError(#[from] EncryptionError),
 
+    #[error("incorrect password")]
+    IncorrectPassword,
+
     #[error("invalid byte length: {}", _0)]
     InvalidByteLength(usize),
 
     #[error("invalid character length: {}", _0)]
     InvalidCharacterLength(usize),
 
+    #[error("invalid checksum")]
+    InvalidChecksum,
+
+    #[error("invalid derivation depth: {}", _0)]
+    InvalidDerivationDepth(usize),
+
+    #[error("invalid derivation path: {}", _0)]
+    InvalidDerivationPath(String),
+
+    #[error("invalid entropy length: {}", _0)]
+    InvalidEntropyLength(usize),
+
+    #[error("invalid keystore: {}", _0)]
+    InvalidKeystore(String),
+
+    #[error("invalid mnemonic checksum")]
+    InvalidMnemonicChecksum,
+
+    #[error("invalid mnemonic length: {} words", _0)]
+    InvalidMnemonicLength(usize),
+
+    #[error("invalid mnemonic word: {}", _0)]
+    InvalidMnemonicWord(String),
+
     #[error("invalid prefix: {:?}", _0)]
     InvalidPrefix(String),
 
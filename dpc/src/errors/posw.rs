@@ -35,6 +35,10 @@ pub enum PoswError {
     #[error(transparent)]
     IoError(#[from] IoError),
 
+    /// Thrown when mining without the proving key
+    #[error("cannot mine without the PoSW proving key")]
+    MissingProvingKey,
+
     /// Thrown when the parameters cannot be loaded
     #[error("could not load PoSW parameters: {0}")]
     Parameters(#[from] ParameterError),
@@ -84,6 +84,40 @@ pub enum VMError {
 
     #[error("{}", _0)]
     SNARKError(#[from] SNARKError),
+
+    #[error("{}", _0)]
+    VirtualMachineError(#[from] VirtualMachineError),
+}
+
+/// The stage at which the virtual machine failed to self-check a transition it produced.
+#[derive(Debug, Error)]
+pub enum VirtualMachineError {
+    #[error("Inner proof for transition {transition_id} failed to verify: {reason}")]
+    InvalidInnerProof { transition_id: String, reason: String },
+
+    #[error("Program proof for function {function_id} of program {program_id} in transition {transition_id} failed to verify: {reason}")]
+    InvalidProgramProof {
+        transition_id: String,
+        program_id: String,
+        function_id: String,
+        reason: String,
+    },
+
+    #[error("Program path for function {function_id} does not lead to program {program_id}: {reason}")]
+    InvalidProgramPath {
+        program_id: String,
+        function_id: String,
+        reason: String,
+    },
+
+    #[error(
+        "Outer proof for transition {transition_id} with inner circuit {inner_circuit_id} failed to verify: {reason}"
+    )]
+    InvalidOuterProof {
+        transition_id: String,
+        inner_circuit_id: String,
+        reason: String,
+    },
 }
 
 impl From<std::io::Error> for VMError {
@@ -16,15 +16,16 @@
 
 use crate::prelude::*;
 use snarkvm_algorithms::merkle_tree::*;
+use snarkvm_utilities::{FromBytes, ToBytes};
 
 use anyhow::{anyhow, Result};
 use chrono::Utc;
-use itertools::Itertools;
-use std::collections::HashMap;
+use std::{collections::HashMap, convert::TryInto, sync::Arc};
 
 pub const TWO_HOURS_UNIX: i64 = 7200;
 
-#[derive(Clone, Debug)]
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"))]
 pub struct Blocks<N: Network> {
     /// The current block height.
     current_height: u32,
@@ -34,33 +35,111 @@ pub struct Blocks<N: Network> {
     ledger_tree: LedgerTree<N>,
     /// The chain of previous block hashes.
     previous_hashes: HashMap<u32, N::BlockHash>,
+    /// The index of block hashes to their block height.
+    block_hashes: HashMap<N::BlockHash, u32>,
     /// The chain of block headers.
     headers: HashMap<u32, BlockHeader<N>>,
+    /// The index of previous ledger roots to the height of the block header that contains them.
+    ledger_roots: HashMap<N::LedgerRoot, u32>,
     /// The chain of block transactions.
     transactions: HashMap<u32, Transactions<N>>,
+    /// The index of transaction IDs to their block height and position in the block.
+    transaction_ids: HashMap<N::TransactionID, (u32, usize)>,
+    /// The index of serial numbers to their block height, transaction ID, and transition ID.
+    serial_numbers: HashMap<N::SerialNumber, (u32, N::TransactionID, N::TransitionID)>,
+    /// The index of commitments to their block height, transaction ID, and transition ID.
+    commitments: HashMap<N::Commitment, (u32, N::TransactionID, N::TransitionID)>,
+    /// The index of deployed program IDs to their deployment.
+    deployments: HashMap<N::ProgramID, Deployment<N>>,
+    /// The storage backend for the chain.
+    #[derivative(Debug = "ignore")]
+    storage: Arc<dyn Storage>,
 }
 
 impl<N: Network> Blocks<N> {
-    /// Initializes a new instance of `Blocks` with the genesis block.
+    /// Initializes a new instance of `Blocks` with the genesis block, in memory.
     pub fn new() -> Result<Self> {
+        Self::open(Arc::new(MemoryStorage::new()))
+    }
+
+    ///
+    /// Opens an instance of `Blocks` from the given storage.
+    ///
+    /// If the storage is empty, it is initialized with the genesis block.
+    /// Otherwise, the chain is loaded from storage, and its linkage is checked.
+    ///
+    pub fn open(storage: Arc<dyn Storage>) -> Result<Self> {
         let genesis_block = N::genesis_block();
-        let height = genesis_block.height();
 
         let mut blocks = Self {
-            current_height: height,
+            current_height: genesis_block.height(),
             current_hash: genesis_block.hash(),
             ledger_tree: LedgerTree::<N>::new()?,
             previous_hashes: Default::default(),
+            block_hashes: Default::default(),
             headers: Default::default(),
+            ledger_roots: Default::default(),
             transactions: Default::default(),
+            transaction_ids: Default::default(),
+            serial_numbers: Default::default(),
+            commitments: Default::default(),
+            deployments: Default::default(),
+            storage: storage.clone(),
         };
 
-        blocks.ledger_tree.add(&genesis_block.hash())?;
-        blocks
-            .previous_hashes
-            .insert(height, genesis_block.previous_block_hash());
-        blocks.headers.insert(height, genesis_block.header().clone());
-        blocks.transactions.insert(height, genesis_block.transactions().clone());
+        let stored_block_hashes = storage.get_all(DataMap::BlockHashes)?;
+
+        // If the storage is empty, initialize it with the genesis block.
+        if stored_block_hashes.is_empty() {
+            storage.commit(Self::to_storage_batch(genesis_block)?)?;
+            blocks.ledger_tree.add(&genesis_block.hash())?;
+            blocks.insert(genesis_block);
+            return Ok(blocks);
+        }
+
+        for (expected_height, (key, value)) in stored_block_hashes.iter().enumerate() {
+            // Ensure the stored heights are contiguous from genesis.
+            let height = Self::height_from_key(key)?;
+            if height as usize != expected_height {
+                return Err(anyhow!("Missing block in storage for height {}", expected_height));
+            }
+
+            let block_hash = N::BlockHash::read_le(&value[..])?;
+            let previous_block_hash =
+                N::BlockHash::read_le(&Self::read_from_storage(&*storage, DataMap::PreviousHashes, height)?[..])?;
+            let header = BlockHeader::<N>::read_le(&Self::read_from_storage(&*storage, DataMap::Headers, height)?[..])?;
+            let transactions =
+                Transactions::<N>::read_le(&Self::read_from_storage(&*storage, DataMap::Transactions, height)?[..])?;
+
+            // Ensure the stored block links to the previously loaded block.
+            match height == 0 {
+                true => {
+                    if block_hash != genesis_block.hash() {
+                        return Err(anyhow!(
+                            "The stored genesis block does not match the network genesis block"
+                        ));
+                    }
+                }
+                false => {
+                    if previous_block_hash != blocks.current_hash {
+                        return Err(anyhow!(
+                            "The stored block at height {} has an incorrect previous block hash",
+                            height
+                        ));
+                    }
+                }
+            }
+
+            blocks.ledger_tree.add(&block_hash)?;
+            blocks.previous_hashes.insert(height, previous_block_hash);
+            blocks.block_hashes.insert(block_hash, height);
+            blocks.ledger_roots.insert(header.previous_ledger_root(), height);
+            blocks.headers.insert(height, header);
+            blocks.add_to_indexes(height, &transactions);
+            blocks.transactions.insert(height, transactions);
+            blocks.current_height = height;
+            blocks.current_hash = block_hash;
+        }
 
         Ok(blocks)
     }
@@ -156,6 +235,14 @@ impl<N: Network> Blocks<N> {
         }
     }
 
+    /// Returns the block height given the block hash.
+    pub fn get_block_height(&self, block_hash: &N::BlockHash) -> Result<u32> {
+        match self.block_hashes.get(block_hash) {
+            Some(height) => Ok(*height),
+            None => Err(anyhow!("Missing block height for block hash {}", block_hash)),
+        }
+    }
+
     /// Returns `true` if the given block height exists.
     pub fn contains_height(&self, height: u32) -> bool {
         self.previous_hashes.contains_key(&height)
@@ -165,88 +252,377 @@ impl<N: Network> Blocks<N> {
 
     /// Returns `true` if the given ledger root exists.
     pub fn contains_ledger_root(&self, ledger_root: &N::LedgerRoot) -> bool {
-        *ledger_root == self.latest_ledger_root()
-            || self
-                .headers
-                .values()
-                .map(BlockHeader::previous_ledger_root)
-                .any(|root| root == *ledger_root)
+        *ledger_root == self.latest_ledger_root() || self.ledger_roots.contains_key(ledger_root)
+    }
+
+    ///
+    /// Returns the number of blocks that have been added since the given ledger root was the latest ledger root,
+    /// or `None` if the given ledger root does not exist.
+    ///
+    pub fn get_ledger_root_age(&self, ledger_root: &N::LedgerRoot) -> Option<u32> {
+        if *ledger_root == self.latest_ledger_root() {
+            return Some(0);
+        }
+
+        // The previous ledger root in the block header at height `h` includes the blocks up to height `h - 1`.
+        self.ledger_roots
+            .get(ledger_root)
+            .map(|height| self.current_height + 1 - height)
     }
 
     /// Returns `true` if the given block hash exists.
     pub fn contains_block_hash(&self, block_hash: &N::BlockHash) -> bool {
-        self.current_hash == *block_hash || self.previous_hashes.values().any(|hash| *hash == *block_hash)
+        self.block_hashes.contains_key(block_hash)
     }
 
     /// Returns `true` if the given transaction exists.
     pub fn contains_transaction(&self, transaction: &Transaction<N>) -> bool {
-        self.transactions
-            .values()
-            .flat_map(|transactions| &**transactions)
-            .any(|tx| *tx == *transaction)
+        self.contains_transaction_id(&transaction.transaction_id())
+    }
+
+    /// Returns `true` if the given transaction ID exists.
+    pub fn contains_transaction_id(&self, transaction_id: &N::TransactionID) -> bool {
+        self.transaction_ids.contains_key(transaction_id)
     }
 
     /// Returns `true` if the given serial number exists.
     pub fn contains_serial_number(&self, serial_number: &N::SerialNumber) -> bool {
-        self.transactions
-            .values()
-            .flat_map(|transactions| (**transactions).iter().map(Transaction::serial_numbers))
-            .any(|mut serial_numbers| serial_numbers.contains(serial_number))
+        self.serial_numbers.contains_key(serial_number)
     }
 
     /// Returns `true` if the given commitment exists.
     pub fn contains_commitment(&self, commitment: &N::Commitment) -> bool {
-        self.transactions
-            .values()
-            .flat_map(|transactions| (**transactions).iter().map(Transaction::commitments))
-            .any(|mut commitments| commitments.contains(commitment))
+        self.commitments.contains_key(commitment)
+    }
+
+    /// Returns `true` if the given program ID is deployed.
+    pub fn contains_program(&self, program_id: &N::ProgramID) -> bool {
+        self.deployments.contains_key(program_id)
+    }
+
+    /// Returns `true` if the given function ID is deployed in the given program ID.
+    pub fn contains_function(&self, program_id: &N::ProgramID, function_id: &N::FunctionID) -> bool {
+        match self.deployments.get(program_id) {
+            Some(deployment) => deployment.contains_function(function_id),
+            None => false,
+        }
+    }
+
+    /// Returns the deployment of the given program ID.
+    pub fn get_deployment(&self, program_id: &N::ProgramID) -> Result<&Deployment<N>> {
+        match self.deployments.get(program_id) {
+            Some(deployment) => Ok(deployment),
+            None => Err(anyhow!("Program {} is not deployed in the ledger", program_id)),
+        }
+    }
+
+    ///
+    /// Returns an error if the given transaction deploys a program that is already deployed or pays no fee,
+    /// or executes a program that is not deployed, as read from the first output record of each transition.
+    ///
+    pub fn check_programs(&self, transaction: &Transaction<N>) -> Result<()> {
+        for transition in transaction.transitions() {
+            for deployment in transition.deployments() {
+                if self.contains_program(&deployment.program_id()) {
+                    return Err(anyhow!("Program {} is already deployed", deployment.program_id()));
+                }
+                if !transition.value_balance().is_positive() {
+                    return Err(anyhow!("Program {} is deployed without a fee", deployment.program_id()));
+                }
+            }
+
+            // Ensure the program ID of the transition is deployed.
+            let program_id = transition.to_program_id()?;
+            let is_noop_program = program_id == *N::noop_program_id();
+            if !is_noop_program && !self.contains_program(&program_id) {
+                return Err(anyhow!("Program {} is not deployed in the ledger", program_id));
+            }
+
+            // Ensure each evaluated function is deployed in the program ID of the transition.
+            for event in transition.events() {
+                if let Event::Operation(Operation::Evaluate(function_id, ..)) = event {
+                    let is_deployed = match is_noop_program {
+                        true => function_id == N::noop_function_id(),
+                        false => self.contains_function(&program_id, function_id),
+                    };
+                    if !is_deployed {
+                        return Err(anyhow!(
+                            "Function {} is not deployed in program {}",
+                            function_id,
+                            program_id
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the block height and position in the block of the given transaction ID.
+    pub fn get_transaction_location(&self, transaction_id: &N::TransactionID) -> Result<(u32, usize)> {
+        match self.transaction_ids.get(transaction_id) {
+            Some(location) => Ok(*location),
+            None => Err(anyhow!("Missing transaction {} in the ledger", transaction_id)),
+        }
+    }
+
+    /// Returns the transaction given the transaction ID.
+    pub fn get_transaction(&self, transaction_id: &N::TransactionID) -> Result<&Transaction<N>> {
+        let (height, index) = self.get_transaction_location(transaction_id)?;
+        match self.get_block_transactions(height)?.get(index) {
+            Some(transaction) => Ok(transaction),
+            None => Err(anyhow!("Missing transaction {} in block {}", transaction_id, height)),
+        }
+    }
+
+    /// Returns the block height, transaction ID, and transition ID of the given serial number.
+    pub fn get_serial_number_location(
+        &self,
+        serial_number: &N::SerialNumber,
+    ) -> Result<(u32, N::TransactionID, N::TransitionID)> {
+        match self.serial_numbers.get(serial_number) {
+            Some(location) => Ok(*location),
+            None => Err(anyhow!("Missing serial number {} in the ledger", serial_number)),
+        }
+    }
+
+    /// Returns the block height, transaction ID, and transition ID of the given commitment.
+    pub fn get_commitment_location(
+        &self,
+        commitment: &N::Commitment,
+    ) -> Result<(u32, N::TransactionID, N::TransitionID)> {
+        match self.commitments.get(commitment) {
+            Some(location) => Ok(*location),
+            None => Err(anyhow!("Missing commitment {} in the ledger", commitment)),
+        }
     }
 
     /// Adds the given block as the next block in the chain.
     pub fn add_next(&mut self, block: &Block<N>) -> Result<()> {
+        self.check_next(block)?;
+
+        // Add the block to the ledger tree, and persist the block before updating the rest of the in-memory state.
+        let batch = Self::to_storage_batch(block)?;
+        self.ledger_tree.add(&block.hash())?;
+        if let Err(error) = self.storage.commit(batch) {
+            // Remove the block hash from the ledger tree, which held one block hash per height below the block.
+            self.ledger_tree.truncate(block.height())?;
+            return Err(error);
+        }
+        self.insert(block);
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the number of blocks at the start of the given branch whose headers follow from the chain
+    /// at the given fork height, and from each other.
+    ///
+    /// As the cumulative weight of a block is only checked against the block it follows from,
+    /// the cumulative weight of a branch is only trustworthy up to its first invalid header.
+    ///
+    pub fn count_valid_headers(&self, fork_height: u32, branch: &[Block<N>]) -> Result<usize> {
+        let mut previous_block_hash = self.get_block_hash(fork_height)?;
+        let mut previous_header = self.get_block_header(fork_height)?;
+        for (index, block) in branch.iter().enumerate() {
+            if Self::check_next_header(previous_block_hash, previous_header, block).is_err() {
+                return Ok(index);
+            }
+            previous_block_hash = block.hash();
+            previous_header = block.header();
+        }
+        Ok(branch.len())
+    }
+
+    ///
+    /// Removes all blocks above the given block height, returning the removed blocks in ascending order.
+    /// The ledger tree is truncated to the state it had at the given block height.
+    ///
+    pub fn rollback_to(&mut self, height: u32) -> Result<Vec<Block<N>>> {
+        // Retrieve the blocks to remove.
+        let removed_blocks = self.get_blocks_above(height)?;
+
+        if removed_blocks.is_empty() {
+            return Ok(removed_blocks);
+        }
+
+        // Persist the removal before updating the in-memory state.
+        self.commit_staged(&removed_blocks, &[])?;
+        self.remove_above(height, &removed_blocks)?;
+
+        Ok(removed_blocks)
+    }
+
+    ///
+    /// Removes all blocks above the given block height from the in-memory state, without persisting the removal,
+    /// returning the removed blocks in ascending order.
+    ///
+    /// The staged changes are persisted with `commit_staged`, or undone with `restore_staged`,
+    /// so that only the blocks that change are held in memory twice.
+    ///
+    pub(crate) fn rollback_staged(&mut self, height: u32) -> Result<Vec<Block<N>>> {
+        let removed_blocks = self.get_blocks_above(height)?;
+        self.remove_above(height, &removed_blocks)?;
+        Ok(removed_blocks)
+    }
+
+    /// Adds the given block as the next block in the in-memory state, without persisting it.
+    pub(crate) fn add_next_staged(&mut self, block: &Block<N>) -> Result<()> {
+        self.check_next(block)?;
+        self.ledger_tree.add(&block.hash())?;
+        self.insert(block);
+        Ok(())
+    }
+
+    /// Persists the removal of the given removed blocks and the addition of the given added blocks in a single batch.
+    pub(crate) fn commit_staged(&self, removed_blocks: &[Block<N>], added_blocks: &[Block<N>]) -> Result<()> {
+        let mut batch = StorageBatch::new();
+        for block in removed_blocks {
+            Self::remove_from_storage_batch(&mut batch, block.height());
+        }
+        for block in added_blocks {
+            Self::add_to_storage_batch(&mut batch, block)?;
+        }
+        self.storage.commit(batch)
+    }
+
+    ///
+    /// Undoes the staged changes above the given block height, by removing the staged blocks from the
+    /// in-memory state and re-inserting the given blocks that were removed above the given block height.
+    ///
+    pub(crate) fn restore_staged(&mut self, height: u32, removed_blocks: &[Block<N>]) -> Result<()> {
+        let staged_blocks = self.get_blocks_above(height)?;
+        self.remove_above(height, &staged_blocks)?;
+
+        // The removed blocks were valid in the chain, so they are re-inserted without checks.
+        for block in removed_blocks {
+            self.ledger_tree.add(&block.hash())?;
+            self.insert(block);
+        }
+        Ok(())
+    }
+
+    /// Returns the blocks above the given block height, in ascending order.
+    fn get_blocks_above(&self, height: u32) -> Result<Vec<Block<N>>> {
+        // Ensure the given block height exists.
+        if height > self.current_height {
+            return Err(anyhow!("Given block height {} is greater than current height", height));
+        }
+
+        (height + 1..=self.current_height)
+            .map(|height| self.get_block(height))
+            .collect()
+    }
+
+    ///
+    /// Removes the given blocks, which are all blocks above the given block height, from the in-memory state.
+    /// The ledger tree is truncated to the state it had at the given block height.
+    ///
+    fn remove_above(&mut self, height: u32, removed_blocks: &[Block<N>]) -> Result<()> {
+        // As the given height is at most the current height, the ledger tree is always truncated successfully.
+        self.current_hash = self.get_block_hash(height)?;
+        self.current_height = height;
+        self.ledger_tree.truncate(height + 1)?;
+        for block in removed_blocks {
+            let block_height = block.height();
+            self.previous_hashes.remove(&block_height);
+            self.block_hashes.remove(&block.hash());
+            self.ledger_roots.remove(&block.previous_ledger_root());
+            self.headers.remove(&block_height);
+            self.transactions.remove(&block_height);
+            self.remove_from_indexes(block.transactions());
+        }
+        Ok(())
+    }
+
+    /// Ensures the given block is valid as the next block in the chain.
+    fn check_next(&self, block: &Block<N>) -> Result<()> {
         // Ensure the block itself is valid.
         if !block.is_valid() {
             return Err(anyhow!("The given block is invalid"));
         }
 
-        // Ensure the next block height is correct.
-        let height = block.height();
-        if self.current_height + 1 != height {
-            return Err(anyhow!("The given block has an incorrect block height"));
-        }
-
         // Ensure the block height does not already exist.
-        if self.contains_height(height) {
+        if self.contains_height(block.height()) {
             return Err(anyhow!("The given block height already exists in the ledger"));
         }
 
-        // Ensure the previous block hash is correct.
-        if self.current_hash != block.previous_block_hash() {
-            return Err(anyhow!("The given block has an incorrect previous block hash"));
-        }
-
         // Ensure the block hash does not already exist.
-        let block_hash = block.hash();
-        if self.contains_block_hash(&block_hash) {
+        if self.contains_block_hash(&block.hash()) {
             return Err(anyhow!("The given block hash already exists in the ledger"));
         }
 
+        // Ensure the block header follows from the current block header.
+        Self::check_next_header(self.current_hash, self.get_block_header(self.current_height)?, block)?;
+
+        for transaction in block.transactions().iter() {
+            // Ensure the transaction in the block do not already exist.
+            if self.contains_transaction(transaction) {
+                return Err(anyhow!("The given block has a duplicate transaction in the ledger"));
+            }
+            // Ensure the transaction in the block references a valid past or current ledger root.
+            if !self.contains_ledger_root(&transaction.ledger_root()) {
+                return Err(anyhow!(
+                    "The given transaction references a non-existent ledger root {}",
+                    &transaction.ledger_root()
+                ));
+            }
+            // Ensure the transaction in the block deploys new programs, and evaluates deployed functions.
+            self.check_programs(transaction)?;
+        }
+
+        // Ensure the ledger does not already contain a given serial numbers.
+        for serial_number in block.serial_numbers() {
+            if self.contains_serial_number(serial_number) {
+                return Err(anyhow!("Serial number already exists in the ledger"));
+            }
+        }
+
+        // Ensure the ledger does not already contain a given commitments.
+        for commitment in block.commitments() {
+            if self.contains_commitment(commitment) {
+                return Err(anyhow!("Commitment already exists in the ledger"));
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Ensures the header of the given block follows from the given previous block hash and header,
+    /// with the next block height, a later timestamp, and the expected difficulty target and cumulative weight.
+    ///
+    fn check_next_header(
+        previous_block_hash: N::BlockHash,
+        previous_header: &BlockHeader<N>,
+        block: &Block<N>,
+    ) -> Result<()> {
+        // Ensure the next block height is correct.
+        if previous_header.height() + 1 != block.height() {
+            return Err(anyhow!("The given block has an incorrect block height"));
+        }
+
+        // Ensure the previous block hash is correct.
+        if previous_block_hash != block.previous_block_hash() {
+            return Err(anyhow!("The given block has an incorrect previous block hash"));
+        }
+
         // Ensure the next block timestamp is within the declared time limit.
         let now = Utc::now().timestamp();
         if block.timestamp() > (now + TWO_HOURS_UNIX) {
             return Err(anyhow!("The given block timestamp exceeds the time limit"));
         }
 
-        // Ensure the next block timestamp is after the current block timestamp.
-        let current_block = self.latest_block()?;
-        if block.timestamp() <= current_block.timestamp() {
+        // Ensure the next block timestamp is after the previous block timestamp.
+        if block.timestamp() <= previous_header.timestamp() {
             return Err(anyhow!("The given block timestamp is before the current timestamp"));
         }
 
         // Ensure the expected difficulty target is met.
         let expected_difficulty_target = Blocks::<N>::compute_difficulty_target(
-            current_block.timestamp(),
-            current_block.difficulty_target(),
+            previous_header.timestamp(),
+            previous_header.difficulty_target(),
             block.timestamp(),
         );
         if block.difficulty_target() != expected_difficulty_target {
@@ -258,7 +634,7 @@ impl<N: Network> Blocks<N> {
         }
 
         // Ensure the expected cumulative weight is computed correctly.
-        let expected_cumulative_weight = current_block
+        let expected_cumulative_weight = previous_header
             .cumulative_weight()
             .saturating_add((u64::MAX / expected_difficulty_target) as u128);
         if block.cumulative_weight() != expected_cumulative_weight {
@@ -269,49 +645,115 @@ impl<N: Network> Blocks<N> {
             ));
         }
 
-        for transaction in block.transactions().iter() {
-            // Ensure the transaction in the block do not already exist.
-            if self.contains_transaction(transaction) {
-                return Err(anyhow!("The given block has a duplicate transaction in the ledger"));
-            }
-            // Ensure the transaction in the block references a valid past or current ledger root.
-            if !self.contains_ledger_root(&transaction.ledger_root()) {
-                return Err(anyhow!(
-                    "The given transaction references a non-existent ledger root {}",
-                    &transaction.ledger_root()
-                ));
-            }
-        }
+        Ok(())
+    }
 
-        // Ensure the ledger does not already contain a given serial numbers.
-        for serial_number in block.serial_numbers() {
-            if self.contains_serial_number(serial_number) {
-                return Err(anyhow!("Serial number already exists in the ledger"));
+    /// Inserts the given block into the in-memory state, except for the ledger tree, without performing any checks.
+    fn insert(&mut self, block: &Block<N>) {
+        let height = block.height();
+
+        self.current_height = height;
+        self.current_hash = block.hash();
+        self.previous_hashes.insert(height, block.previous_block_hash());
+        self.block_hashes.insert(block.hash(), height);
+        self.ledger_roots.insert(block.previous_ledger_root(), height);
+        self.headers.insert(height, block.header().clone());
+        self.add_to_indexes(height, block.transactions());
+        self.transactions.insert(height, block.transactions().clone());
+    }
+
+    /// Adds the transaction IDs, serial numbers, commitments, and deployments of the given transactions
+    /// to the indexes.
+    fn add_to_indexes(&mut self, height: u32, transactions: &Transactions<N>) {
+        for (index, transaction) in transactions.iter().enumerate() {
+            let transaction_id = transaction.transaction_id();
+            self.transaction_ids.insert(transaction_id, (height, index));
+
+            for transition in transaction.transitions() {
+                let transition_id = transition.transition_id();
+                for serial_number in transition.serial_numbers() {
+                    self.serial_numbers
+                        .insert(*serial_number, (height, transaction_id, transition_id));
+                }
+                for commitment in transition.commitments() {
+                    self.commitments
+                        .insert(*commitment, (height, transaction_id, transition_id));
+                }
+                for deployment in transition.deployments() {
+                    self.deployments.insert(deployment.program_id(), deployment.clone());
+                }
             }
         }
+    }
 
-        // Ensure the ledger does not already contain a given commitments.
-        for commitment in block.commitments() {
-            if self.contains_commitment(commitment) {
-                return Err(anyhow!("Commitment already exists in the ledger"));
+    /// Removes the transaction IDs, serial numbers, commitments, and deployments of the given transactions
+    /// from the indexes.
+    fn remove_from_indexes(&mut self, transactions: &Transactions<N>) {
+        for transaction in transactions.iter() {
+            self.transaction_ids.remove(&transaction.transaction_id());
+            for serial_number in transaction.serial_numbers() {
+                self.serial_numbers.remove(serial_number);
+            }
+            for commitment in transaction.commitments() {
+                self.commitments.remove(commitment);
+            }
+            for deployment in transaction.deployments() {
+                self.deployments.remove(&deployment.program_id());
             }
         }
+    }
 
-        // Add the block to the ledger. This code section executes atomically.
-        {
-            let mut blocks = self.clone();
+    /// Returns the storage batch that persists the given block.
+    fn to_storage_batch(block: &Block<N>) -> Result<StorageBatch> {
+        let mut batch = StorageBatch::new();
+        Self::add_to_storage_batch(&mut batch, block)?;
+        Ok(batch)
+    }
 
-            blocks.current_height = height;
-            blocks.current_hash = block_hash;
-            blocks.ledger_tree.add(&block.hash())?;
-            blocks.previous_hashes.insert(height, block.previous_block_hash());
-            blocks.headers.insert(height, block.header().clone());
-            blocks.transactions.insert(height, block.transactions().clone());
+    /// Adds the operations that persist the given block to the given storage batch.
+    fn add_to_storage_batch(batch: &mut StorageBatch, block: &Block<N>) -> Result<()> {
+        let key = Self::height_to_key(block.height());
+
+        batch.insert(DataMap::BlockHashes, key.clone(), block.hash().to_bytes_le()?);
+        batch.insert(
+            DataMap::PreviousHashes,
+            key.clone(),
+            block.previous_block_hash().to_bytes_le()?,
+        );
+        batch.insert(DataMap::Headers, key.clone(), block.header().to_bytes_le()?);
+        batch.insert(DataMap::Transactions, key, block.transactions().to_bytes_le()?);
+        Ok(())
+    }
 
-            *self = blocks;
+    /// Adds the operations that remove the block at the given height to the given storage batch.
+    fn remove_from_storage_batch(batch: &mut StorageBatch, height: u32) {
+        let key = Self::height_to_key(height);
+
+        batch.remove(DataMap::BlockHashes, key.clone());
+        batch.remove(DataMap::PreviousHashes, key.clone());
+        batch.remove(DataMap::Headers, key.clone());
+        batch.remove(DataMap::Transactions, key);
+    }
+
+    /// Returns the stored value in the given map for the given block height.
+    fn read_from_storage(storage: &dyn Storage, map: DataMap, height: u32) -> Result<Vec<u8>> {
+        match storage.get(map, &Self::height_to_key(height))? {
+            Some(value) => Ok(value),
+            None => Err(anyhow!("Missing {:?} entry in storage for height {}", map, height)),
         }
+    }
 
-        Ok(())
+    /// Returns the storage key for the given block height. Keys are big-endian, so they sort by height.
+    fn height_to_key(height: u32) -> Vec<u8> {
+        height.to_be_bytes().to_vec()
+    }
+
+    /// Returns the block height for the given storage key.
+    fn height_from_key(key: &[u8]) -> Result<u32> {
+        let bytes: [u8; 4] = key
+            .try_into()
+            .map_err(|_| anyhow!("Invalid block height key in storage"))?;
+        Ok(u32::from_be_bytes(bytes))
     }
 
     /// Returns the ledger tree.
@@ -331,55 +773,18 @@ impl<N: Network> Blocks<N> {
     /// Returns a ledger proof for the given commitment.
     ///
     pub fn to_ledger_proof(&self, commitment: N::Commitment) -> Result<LedgerProof<N>> {
-        // TODO (howardwu): Optimize this operation.
-        let transaction = self
-            .transactions
-            .values()
-            .flat_map(|transactions| &**transactions)
-            .filter(|transaction| transaction.commitments().contains(&commitment))
-            .collect::<Vec<_>>();
-        assert_eq!(1, transaction.len()); // TODO (howardwu): Clean this up with a proper error handler.
-        let transaction = transaction[0];
-        let local_proof = {
-            // Initialize a transitions tree.
-            let mut transitions_tree = Transitions::<N>::new()?;
-            // Add all given transition IDs to the tree.
-            transitions_tree.add_all(&transaction.transitions())?;
-            // Return the local proof for the transitions tree.
-            transitions_tree.to_local_proof(commitment)?
-        };
-        let transaction_id = local_proof.transaction_id();
-
-        // TODO (howardwu): Optimize this operation.
-        let block_height = self
-            .transactions
-            .iter()
-            .filter_map(
-                |(block_height, transactions)| match transactions.transaction_ids().contains(&transaction_id) {
-                    true => Some(block_height),
-                    false => None,
-                },
-            )
-            .collect::<Vec<_>>();
-        assert_eq!(1, block_height.len()); // TODO (howardwu): Clean this up with a proper error handler.
-        let block_height = *block_height[0];
+        // Retrieve the location of the commitment.
+        let (block_height, transaction_id, _) = self.get_commitment_location(&commitment)?;
+        let (_, index) = self.get_transaction_location(&transaction_id)?;
+
+        let transaction = self.get_transaction(&transaction_id)?;
+        let local_proof = transaction.to_local_proof(commitment)?;
+
         let transactions = self.get_block_transactions(block_height)?;
         let block_header = self.get_block_header(block_height)?;
 
         // Compute the transactions inclusion proof.
-        let transactions_inclusion_proof = {
-            // TODO (howardwu): Optimize this operation.
-            let index = transactions
-                .transaction_ids()
-                .enumerate()
-                .filter_map(|(index, id)| match id == transaction_id {
-                    true => Some(index),
-                    false => None,
-                })
-                .collect::<Vec<_>>();
-            assert_eq!(1, index.len()); // TODO (howardwu): Clean this up with a proper error handler.
-            transactions.to_transactions_inclusion_proof(index[0], transaction_id)?
-        };
+        let transactions_inclusion_proof = transactions.to_transactions_inclusion_proof(index, transaction_id)?;
 
         // Compute the block header inclusion proof.
         let transactions_root = transactions.transactions_root();
@@ -406,41 +811,7 @@ impl<N: Network> Blocks<N> {
 
     /// Returns the expected difficulty target given the previous block and expected next block details.
     pub fn compute_difficulty_target(previous_timestamp: i64, previous_difficulty_target: u64, timestamp: i64) -> u64 {
-        const NUM_BLOCKS_PER_RETARGET: i64 = 1i64;
-
-        /// Bitcoin difficulty retarget algorithm.
-        ///     T_{i+1} = T_i * (S / (M * B)).
-        ///     M = Number of blocks per retarget.
-        ///     B = Expected time per block.
-        ///     S = Time elapsed between the last M blocks.
-        fn bitcoin_retarget(
-            previous_timestamp: i64,
-            previous_difficulty: u64,
-            block_timestamp: i64,
-            target_block_time: i64,
-        ) -> u64 {
-            let time_elapsed = block_timestamp.saturating_sub(previous_timestamp);
-            let time_elapsed = match time_elapsed > 0 {
-                true => time_elapsed,
-                false => 1,
-            };
-
-            let difficulty_factor = time_elapsed as f64 / (NUM_BLOCKS_PER_RETARGET * target_block_time) as f64;
-
-            let new_difficulty = (previous_difficulty as f64) * difficulty_factor;
-
-            match new_difficulty.is_finite() {
-                true => new_difficulty as u64,
-                false => u64::MAX,
-            }
-        }
-
-        bitcoin_retarget(
-            previous_timestamp,
-            previous_difficulty_target,
-            timestamp,
-            N::ALEO_BLOCK_TIME_IN_SECS,
-        )
+        N::DifficultyRetarget::compute_difficulty_target(previous_timestamp, previous_difficulty_target, timestamp)
     }
 }
 
@@ -450,6 +821,63 @@ mod tests {
     use crate::testnet2::Testnet2;
 
     use rand::{thread_rng, Rng};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// A storage backend that rejects every commit once it is set to fail.
+    #[derive(Default)]
+    struct FailingStorage {
+        storage: MemoryStorage,
+        is_failing: AtomicBool,
+    }
+
+    impl Storage for FailingStorage {
+        fn get(&self, map: DataMap, key: &[u8]) -> Result<Option<Vec<u8>>> {
+            self.storage.get(map, key)
+        }
+
+        fn get_all(&self, map: DataMap) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+            self.storage.get_all(map)
+        }
+
+        fn commit(&self, batch: StorageBatch) -> Result<()> {
+            match self.is_failing.load(Ordering::SeqCst) {
+                true => Err(anyhow!("Failed to commit")),
+                false => self.storage.commit(batch),
+            }
+        }
+    }
+
+    #[test]
+    fn test_failed_commit_leaves_blocks_unchanged() {
+        let rng = &mut thread_rng();
+        let recipient = Account::<Testnet2>::new(rng);
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        ledger
+            .mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng)
+            .unwrap();
+        let block = ledger.latest_block().unwrap();
+
+        let storage = Arc::new(FailingStorage::default());
+        let mut blocks = Blocks::<Testnet2>::open(storage.clone()).unwrap();
+        let genesis_ledger_root = blocks.latest_ledger_root();
+
+        // Ensure a block that fails to persist is not added.
+        storage.is_failing.store(true, Ordering::SeqCst);
+        assert!(blocks.add_next(&block).is_err());
+        assert_eq!(0, blocks.latest_block_height());
+        assert_eq!(genesis_ledger_root, blocks.latest_ledger_root());
+        assert!(!blocks.contains_block_hash(&block.hash()));
+
+        // Ensure a removal that fails to persist does not remove the block.
+        storage.is_failing.store(false, Ordering::SeqCst);
+        blocks.add_next(&block).unwrap();
+        storage.is_failing.store(true, Ordering::SeqCst);
+        assert!(blocks.rollback_to(0).is_err());
+        assert_eq!(1, blocks.latest_block_height());
+        assert_eq!(ledger.latest_ledger_root(), blocks.latest_ledger_root());
+        assert_eq!(block, blocks.latest_block().unwrap());
+    }
 
     #[test]
     fn test_retargeting_algorithm_increased() {
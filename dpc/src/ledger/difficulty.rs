@@ -0,0 +1,326 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{DifficultyRetargetScheme, Network};
+
+/// The number of fractional bits in the fixed-point exponent of the ASERT retarget.
+const ASERT_RADIX_BITS: i128 = 16;
+
+///
+/// Bitcoin difficulty retarget algorithm, retargeting after every block.
+///     T_{i+1} = T_i * (S / B).
+///     B = Expected time per block.
+///     S = Time elapsed since the previous block.
+///
+/// This is the retarget of the existing networks, and changing it for them is a consensus change.
+///
+#[derive(Clone, Debug)]
+pub struct BitcoinRetarget;
+
+impl<N: Network> DifficultyRetargetScheme<N> for BitcoinRetarget {
+    fn compute_difficulty_target(previous_timestamp: i64, previous_difficulty_target: u64, timestamp: i64) -> u64 {
+        let time_elapsed = timestamp.saturating_sub(previous_timestamp);
+        let time_elapsed = match time_elapsed > 0 {
+            true => time_elapsed,
+            false => 1,
+        };
+
+        let difficulty_factor = time_elapsed as f64 / N::ALEO_BLOCK_TIME_IN_SECS as f64;
+
+        let new_difficulty = (previous_difficulty_target as f64) * difficulty_factor;
+
+        match new_difficulty.is_finite() {
+            true => new_difficulty as u64,
+            false => u64::MAX,
+        }
+    }
+}
+
+///
+/// ASERT-style difficulty retarget, an exponentially-weighted moving window over past block times.
+///     T_{i+1} = T_i * 2^((S - B) / H).
+///     B = Expected time per block.
+///     S = Time elapsed since the previous block.
+///     H = Half-life of the moving window, in seconds.
+///
+/// The exponent is evaluated in integer-only fixed-point arithmetic, so the result is deterministic.
+///
+#[derive(Clone, Debug)]
+pub struct AsertRetarget<const HALF_LIFE_IN_SECS: i64>;
+
+impl<N: Network, const HALF_LIFE_IN_SECS: i64> DifficultyRetargetScheme<N> for AsertRetarget<HALF_LIFE_IN_SECS> {
+    fn compute_difficulty_target(previous_timestamp: i64, previous_difficulty_target: u64, timestamp: i64) -> u64 {
+        let radix = 1i128 << ASERT_RADIX_BITS;
+        let half_life = HALF_LIFE_IN_SECS.max(1) as i128;
+
+        // Compute the exponent (S - B) / H in fixed-point, rounded towards negative infinity.
+        let drift = (timestamp as i128 - previous_timestamp as i128) - N::ALEO_BLOCK_TIME_IN_SECS as i128;
+        let exponent = (drift * radix).div_euclid(half_life);
+        let num_shifts = exponent.div_euclid(radix);
+        let fraction = exponent.rem_euclid(radix) as u128;
+
+        // Approximate 2^fraction with a cubic polynomial, scaled by the radix.
+        let factor = radix as u128
+            + ((195_766_423_245_049 * fraction + 971_821_376 * fraction.pow(2) + 5_127 * fraction.pow(3) + (1 << 47))
+                >> 48);
+
+        // The product is less than 2^81, so it may be shifted left by at most 46 bits without overflow.
+        let target = previous_difficulty_target as u128 * factor;
+        let shift = num_shifts - ASERT_RADIX_BITS;
+        let target = match shift >= 0 {
+            true => match shift < 47 {
+                true => target << shift,
+                false => u128::MAX,
+            },
+            false => match -shift < 128 {
+                true => target >> -shift,
+                false => 0,
+            },
+        };
+
+        target.clamp(1, u64::MAX as u128) as u64
+    }
+}
+
+///
+/// Proportional difficulty retarget, clamped to a maximum adjustment per block.
+///     T_{i+1} = clamp(T_i * (S / B), T_i * 100 / (100 + P), T_i * (100 + P) / 100).
+///     B = Expected time per block.
+///     S = Time elapsed since the previous block.
+///     P = Maximum adjustment per block, in percent.
+///
+#[derive(Clone, Debug)]
+pub struct ClampedRetarget<const MAXIMUM_ADJUSTMENT_PERCENT: u64>;
+
+impl<N: Network, const MAXIMUM_ADJUSTMENT_PERCENT: u64> DifficultyRetargetScheme<N>
+    for ClampedRetarget<MAXIMUM_ADJUSTMENT_PERCENT>
+{
+    fn compute_difficulty_target(previous_timestamp: i64, previous_difficulty_target: u64, timestamp: i64) -> u64 {
+        let block_time = N::ALEO_BLOCK_TIME_IN_SECS.max(1) as u128;
+        let percent = MAXIMUM_ADJUSTMENT_PERCENT as u128;
+        let previous_difficulty_target = previous_difficulty_target as u128;
+
+        // Bound the elapsed time beyond the upper clamp, so the product below cannot overflow.
+        let time_elapsed =
+            (timestamp as i128 - previous_timestamp as i128).clamp(0, (block_time * (200 + percent)) as i128) as u128;
+
+        let lower_bound = previous_difficulty_target * 100 / (100 + percent);
+        let upper_bound = previous_difficulty_target * (100 + percent) / 100;
+        let target = (previous_difficulty_target * time_elapsed / block_time).clamp(lower_bound, upper_bound);
+
+        target.clamp(1, u64::MAX as u128) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testnet1::Testnet1, testnet2::Testnet2};
+
+    const BLOCK_TIME: i64 = Testnet2::ALEO_BLOCK_TIME_IN_SECS;
+
+    /// Returns the block time of a miner with the given hashrate,
+    /// which is inversely proportional to the difficulty target.
+    fn sample_block_time(difficulty_target: u64, hashrate: u64) -> i64 {
+        ((u64::MAX / difficulty_target) / hashrate).max(1) as i64
+    }
+
+    ///
+    /// Simulates a chain starting from the given difficulty target, mined with the given hashrate for each
+    /// of the given number of blocks, and returns the resulting block times.
+    ///
+    fn simulate<D: DifficultyRetargetScheme<Testnet2>>(
+        mut difficulty_target: u64,
+        schedule: &[(u64, usize)],
+    ) -> Vec<i64> {
+        let mut timestamp = 0;
+        let mut block_times = Vec::new();
+        for (hashrate, num_blocks) in schedule {
+            for _ in 0..*num_blocks {
+                let block_time = sample_block_time(difficulty_target, *hashrate);
+                difficulty_target = D::compute_difficulty_target(timestamp, difficulty_target, timestamp + block_time);
+                timestamp += block_time;
+                block_times.push(block_time);
+            }
+        }
+        block_times
+    }
+
+    /// Returns the mean of the given block times.
+    fn mean(block_times: &[i64]) -> f64 {
+        block_times.iter().sum::<i64>() as f64 / block_times.len() as f64
+    }
+
+    /// Asserts the given block times have converged to the network block time.
+    fn assert_converged(block_times: &[i64]) {
+        let mean = mean(block_times);
+        assert!(
+            (mean - BLOCK_TIME as f64).abs() < 1.0,
+            "Mean block time {} did not converge",
+            mean
+        );
+    }
+
+    /// Simulates a chain over a sequence of alternating fast and slow block times with the expected mean.
+    fn simulate_alternating<D: DifficultyRetargetScheme<Testnet2>>(difficulty_target: u64) -> u64 {
+        let mut timestamp = 0;
+        let mut target = difficulty_target;
+        for i in 0..10_000 {
+            let block_time = match i % 2 == 0 {
+                true => BLOCK_TIME / 4,
+                false => BLOCK_TIME * 2 - BLOCK_TIME / 4,
+            };
+            target = D::compute_difficulty_target(timestamp, target, timestamp + block_time);
+            timestamp += block_time;
+        }
+        target
+    }
+
+    fn test_convergence<D: DifficultyRetargetScheme<Testnet2>>(num_blocks: usize) {
+        const HASHRATE: u64 = 1 << 20;
+
+        // Start from the easiest difficulty target.
+        let block_times = simulate::<D>(u64::MAX, &[(HASHRATE, num_blocks)]);
+        assert_converged(&block_times[num_blocks - 1000..]);
+
+        // Start from a difficulty target that is far too hard.
+        let block_times = simulate::<D>(1 << 30, &[(HASHRATE, num_blocks)]);
+        assert!(block_times[0] > BLOCK_TIME * 100);
+        assert_converged(&block_times[num_blocks - 1000..]);
+
+        // Double the hashrate after converging.
+        let block_times = simulate::<D>(u64::MAX, &[(HASHRATE, num_blocks), (HASHRATE * 2, num_blocks)]);
+        assert!(block_times[num_blocks] < BLOCK_TIME);
+        assert_converged(&block_times[2 * num_blocks - 1000..]);
+    }
+
+    #[test]
+    fn test_asert_retarget_converges() {
+        test_convergence::<AsertRetarget<3600>>(10_000);
+    }
+
+    #[test]
+    fn test_existing_networks_keep_bitcoin_retarget() {
+        const DIFFICULTY_TARGET: u64 = 1 << 40;
+
+        fn retarget<N: Network>(difficulty_target: u64, block_time: i64) -> u64 {
+            N::DifficultyRetarget::compute_difficulty_target(0, difficulty_target, block_time)
+        }
+
+        for block_time in [0, 1, BLOCK_TIME / 2, BLOCK_TIME, BLOCK_TIME * 3] {
+            let expected = <BitcoinRetarget as DifficultyRetargetScheme<Testnet2>>::compute_difficulty_target(
+                0,
+                DIFFICULTY_TARGET,
+                block_time,
+            );
+            assert_eq!(expected, retarget::<Testnet2>(DIFFICULTY_TARGET, block_time));
+        }
+
+        // The difficulty target is proportional to the block time, which is at least one second.
+        assert_eq!(DIFFICULTY_TARGET, retarget::<Testnet2>(DIFFICULTY_TARGET, BLOCK_TIME));
+        assert_eq!(
+            DIFFICULTY_TARGET * 3,
+            retarget::<Testnet2>(DIFFICULTY_TARGET, BLOCK_TIME * 3)
+        );
+        assert_eq!(
+            DIFFICULTY_TARGET / BLOCK_TIME as u64,
+            retarget::<Testnet2>(DIFFICULTY_TARGET, 0)
+        );
+        assert_eq!(
+            DIFFICULTY_TARGET / Testnet1::ALEO_BLOCK_TIME_IN_SECS as u64,
+            retarget::<Testnet1>(DIFFICULTY_TARGET, 1)
+        );
+
+        // The easiest difficulty target is kept when blocks are found on time.
+        assert_eq!(u64::MAX, retarget::<Testnet2>(u64::MAX, BLOCK_TIME));
+    }
+
+    #[test]
+    fn test_clamped_retarget_converges() {
+        test_convergence::<ClampedRetarget<25>>(2_000);
+    }
+
+    #[test]
+    fn test_asert_retarget_is_stable() {
+        const DIFFICULTY_TARGET: u64 = 1 << 40;
+
+        // An exact block time leaves the difficulty target unchanged.
+        assert_eq!(
+            DIFFICULTY_TARGET,
+            <AsertRetarget<3600> as DifficultyRetargetScheme<Testnet2>>::compute_difficulty_target(
+                0,
+                DIFFICULTY_TARGET,
+                BLOCK_TIME
+            )
+        );
+
+        // Alternating block times with the expected mean keep the difficulty target within 5%.
+        let target = simulate_alternating::<AsertRetarget<3600>>(DIFFICULTY_TARGET);
+        assert!((target as i128 - DIFFICULTY_TARGET as i128).abs() < (DIFFICULTY_TARGET / 20) as i128);
+    }
+
+    #[test]
+    fn test_clamped_retarget_is_stable() {
+        const DIFFICULTY_TARGET: u64 = 1 << 40;
+
+        // An exact block time leaves the difficulty target unchanged.
+        assert_eq!(
+            DIFFICULTY_TARGET,
+            <ClampedRetarget<25> as DifficultyRetargetScheme<Testnet2>>::compute_difficulty_target(
+                0,
+                DIFFICULTY_TARGET,
+                BLOCK_TIME
+            )
+        );
+
+        // Alternating block times with the expected mean keep the difficulty target within 5%.
+        let target = simulate_alternating::<ClampedRetarget<25>>(DIFFICULTY_TARGET);
+        assert!((target as i128 - DIFFICULTY_TARGET as i128).abs() < (DIFFICULTY_TARGET / 20) as i128);
+    }
+
+    #[test]
+    fn test_clamped_retarget_bounds_adjustment() {
+        const DIFFICULTY_TARGET: u64 = 1 << 40;
+
+        // A block found instantly, or before the previous block, lowers the target by at most 25%.
+        let target = <ClampedRetarget<25> as DifficultyRetargetScheme<Testnet2>>::compute_difficulty_target(
+            100,
+            DIFFICULTY_TARGET,
+            100,
+        );
+        assert_eq!(DIFFICULTY_TARGET * 100 / 125, target);
+        let target = <ClampedRetarget<25> as DifficultyRetargetScheme<Testnet2>>::compute_difficulty_target(
+            100,
+            DIFFICULTY_TARGET,
+            0,
+        );
+        assert_eq!(DIFFICULTY_TARGET * 100 / 125, target);
+
+        // A block found very late raises the target by at most 25%.
+        let target = <ClampedRetarget<25> as DifficultyRetargetScheme<Testnet2>>::compute_difficulty_target(
+            0,
+            DIFFICULTY_TARGET,
+            i64::MAX,
+        );
+        assert_eq!(DIFFICULTY_TARGET * 125 / 100, target);
+        let target = <ClampedRetarget<25> as DifficultyRetargetScheme<Testnet2>>::compute_difficulty_target(
+            0,
+            u64::MAX,
+            i64::MAX,
+        );
+        assert_eq!(u64::MAX, target);
+    }
+}
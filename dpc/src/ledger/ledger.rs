@@ -15,28 +15,59 @@
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::prelude::*;
+use snarkvm_utilities::ToBytes;
 
 use anyhow::{anyhow, Result};
 use chrono::Utc;
 use rand::{CryptoRng, Rng};
-use std::{collections::HashMap, sync::atomic::AtomicBool};
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    path::Path,
+    sync::{atomic::AtomicBool, Arc},
+};
+
+/// The maximum number of blocks that may be rolled back in a chain reorganization.
+pub const MAXIMUM_FORK_DEPTH: u32 = 4096;
+
+/// The maximum size of the unconfirmed transactions included in a mined block, in bytes.
+pub const MAXIMUM_BLOCK_TEMPLATE_SIZE_IN_BYTES: usize = 4 * 1024 * 1024;
+
+/// The maximum number of orphan blocks kept by the ledger.
+pub const MAXIMUM_ORPHAN_BLOCKS: usize = MAXIMUM_FORK_DEPTH as usize;
+
+/// The maximum total size of the orphan blocks kept by the ledger, in bytes.
+pub const MAXIMUM_ORPHAN_BLOCKS_SIZE_IN_BYTES: usize = 256 * 1024 * 1024;
 
 #[derive(Clone, Debug)]
 pub struct Ledger<N: Network> {
     /// The canonical chain of blocks.
     canon_blocks: Blocks<N>,
-    /// The set of unknown orphan blocks.
-    orphan_blocks: HashMap<u32, Block<N>>,
+    /// The set of orphan blocks, keyed by block hash.
+    orphan_blocks: HashMap<N::BlockHash, Block<N>>,
+    /// The total size of the orphan blocks, in bytes.
+    orphan_blocks_size_in_bytes: usize,
     /// The pool of unconfirmed transactions.
     memory_pool: MemoryPool<N>,
 }
 
 impl<N: Network> Ledger<N> {
-    /// Initializes a new instance of the ledger.
+    /// Initializes a new instance of the ledger, in memory.
     pub fn new() -> Result<Self> {
+        Self::with_storage(Arc::new(MemoryStorage::new()))
+    }
+
+    /// Opens an instance of the ledger persisted in the given directory, creating it if it does not exist.
+    pub fn open<P: AsRef<Path>>(directory: P) -> Result<Self> {
+        Self::with_storage(Arc::new(FileStorage::open(directory)?))
+    }
+
+    /// Opens an instance of the ledger from the given storage backend.
+    pub fn with_storage(storage: Arc<dyn Storage>) -> Result<Self> {
         Ok(Self {
-            canon_blocks: Blocks::new()?,
+            canon_blocks: Blocks::open(storage)?,
             orphan_blocks: Default::default(),
+            orphan_blocks_size_in_bytes: 0,
             memory_pool: MemoryPool::new(),
         })
     }
@@ -81,6 +112,11 @@ impl<N: Network> Ledger<N> {
         self.canon_blocks.latest_block()
     }
 
+    /// Returns the block given the block height.
+    pub fn get_block(&self, height: u32) -> Result<Block<N>> {
+        self.canon_blocks.get_block(height)
+    }
+
     /// Returns `true` if the given ledger root exists on the canon chain.
     pub fn contains_ledger_root(&self, ledger_root: &N::LedgerRoot) -> bool {
         self.canon_blocks.contains_ledger_root(ledger_root)
@@ -96,35 +132,345 @@ impl<N: Network> Ledger<N> {
         self.canon_blocks.contains_transaction(transaction)
     }
 
+    /// Returns `true` if the given serial number exists on the canon chain.
+    pub fn contains_serial_number(&self, serial_number: &N::SerialNumber) -> bool {
+        self.canon_blocks.contains_serial_number(serial_number)
+    }
+
+    /// Returns `true` if the given commitment exists on the canon chain.
+    pub fn contains_commitment(&self, commitment: &N::Commitment) -> bool {
+        self.canon_blocks.contains_commitment(commitment)
+    }
+
+    /// Returns `true` if the given program ID is deployed on the canon chain.
+    pub fn contains_program(&self, program_id: &N::ProgramID) -> bool {
+        self.canon_blocks.contains_program(program_id)
+    }
+
+    /// Returns the deployment on the canon chain given the program ID.
+    pub fn get_deployment(&self, program_id: &N::ProgramID) -> Result<&Deployment<N>> {
+        self.canon_blocks.get_deployment(program_id)
+    }
+
+    /// Returns the transaction on the canon chain given the transaction ID.
+    pub fn get_transaction(&self, transaction_id: &N::TransactionID) -> Result<&Transaction<N>> {
+        self.canon_blocks.get_transaction(transaction_id)
+    }
+
+    /// Returns the memory pool of unconfirmed transactions.
+    pub fn memory_pool(&self) -> &MemoryPool<N> {
+        &self.memory_pool
+    }
+
+    /// Updates the memory pool configuration, returning any evicted transactions.
+    pub fn set_memory_pool_config(&mut self, config: MemoryPoolConfig) -> Vec<Transaction<N>> {
+        self.memory_pool.set_config(config)
+    }
+
+    /// Returns `true` if the given ledger root is too far behind the canon chain for new transactions.
+    pub fn is_ledger_root_expired(&self, ledger_root: &N::LedgerRoot) -> bool {
+        match self.canon_blocks.get_ledger_root_age(ledger_root) {
+            Some(age) => age > self.memory_pool.config().maximum_ledger_root_age,
+            None => true,
+        }
+    }
+
+    /// Returns `true` if the given block hash exists in the orphan blocks.
+    pub fn contains_orphan_block(&self, block_hash: &N::BlockHash) -> bool {
+        self.orphan_blocks.contains_key(block_hash)
+    }
+
+    /// Returns the orphan blocks, keyed by block hash.
+    pub fn orphan_blocks(&self) -> &HashMap<N::BlockHash, Block<N>> {
+        &self.orphan_blocks
+    }
+
+    ///
     /// Adds the given canon block, if it is well-formed and does not already exist.
     /// Note: This method requires blocks to be added in order of canon block height.
-    pub fn add_next_block(&mut self, block: &Block<N>) -> Result<()> {
+    ///
+    /// Returns the transactions removed from the memory pool, as they were confirmed by,
+    /// conflict with, or reference a ledger root that has expired with the new canon blocks.
+    /// Once the block is canon, a failure to switch to an orphan branch that now connects to it
+    /// is logged, and does not reject the block.
+    ///
+    pub fn add_next_block(&mut self, block: &Block<N>) -> Result<MemoryPoolRemovals<N>> {
         // Attempt to insert the block into canon.
         self.canon_blocks.add_next(block)?;
+        let mut removals = self.revalidate_memory_pool(block);
 
-        Ok(())
+        // Attempt to extend the canon chain with any orphan blocks that now connect to it.
+        if let Err(error) = self.update_canon_chain(&mut removals) {
+            eprintln!("{}", error);
+        }
+
+        Ok(removals)
     }
 
+    ///
     /// Adds the given orphan block, if it is well-formed and does not already exist.
-    pub fn add_orphan_block(&mut self, block: &Block<N>) -> Result<()> {
+    ///
+    /// If the orphan blocks are full, the orphan blocks with a greater height than the given block are evicted,
+    /// as they are the furthest from connecting to the canon chain, and the given block is rejected if there are none.
+    ///
+    /// If the orphan block completes a fork with a greater cumulative weight than the canon chain,
+    /// the canon chain is rolled back to the fork point and the fork becomes canon.
+    ///
+    /// Returns the transactions removed from the memory pool by the new canon blocks, if any,
+    /// or an error if the fork fails to become canon.
+    ///
+    pub fn add_orphan_block(&mut self, block: &Block<N>) -> Result<MemoryPoolRemovals<N>> {
         // Ensure the block does not exist in canon.
-        if self.canon_blocks.contains_block_hash(&block.hash()) {
+        let block_hash = block.hash();
+        if self.canon_blocks.contains_block_hash(&block_hash) {
             return Err(anyhow!("Orphan block already exists in canon chain"));
         }
 
+        // Ensure the block does not exist in the orphan blocks.
+        if self.orphan_blocks.contains_key(&block_hash) {
+            return Err(anyhow!("Orphan block already exists in the orphan blocks"));
+        }
+
+        // Ensure the block is not too far behind the canon chain.
+        if block.height().saturating_add(MAXIMUM_FORK_DEPTH) <= self.latest_block_height() {
+            return Err(anyhow!("Orphan block is beyond the maximum fork depth"));
+        }
+
+        // Ensure the block itself is valid.
+        if !block.is_valid() {
+            return Err(anyhow!("The given orphan block is invalid"));
+        }
+
         // Insert the block into the orphan blocks.
-        self.orphan_blocks.insert(block.height(), block.clone());
+        self.insert_orphan_block(block.clone())?;
+
+        // Attempt to switch to a heavier fork, if one is now complete.
+        let mut removals = MemoryPoolRemovals::default();
+        self.update_canon_chain(&mut removals)?;
+        Ok(removals)
+    }
+
+    ///
+    /// Switches the canon chain to the heaviest fork among the orphan blocks, for as long as
+    /// one exists with a strictly greater cumulative weight than the canon chain.
+    ///
+    /// The transactions removed from the memory pool by the new canon blocks are added to the given removals,
+    /// including those removed before a fork fails to become canon.
+    ///
+    fn update_canon_chain(&mut self, removals: &mut MemoryPoolRemovals<N>) -> Result<()> {
+        let mut result = Ok(());
+        while let Some((fork_height, branch)) = self.find_heaviest_fork() {
+            match self.reorganize(fork_height, &branch) {
+                Ok(reorganize_removals) => removals.extend(reorganize_removals),
+                Err(error) => {
+                    result = Err(anyhow!("Failed to switch to a fork at height {}: {}", fork_height, error));
+                    break;
+                }
+            }
+        }
+
+        // Remove orphan blocks that are too far behind the canon chain.
+        let latest_block_height = self.latest_block_height();
+        let expired_block_hashes = self
+            .orphan_blocks
+            .values()
+            .filter(|block| block.height().saturating_add(MAXIMUM_FORK_DEPTH) <= latest_block_height)
+            .map(Block::hash)
+            .collect::<Vec<_>>();
+        for block_hash in expired_block_hashes {
+            self.remove_orphan_block(&block_hash);
+        }
+
+        result
+    }
+
+    /// Inserts the given orphan block, evicting the orphan blocks with a greater height if the orphan blocks are full.
+    fn insert_orphan_block(&mut self, block: Block<N>) -> Result<()> {
+        let size_in_bytes = block.to_bytes_le()?.len();
+        while self.orphan_blocks.len() >= MAXIMUM_ORPHAN_BLOCKS
+            || self.orphan_blocks_size_in_bytes + size_in_bytes > MAXIMUM_ORPHAN_BLOCKS_SIZE_IN_BYTES
+        {
+            let highest_block_hash = self
+                .orphan_blocks
+                .values()
+                .filter(|orphan_block| orphan_block.height() > block.height())
+                .max_by_key(|orphan_block| orphan_block.height())
+                .map(Block::hash);
+            match highest_block_hash {
+                Some(block_hash) => self.remove_orphan_block(&block_hash),
+                None => return Err(anyhow!("The orphan blocks are full")),
+            };
+        }
 
+        self.orphan_blocks_size_in_bytes += size_in_bytes;
+        self.orphan_blocks.insert(block.hash(), block);
         Ok(())
     }
 
-    /// Adds the given unconfirmed transaction to the memory pool.
-    pub fn add_unconfirmed_transaction(&mut self, transaction: &Transaction<N>) -> Result<()> {
+    /// Removes the orphan block with the given block hash, returning the removed block.
+    fn remove_orphan_block(&mut self, block_hash: &N::BlockHash) -> Option<Block<N>> {
+        let block = self.orphan_blocks.remove(block_hash)?;
+        let size_in_bytes = block.to_bytes_le().map_or(0, |bytes| bytes.len());
+        self.orphan_blocks_size_in_bytes = self.orphan_blocks_size_in_bytes.saturating_sub(size_in_bytes);
+        Some(block)
+    }
+
+    ///
+    /// Returns the fork height and the blocks of the orphan branch with the greatest cumulative weight,
+    /// if the branch connects to the canon chain and is heavier than the canon chain.
+    ///
+    /// As the cumulative weight of an orphan block is claimed by the block itself, the headers of a branch
+    /// are checked against the canon chain before the branch is selected, and the blocks with invalid headers
+    /// are discarded, so that a branch is only staged if its cumulative weight follows from the canon chain.
+    ///
+    fn find_heaviest_fork(&mut self) -> Option<(u32, Vec<Block<N>>)> {
+        let canon_cumulative_weight = self.latest_cumulative_weight().ok()?;
+
+        // Sort the candidate branch tips by their claimed cumulative weight, from heaviest to lightest.
+        let mut candidates = self
+            .orphan_blocks
+            .values()
+            .filter(|block| block.cumulative_weight() > canon_cumulative_weight)
+            .map(|block| (block.cumulative_weight(), block.hash()))
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|(cumulative_weight, _)| Reverse(*cumulative_weight));
+
+        for (_, tip_hash) in candidates {
+            // Skip the tips that were discarded with an invalid branch.
+            let mut branch = match self.orphan_blocks.get(&tip_hash) {
+                Some(tip) => vec![tip.clone()],
+                None => continue,
+            };
+
+            // Walk back through the orphan blocks to the canon chain.
+            let mut previous_block_hash = branch[0].previous_block_hash();
+            while let Some(parent) = self.orphan_blocks.get(&previous_block_hash) {
+                branch.push(parent.clone());
+                previous_block_hash = parent.previous_block_hash();
+            }
+
+            // Ensure the branch connects to the canon chain within the maximum fork depth.
+            let fork_height = match self.canon_blocks.get_block_height(&previous_block_hash) {
+                Ok(fork_height) if fork_height.saturating_add(MAXIMUM_FORK_DEPTH) >= self.latest_block_height() => {
+                    fork_height
+                }
+                _ => continue,
+            };
+            branch.reverse();
+
+            // Discard the blocks from the first invalid header onwards, and ensure the valid part is heavier.
+            // A branch whose headers fail to be checked is discarded, and the search continues with the other branches.
+            let num_valid_blocks = self
+                .canon_blocks
+                .count_valid_headers(fork_height, &branch)
+                .unwrap_or(0);
+            for block in branch.drain(num_valid_blocks..) {
+                self.remove_orphan_block(&block.hash());
+            }
+            match branch.last() {
+                Some(tip) if tip.cumulative_weight() > canon_cumulative_weight => return Some((fork_height, branch)),
+                _ => continue,
+            }
+        }
+
+        None
+    }
+
+    ///
+    /// Rolls back the canon chain to the given fork height, and adds the given branch of blocks as canon.
+    /// The abandoned canon blocks are kept as orphan blocks, and their transactions are returned to the memory pool.
+    /// If any block in the branch is invalid, the invalid blocks are discarded, and the canon chain is kept
+    /// if it is heavier than the valid part of the branch.
+    ///
+    /// The fork is staged in memory, by only removing the abandoned blocks and adding the branch, and persisted
+    /// with a single storage commit, so the canon chain is never left partially switched.
+    ///
+    fn reorganize(&mut self, fork_height: u32, branch: &[Block<N>]) -> Result<MemoryPoolRemovals<N>> {
+        let canon_cumulative_weight = self.latest_cumulative_weight()?;
+
+        // Stage the fork by rolling back the canon chain to the fork point.
+        let abandoned_blocks = self.canon_blocks.rollback_staged(fork_height)?;
+
+        // Add the branch to the staged chain.
+        let mut num_added_blocks = 0;
+        for block in branch {
+            if let Err(error) = self.canon_blocks.add_next_staged(block) {
+                // Discard the invalid block and its descendants in the branch.
+                for block in &branch[num_added_blocks..] {
+                    self.remove_orphan_block(&block.hash());
+                }
+
+                // Keep the canon chain, if it is heavier than the valid part of the branch.
+                if canon_cumulative_weight > self.latest_cumulative_weight()? {
+                    self.canon_blocks.restore_staged(fork_height, &abandoned_blocks)?;
+                    return Err(error);
+                }
+                break;
+            }
+            num_added_blocks += 1;
+        }
+        let added_blocks = &branch[..num_added_blocks];
+
+        // Switch the canon chain to the fork, or restore the canon chain if the fork fails to persist.
+        if let Err(error) = self.canon_blocks.commit_staged(&abandoned_blocks, added_blocks) {
+            self.canon_blocks.restore_staged(fork_height, &abandoned_blocks)?;
+            return Err(error);
+        }
+
+        let mut removals = MemoryPoolRemovals::default();
+        for block in added_blocks {
+            self.remove_orphan_block(&block.hash());
+            removals.extend(self.revalidate_memory_pool(block));
+        }
+
+        // Return the transactions of the abandoned blocks to the memory pool.
+        for block in &abandoned_blocks {
+            for transaction in block.transactions().iter() {
+                // Skip the coinbase transaction, which is only valid in its own block.
+                if transaction.value_balance().is_negative() {
+                    continue;
+                }
+                // Transactions that conflict with the new canon chain are dropped.
+                let _ = self.add_unconfirmed_transaction(transaction);
+            }
+        }
+
+        // Keep the abandoned blocks as orphans, in case their fork becomes heavier again.
+        for block in abandoned_blocks {
+            // Abandoned blocks that do not fit in the orphan blocks are dropped.
+            let _ = self.insert_orphan_block(block);
+        }
+
+        Ok(removals)
+    }
+
+    /// Removes the transactions from the memory pool that are confirmed by, or conflict with,
+    /// the given canon block, and the transactions that reference an expired ledger root.
+    fn revalidate_memory_pool(&mut self, block: &Block<N>) -> MemoryPoolRemovals<N> {
+        let canon_blocks = &self.canon_blocks;
+        let maximum_ledger_root_age = self.memory_pool.config().maximum_ledger_root_age;
+
+        self.memory_pool.revalidate(block, |ledger_root| {
+            match canon_blocks.get_ledger_root_age(ledger_root) {
+                Some(age) => age > maximum_ledger_root_age,
+                None => true,
+            }
+        })
+    }
+
+    /// Adds the given unconfirmed transaction to the memory pool, returning the transactions
+    /// that were replaced or evicted from the memory pool to make room for it.
+    pub fn add_unconfirmed_transaction(&mut self, transaction: &Transaction<N>) -> Result<Vec<Transaction<N>>> {
         // Ensure the transaction contains ledger roots from the canon chain.
         if !self.canon_blocks.contains_ledger_root(&transaction.ledger_root()) {
             return Err(anyhow!("Transaction references a non-existent ledger root"));
         }
 
+        // Ensure the transaction ledger root is recent enough.
+        if self.is_ledger_root_expired(&transaction.ledger_root()) {
+            return Err(anyhow!("Transaction references an expired ledger root"));
+        }
+
         // Ensure the transaction does not contain serial numbers already in the canon chain.
         for serial_number in transaction.serial_numbers() {
             if self.canon_blocks.contains_serial_number(serial_number) {
@@ -139,10 +485,11 @@ impl<N: Network> Ledger<N> {
             }
         }
 
-        // Attempt to add the transaction into the memory pool.
-        self.memory_pool.add_transaction(transaction)?;
+        // Ensure the transaction deploys new programs, and evaluates deployed functions.
+        self.canon_blocks.check_programs(transaction)?;
 
-        Ok(())
+        // Attempt to add the transaction into the memory pool.
+        self.memory_pool.add_transaction(transaction)
     }
 
     /// Mines a new block and adds it to the canon blocks.
@@ -152,6 +499,24 @@ impl<N: Network> Ledger<N> {
         is_public: bool,
         terminator: &AtomicBool,
         rng: &mut R,
+    ) -> Result<Record<N>> {
+        self.mine_next_block_with_timestamp(recipient, is_public, Utc::now().timestamp(), terminator, rng)
+    }
+
+    ///
+    /// Mines a new block with the given timestamp and adds it to the canon blocks.
+    ///
+    /// As the difficulty target is retargeted from the block timestamps, a local chain may space
+    /// its timestamps by `N::ALEO_BLOCK_TIME_IN_SECS` to pin the difficulty target at the genesis target.
+    /// Every block still requires a full PoSW proof.
+    ///
+    pub fn mine_next_block_with_timestamp<R: Rng + CryptoRng>(
+        &mut self,
+        recipient: Address<N>,
+        is_public: bool,
+        block_timestamp: i64,
+        terminator: &AtomicBool,
+        rng: &mut R,
     ) -> Result<Record<N>> {
         // Prepare the new block.
         let previous_block_hash = self.latest_block_hash();
@@ -161,7 +526,6 @@ impl<N: Network> Ledger<N> {
         let previous_timestamp = self.latest_block_timestamp()?;
         let previous_difficulty_target = self.latest_block_difficulty_target()?;
         let previous_cumulative_weight = self.latest_cumulative_weight()?;
-        let block_timestamp = Utc::now().timestamp();
         let difficulty_target =
             Blocks::<N>::compute_difficulty_target(previous_timestamp, previous_difficulty_target, block_timestamp);
         let cumulative_weight = previous_cumulative_weight.saturating_add((u64::MAX / difficulty_target) as u128);
@@ -170,7 +534,11 @@ impl<N: Network> Ledger<N> {
         let amount = Block::<N>::block_reward(block_height);
         let (coinbase_transaction, coinbase_record) =
             Transaction::<N>::new_coinbase(recipient, amount, is_public, rng)?;
-        let transactions = Transactions::from(&[vec![coinbase_transaction], self.memory_pool.transactions()].concat())?;
+        let coinbase_size_in_bytes = coinbase_transaction.to_bytes_le()?.len();
+        let unconfirmed_transactions = self
+            .memory_pool
+            .block_template(MAXIMUM_BLOCK_TEMPLATE_SIZE_IN_BYTES.saturating_sub(coinbase_size_in_bytes));
+        let transactions = Transactions::from(&[vec![coinbase_transaction], unconfirmed_transactions].concat())?;
 
         // Retrieve the current ledger root.
         let previous_ledger_root = self.canon_blocks.latest_ledger_root();
@@ -188,15 +556,17 @@ impl<N: Network> Ledger<N> {
             rng,
         )?;
 
-        // Attempt to add the block to the canon chain.
+        // Attempt to add the block to the canon chain, which clears the included transactions from the memory pool.
         self.add_next_block(&block)?;
 
-        // On success, clear the memory pool of its transactions.
-        self.memory_pool.clear_all_transactions();
-
         Ok(coinbase_record)
     }
 
+    /// Returns the canon blocks.
+    pub fn to_blocks(&self) -> &Blocks<N> {
+        &self.canon_blocks
+    }
+
     /// Returns the ledger tree.
     pub fn to_ledger_tree(&self) -> &LedgerTree<N> {
         self.canon_blocks.to_ledger_tree()
@@ -213,10 +583,60 @@ impl<N: Network> Ledger<N> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{testnet1::Testnet1, testnet2::Testnet2};
+    use crate::{
+        ledger::test_helpers::sample_noop_transaction,
+        testnet1::Testnet1,
+        testnet2::Testnet2,
+        virtual_machine::test_functions::{factors_function, Factors},
+    };
 
     use rand::thread_rng;
 
+    /// Returns a transaction that deploys the given deployment, paying the given fee from the given record.
+    fn sample_deploy_transaction<R: Rng + CryptoRng>(
+        ledger: &Ledger<Testnet1>,
+        caller: &Account<Testnet1>,
+        record: &Record<Testnet1>,
+        deployment: &Deployment<Testnet1>,
+        fee: AleoAmount,
+        rng: &mut R,
+    ) -> Result<Transaction<Testnet1>> {
+        let ledger_proofs = vec![ledger.to_ledger_proof(record.commitment())?, LedgerProof::default()];
+        let ledger_root = ledger_proofs[0].ledger_root();
+        let request = Request::new_deploy(
+            caller.private_key(),
+            vec![record.clone()],
+            ledger_proofs,
+            deployment.clone(),
+            fee,
+            false,
+            rng,
+        )?;
+        let (vm, _) = VirtualMachine::<Testnet1>::new(ledger_root)?.execute(&request, rng)?;
+        vm.finalize()
+    }
+
+    /// Returns the given transaction, with the events of its transition replaced by the given events.
+    fn with_events(
+        transaction: &Transaction<Testnet1>,
+        events: Vec<Event<Testnet1>>,
+    ) -> Result<Transaction<Testnet1>> {
+        let transition = &transaction.transitions()[0];
+        let transition = Transition::from(
+            transition.transition_id(),
+            transition.serial_numbers().cloned().collect(),
+            transition.ciphertexts().cloned().collect(),
+            *transition.value_balance(),
+            events,
+            transition.proof().clone(),
+        )?;
+        Transaction::from(
+            transaction.inner_circuit_id(),
+            transaction.ledger_root(),
+            vec![transition],
+        )
+    }
+
     #[test]
     fn test_new() {
         let ledger = Ledger::<Testnet1>::new().unwrap();
@@ -250,4 +670,647 @@ mod tests {
             assert_eq!(1, ledger.latest_block_height());
         }
     }
+
+    #[test]
+    fn test_mine_next_block_with_timestamp() {
+        let rng = &mut thread_rng();
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let recipient = Account::<Testnet2>::new(rng);
+        let genesis_difficulty_target = ledger.latest_block_difficulty_target().unwrap();
+
+        // Spacing the block timestamps by the block time keeps the difficulty target constant.
+        for height in 1..=2 {
+            let timestamp = ledger.latest_block_timestamp().unwrap() + Testnet2::ALEO_BLOCK_TIME_IN_SECS;
+            ledger
+                .mine_next_block_with_timestamp(recipient.address(), true, timestamp, &AtomicBool::new(false), rng)
+                .unwrap();
+            assert_eq!(height, ledger.latest_block_height());
+            assert_eq!(timestamp, ledger.latest_block_timestamp().unwrap());
+            assert_eq!(
+                genesis_difficulty_target,
+                ledger.latest_block_difficulty_target().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_add_next_block_removes_confirmed_transactions() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+        let recipient = Account::<Testnet2>::new(rng);
+        let transaction = sample_noop_transaction(rng);
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        ledger.add_unconfirmed_transaction(&transaction).unwrap();
+        assert_eq!(1, ledger.memory_pool().num_transactions());
+
+        // Mine a block with the transaction on a separate ledger.
+        let mut miner = Ledger::<Testnet2>::new().unwrap();
+        miner.add_unconfirmed_transaction(&transaction).unwrap();
+        miner
+            .mine_next_block(recipient.address(), true, &terminator, rng)
+            .unwrap();
+        assert_eq!(0, miner.memory_pool().num_transactions());
+
+        let removals = ledger.add_next_block(&miner.latest_block().unwrap()).unwrap();
+        assert_eq!(&[transaction], removals.confirmed());
+        assert!(removals.conflicting().is_empty());
+        assert!(removals.expired().is_empty());
+        assert_eq!(0, ledger.memory_pool().num_transactions());
+    }
+
+    #[test]
+    fn test_canon_chain_indexes() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+        let directory = tempfile::tempdir().unwrap();
+        let recipient = Account::<Testnet2>::new(rng);
+        let transaction = sample_noop_transaction(rng);
+
+        let mut ledger = Ledger::<Testnet2>::open(directory.path()).unwrap();
+        ledger.add_unconfirmed_transaction(&transaction).unwrap();
+        ledger
+            .mine_next_block(recipient.address(), true, &terminator, rng)
+            .unwrap();
+
+        let block_hash = ledger.latest_block_hash();
+        assert!(ledger.contains_block_hash(&block_hash));
+        assert_eq!(1, ledger.to_blocks().get_block_height(&block_hash).unwrap());
+
+        let transaction_id = transaction.transaction_id();
+        assert_eq!(&transaction, ledger.get_transaction(&transaction_id).unwrap());
+
+        for transition in transaction.transitions() {
+            let expected = (1, transaction_id, transition.transition_id());
+            for serial_number in transition.serial_numbers() {
+                assert!(ledger.contains_serial_number(serial_number));
+                assert_eq!(
+                    expected,
+                    ledger.to_blocks().get_serial_number_location(serial_number).unwrap()
+                );
+            }
+            for commitment in transition.commitments() {
+                assert!(ledger.contains_commitment(commitment));
+                assert_eq!(
+                    expected,
+                    ledger.to_blocks().get_commitment_location(commitment).unwrap()
+                );
+            }
+        }
+
+        // Ensure a ledger proof is produced for each commitment via the indexes.
+        for commitment in ledger.latest_block().unwrap().commitments() {
+            assert!(ledger.to_ledger_proof(*commitment).is_ok());
+        }
+
+        drop(ledger);
+
+        // Ensure the indexes are rebuilt when the chain is reopened from storage.
+        let open_blocks = || Blocks::<Testnet2>::open(Arc::new(FileStorage::open(directory.path()).unwrap())).unwrap();
+        let mut blocks = open_blocks();
+        assert_eq!(1, blocks.get_block_height(&block_hash).unwrap());
+        assert_eq!(&transaction, blocks.get_transaction(&transaction_id).unwrap());
+        assert!(transaction
+            .serial_numbers()
+            .all(|serial_number| blocks.contains_serial_number(serial_number)));
+
+        // Ensure the indexes are cleared on rollback, and stay cleared when the chain is reopened.
+        blocks.rollback_to(0).unwrap();
+        for blocks in [blocks, open_blocks()] {
+            assert_eq!(0, blocks.latest_block_height());
+            assert!(!blocks.contains_block_hash(&block_hash));
+            assert!(blocks.contains_block_hash(&Testnet2::genesis_block().hash()));
+            assert!(!blocks.contains_transaction(&transaction));
+            assert!(blocks.get_transaction(&transaction_id).is_err());
+            for serial_number in transaction.serial_numbers() {
+                assert!(!blocks.contains_serial_number(serial_number));
+            }
+            for commitment in transaction.commitments() {
+                assert!(!blocks.contains_commitment(commitment));
+                assert!(blocks.to_ledger_proof(*commitment).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn test_deploy_program() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+        let caller = Account::<Testnet1>::new(rng);
+
+        let (function, verifying_key) = factors_function::<Testnet1>();
+        let program = Program::new(vec![function]).unwrap();
+        let deployment = Deployment::new(&program, vec![verifying_key]).unwrap();
+        let program_id = deployment.program_id();
+
+        // Mine a record for each deployment.
+        let mut ledger = Ledger::<Testnet1>::new().unwrap();
+        let records = (0..2)
+            .map(|_| {
+                ledger
+                    .mine_next_block(caller.address(), true, &terminator, rng)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        // Ensure a deployment must pay a fee.
+        assert!(sample_deploy_transaction(&ledger, &caller, &records[0], &deployment, AleoAmount::ZERO, rng).is_err());
+
+        let fee = AleoAmount(1);
+        let transaction = sample_deploy_transaction(&ledger, &caller, &records[0], &deployment, fee, rng).unwrap();
+        let duplicate_transaction =
+            sample_deploy_transaction(&ledger, &caller, &records[1], &deployment, fee, rng).unwrap();
+        assert_eq!(vec![&deployment], transaction.deployments().collect::<Vec<_>>());
+        assert_eq!(fee, *transaction.transitions()[0].value_balance());
+
+        // Ensure the program may only be deployed once in the memory pool.
+        ledger.add_unconfirmed_transaction(&transaction).unwrap();
+        assert!(ledger.add_unconfirmed_transaction(&duplicate_transaction).is_err());
+
+        ledger
+            .mine_next_block(caller.address(), true, &terminator, rng)
+            .unwrap();
+        assert!(ledger.contains_program(&program_id));
+        assert_eq!(&deployment, ledger.get_deployment(&program_id).unwrap());
+        assert!(ledger
+            .to_blocks()
+            .contains_function(&program_id, &deployment.function_ids()[0]));
+
+        // Ensure the program may only be deployed once in the ledger.
+        assert!(ledger.add_unconfirmed_transaction(&duplicate_transaction).is_err());
+
+        // Ensure the deployment is removed on rollback.
+        let mut blocks = ledger.to_blocks().clone();
+        blocks.rollback_to(0).unwrap();
+        assert!(!blocks.contains_program(&program_id));
+        assert!(blocks.get_deployment(&program_id).is_err());
+    }
+
+    #[test]
+    fn test_tampered_deployment_is_rejected() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+        let caller = Account::<Testnet1>::new(rng);
+
+        let (function, verifying_key) = factors_function::<Testnet1>();
+        let deployment = Deployment::new(&Program::new(vec![function]).unwrap(), vec![verifying_key]).unwrap();
+        let noop_program = Program::new(vec![Arc::new(Noop::<Testnet1>::new())]).unwrap();
+        let noop_deployment =
+            Deployment::new(&noop_program, vec![Testnet1::noop_circuit_verifying_key().clone()]).unwrap();
+
+        let mut ledger = Ledger::<Testnet1>::new().unwrap();
+        let record = ledger
+            .mine_next_block(caller.address(), true, &terminator, rng)
+            .unwrap();
+        let transaction =
+            sample_deploy_transaction(&ledger, &caller, &record, &deployment, AleoAmount(1), rng).unwrap();
+        assert!(transaction.is_valid());
+
+        let ledger_proofs = vec![LedgerProof::default(); Testnet1::NUM_INPUT_RECORDS];
+        let noop_request = Request::new_noop(ledger_proofs, rng).unwrap();
+        let noop_transaction = Transaction::new(LedgerTree::new().unwrap(), &noop_request, rng).unwrap();
+        assert!(noop_transaction.is_valid());
+
+        // Ensure a deployment may not be stripped from, swapped in, or injected into a transition,
+        // as its deployment record is revealed and bound to the transition ID.
+        let deploy = |deployment: &Deployment<Testnet1>| Event::Operation(Operation::Deploy(deployment.clone()));
+        let reveal = transaction
+            .events()
+            .find(|event| matches!(event, Event::RecordViewKey(..)))
+            .unwrap()
+            .clone();
+        let noop_reveal = noop_transaction.events().next().unwrap().clone();
+        for tampered_events in [
+            vec![reveal.clone()],
+            vec![deploy(&noop_deployment), reveal.clone()],
+            vec![deploy(&deployment), deploy(&deployment)],
+            vec![deploy(&deployment)],
+            vec![],
+        ] {
+            assert!(with_events(&transaction, tampered_events).is_err());
+        }
+        assert!(with_events(&noop_transaction, vec![deploy(&deployment), noop_reveal]).is_err());
+
+        ledger.add_unconfirmed_transaction(&transaction).unwrap();
+    }
+
+    #[test]
+    fn test_replace_deployment_by_fee() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+        let caller = Account::<Testnet1>::new(rng);
+
+        let (function, verifying_key) = factors_function::<Testnet1>();
+        let deployment = Deployment::new(&Program::new(vec![function]).unwrap(), vec![verifying_key]).unwrap();
+
+        let mut ledger = Ledger::<Testnet1>::new().unwrap();
+        let records = (0..2)
+            .map(|_| {
+                ledger
+                    .mine_next_block(caller.address(), true, &terminator, rng)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        // Deploy the same program by spending the same record with different fees, and by spending another record.
+        let low_fee =
+            sample_deploy_transaction(&ledger, &caller, &records[0], &deployment, AleoAmount(1), rng).unwrap();
+        let high_fee =
+            sample_deploy_transaction(&ledger, &caller, &records[0], &deployment, AleoAmount(2), rng).unwrap();
+        let duplicate =
+            sample_deploy_transaction(&ledger, &caller, &records[1], &deployment, AleoAmount(3), rng).unwrap();
+        ledger.add_unconfirmed_transaction(&low_fee).unwrap();
+
+        // Ensure a deployment with a higher fee replaces the conflicting deployment.
+        assert_eq!(vec![low_fee], ledger.add_unconfirmed_transaction(&high_fee).unwrap());
+        assert_eq!(vec![high_fee], ledger.memory_pool().transactions());
+
+        // Ensure a deployment of the same program that does not conflict is still rejected.
+        assert!(ledger.add_unconfirmed_transaction(&duplicate).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_program() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+        let caller = Account::<Testnet1>::new(rng);
+
+        let (function, verifying_key) = factors_function::<Testnet1>();
+        let function_id = function.function_id();
+        let program = Program::new(vec![function]).unwrap();
+        let program_id = program.program_id();
+        let deployment = Deployment::new(&program, vec![verifying_key.clone()]).unwrap();
+
+        let mut programs = Programs::<Testnet1>::new().unwrap();
+        programs.add(program, vec![verifying_key]).unwrap();
+        let programs = Arc::new(programs);
+
+        // Evaluate the factors function publicly and privately, as each reveals the program ID in its first output record.
+        let mut transactions = Vec::with_capacity(2);
+        for is_public in [true, false] {
+            let records = (0..Testnet1::NUM_INPUT_RECORDS)
+                .map(|_| Record::new(caller.address(), AleoAmount::ZERO, Payload::default(), program_id, rng).unwrap())
+                .collect();
+            let function_inputs = FunctionInputs::new(
+                &caller.address(),
+                &caller.address(),
+                AleoAmount::ZERO,
+                Payload::default(),
+            );
+            let request = Request::new(
+                caller.private_key(),
+                records,
+                vec![LedgerProof::default(); Testnet1::NUM_INPUT_RECORDS],
+                Operation::Evaluate(function_id, FunctionType::Full, function_inputs),
+                AleoAmount::ZERO,
+                is_public,
+                rng,
+            )
+            .unwrap();
+
+            let ledger_root = LedgerTree::<Testnet1>::new().unwrap().root();
+            let vm = VirtualMachine::<Testnet1>::new_with_programs(ledger_root, programs.clone()).unwrap();
+            let (vm, _) = vm
+                .execute_with_private_variables(&request, &Factors(11, 13), rng)
+                .unwrap();
+            let transaction = vm.finalize().unwrap();
+            assert_eq!(program_id, transaction.transitions()[0].to_program_id().unwrap());
+            transactions.push(transaction);
+        }
+
+        // Ensure the first output record may not be hidden, as it binds the program ID of the transition.
+        assert!(with_events(&transactions[1], vec![]).is_err());
+
+        // Ensure an evaluation of an undeployed program is rejected, even without its evaluation event.
+        let private_transaction = &transactions[1];
+        assert_eq!(0, private_transaction.events().filter(|event| matches!(event, Event::Operation(..))).count());
+
+        let mut ledger = Ledger::<Testnet1>::new().unwrap();
+        for transaction in &transactions {
+            assert!(ledger.to_blocks().check_programs(transaction).is_err());
+        }
+
+        // Ensure an evaluation of a deployed program is accepted.
+        let record = ledger
+            .mine_next_block(caller.address(), true, &terminator, rng)
+            .unwrap();
+        let deploy_transaction =
+            sample_deploy_transaction(&ledger, &caller, &record, &deployment, AleoAmount(1), rng).unwrap();
+        ledger.add_unconfirmed_transaction(&deploy_transaction).unwrap();
+        ledger
+            .mine_next_block(caller.address(), true, &terminator, rng)
+            .unwrap();
+        for transaction in &transactions {
+            assert!(ledger.to_blocks().check_programs(transaction).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_add_next_block_removes_expired_transactions() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+        let recipient = Account::<Testnet2>::new(rng);
+        let transaction = sample_noop_transaction(rng);
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        ledger.set_memory_pool_config(MemoryPoolConfig {
+            maximum_ledger_root_age: 1,
+            ..Default::default()
+        });
+        ledger.add_unconfirmed_transaction(&transaction).unwrap();
+
+        // Mine a block without the transaction on a separate ledger.
+        let mut miner = Ledger::<Testnet2>::new().unwrap();
+        miner
+            .mine_next_block(recipient.address(), true, &terminator, rng)
+            .unwrap();
+
+        let removals = ledger.add_next_block(&miner.latest_block().unwrap()).unwrap();
+        assert!(removals.confirmed().is_empty());
+        assert_eq!(std::slice::from_ref(&transaction), removals.expired());
+        assert!(ledger.is_ledger_root_expired(&transaction.ledger_root()));
+        assert!(ledger.add_unconfirmed_transaction(&transaction).is_err());
+    }
+
+    #[test]
+    fn test_reorganize_to_heavier_fork() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+        let recipient = Account::<Testnet2>::new(rng);
+
+        // Mine a canon chain with one block, and a separate fork with two blocks.
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        ledger
+            .mine_next_block(recipient.address(), true, &terminator, rng)
+            .unwrap();
+        let canon_block = ledger.latest_block().unwrap();
+
+        let mut fork = Ledger::<Testnet2>::new().unwrap();
+        fork.mine_next_block(recipient.address(), true, &terminator, rng)
+            .unwrap();
+        fork.mine_next_block(recipient.address(), true, &terminator, rng)
+            .unwrap();
+        let fork_block_1 = fork.get_block(1).unwrap();
+        let fork_block_2 = fork.get_block(2).unwrap();
+
+        // A fork with equal cumulative weight does not replace the canon chain.
+        assert_eq!(canon_block.cumulative_weight(), fork_block_1.cumulative_weight());
+        ledger.add_orphan_block(&fork_block_1).unwrap();
+        assert_eq!(canon_block, ledger.latest_block().unwrap());
+        assert!(ledger.contains_orphan_block(&fork_block_1.hash()));
+
+        // A heavier fork replaces the canon chain up to the fork point.
+        ledger.add_orphan_block(&fork_block_2).unwrap();
+        assert_eq!(2, ledger.latest_block_height());
+        assert_eq!(fork_block_1, ledger.get_block(1).unwrap());
+        assert_eq!(fork_block_2, ledger.latest_block().unwrap());
+        assert_eq!(fork.latest_ledger_root(), ledger.latest_ledger_root());
+
+        // The abandoned canon block is kept as an orphan.
+        assert!(ledger.contains_orphan_block(&canon_block.hash()));
+        assert!(!ledger.contains_orphan_block(&fork_block_1.hash()));
+        assert!(!ledger.contains_block_hash(&canon_block.hash()));
+    }
+
+    #[test]
+    fn test_reorganize_persists_fork() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+        let directory = tempfile::tempdir().unwrap();
+        let recipient = Account::<Testnet2>::new(rng);
+
+        let mut fork = Ledger::<Testnet2>::new().unwrap();
+        fork.mine_next_block(recipient.address(), true, &terminator, rng)
+            .unwrap();
+        fork.mine_next_block(recipient.address(), true, &terminator, rng)
+            .unwrap();
+
+        // Switch a stored canon chain with one block to the heavier fork.
+        {
+            let mut ledger = Ledger::<Testnet2>::open(directory.path()).unwrap();
+            ledger
+                .mine_next_block(recipient.address(), true, &terminator, rng)
+                .unwrap();
+            ledger.add_orphan_block(&fork.get_block(1).unwrap()).unwrap();
+            ledger.add_orphan_block(&fork.get_block(2).unwrap()).unwrap();
+            assert_eq!(fork.latest_block().unwrap(), ledger.latest_block().unwrap());
+        }
+
+        // Ensure the reopened ledger loads the fork.
+        let ledger = Ledger::<Testnet2>::open(directory.path()).unwrap();
+        assert_eq!(2, ledger.latest_block_height());
+        assert_eq!(fork.get_block(1).unwrap(), ledger.get_block(1).unwrap());
+        assert_eq!(fork.latest_block().unwrap(), ledger.latest_block().unwrap());
+        assert_eq!(fork.latest_ledger_root(), ledger.latest_ledger_root());
+    }
+
+    #[test]
+    fn test_reorganize_returns_abandoned_transactions() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+        let recipient = Account::<Testnet2>::new(rng);
+
+        // Mine a canon block with a non-coinbase transaction.
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let transaction = sample_noop_transaction(rng);
+        ledger.add_unconfirmed_transaction(&transaction).unwrap();
+        ledger
+            .mine_next_block(recipient.address(), true, &terminator, rng)
+            .unwrap();
+        assert!(ledger.contains_transaction(&transaction));
+        assert!(ledger.memory_pool().transactions().is_empty());
+
+        // Switch to a heavier fork without the transaction.
+        let mut fork = Ledger::<Testnet2>::new().unwrap();
+        fork.mine_next_block(recipient.address(), true, &terminator, rng)
+            .unwrap();
+        fork.mine_next_block(recipient.address(), true, &terminator, rng)
+            .unwrap();
+        ledger.add_orphan_block(&fork.get_block(1).unwrap()).unwrap();
+        ledger.add_orphan_block(&fork.get_block(2).unwrap()).unwrap();
+        assert_eq!(fork.latest_block().unwrap(), ledger.latest_block().unwrap());
+
+        // Ensure the transaction of the abandoned block returns to the memory pool, without its coinbase transaction.
+        assert!(!ledger.contains_transaction(&transaction));
+        assert_eq!(vec![transaction], ledger.memory_pool().transactions());
+    }
+
+    #[test]
+    fn test_orphan_block_with_invalid_cumulative_weight_is_discarded() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+        let recipient = Account::<Testnet2>::new(rng);
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        ledger
+            .mine_next_block(recipient.address(), true, &terminator, rng)
+            .unwrap();
+        let canon_block = ledger.latest_block().unwrap();
+
+        // Mine a fork block that claims a greater cumulative weight than its difficulty target adds.
+        let genesis_block = Testnet2::genesis_block();
+        let timestamp = genesis_block.timestamp() + Testnet2::ALEO_BLOCK_TIME_IN_SECS;
+        let difficulty_target = Blocks::<Testnet2>::compute_difficulty_target(
+            genesis_block.timestamp(),
+            genesis_block.difficulty_target(),
+            timestamp,
+        );
+        let (coinbase_transaction, _) =
+            Transaction::new_coinbase(recipient.address(), Block::<Testnet2>::block_reward(1), true, rng).unwrap();
+        let block = Block::mine(
+            genesis_block.hash(),
+            1,
+            timestamp,
+            difficulty_target,
+            u128::MAX,
+            canon_block.previous_ledger_root(),
+            Transactions::from(&[coinbase_transaction]).unwrap(),
+            &terminator,
+            rng,
+        )
+        .unwrap();
+
+        // Ensure the fork block is discarded by its header, and the canon chain is kept.
+        ledger.add_orphan_block(&block).unwrap();
+        assert_eq!(canon_block, ledger.latest_block().unwrap());
+        assert!(!ledger.contains_orphan_block(&block.hash()));
+    }
+
+    #[test]
+    fn test_add_next_block_with_invalid_orphan_branch() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+        let recipient = Account::<Testnet2>::new(rng);
+        let transaction = sample_noop_transaction(rng);
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        ledger.add_unconfirmed_transaction(&transaction).unwrap();
+
+        // Mine a block with the transaction on a separate ledger.
+        let mut miner = Ledger::<Testnet2>::new().unwrap();
+        miner.add_unconfirmed_transaction(&transaction).unwrap();
+        miner
+            .mine_next_block(recipient.address(), true, &terminator, rng)
+            .unwrap();
+        let block = miner.latest_block().unwrap();
+
+        // Mine a child block with a valid header, that confirms the transaction a second time.
+        let timestamp = block.timestamp() + Testnet2::ALEO_BLOCK_TIME_IN_SECS;
+        let difficulty_target =
+            Blocks::<Testnet2>::compute_difficulty_target(block.timestamp(), block.difficulty_target(), timestamp);
+        let (coinbase_transaction, _) =
+            Transaction::new_coinbase(recipient.address(), Block::<Testnet2>::block_reward(2), true, rng).unwrap();
+        let orphan_block = Block::mine(
+            block.hash(),
+            2,
+            timestamp,
+            difficulty_target,
+            block.cumulative_weight() + (u64::MAX / difficulty_target) as u128,
+            miner.latest_ledger_root(),
+            Transactions::from(&[coinbase_transaction, transaction.clone()]).unwrap(),
+            &terminator,
+            rng,
+        )
+        .unwrap();
+        ledger.add_orphan_block(&orphan_block).unwrap();
+
+        // Ensure the block is accepted, and its removals are returned, when its orphan child fails to become canon.
+        let removals = ledger.add_next_block(&block).unwrap();
+        assert_eq!(std::slice::from_ref(&transaction), removals.confirmed());
+        assert_eq!(block, ledger.latest_block().unwrap());
+        assert!(!ledger.contains_orphan_block(&orphan_block.hash()));
+        assert!(ledger.memory_pool().transactions().is_empty());
+    }
+
+    #[test]
+    fn test_orphan_blocks_keyed_by_hash() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        ledger
+            .mine_next_block(Account::<Testnet2>::new(rng).address(), true, &terminator, rng)
+            .unwrap();
+
+        // Two orphans at the same height are both retained.
+        let mut orphans = Vec::with_capacity(2);
+        for _ in 0..2 {
+            let mut fork = Ledger::<Testnet2>::new().unwrap();
+            fork.mine_next_block(Account::<Testnet2>::new(rng).address(), true, &terminator, rng)
+                .unwrap();
+            let orphan = fork.latest_block().unwrap();
+            ledger.add_orphan_block(&orphan).unwrap();
+            orphans.push(orphan);
+        }
+
+        assert_eq!(2, ledger.orphan_blocks().len());
+        assert!(orphans
+            .iter()
+            .all(|orphan| ledger.contains_orphan_block(&orphan.hash())));
+        assert!(ledger.add_orphan_block(&orphans[0]).is_err());
+    }
+
+    #[test]
+    fn test_open_existing_ledger() {
+        let rng = &mut thread_rng();
+        let directory = tempfile::tempdir().unwrap();
+        let recipient = Account::<Testnet2>::new(rng);
+
+        let latest_block = {
+            let mut ledger = Ledger::<Testnet2>::open(directory.path()).unwrap();
+            assert_eq!(0, ledger.latest_block_height());
+            ledger
+                .mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng)
+                .unwrap();
+            ledger.latest_block().unwrap()
+        };
+
+        let ledger = Ledger::<Testnet2>::open(directory.path()).unwrap();
+        assert_eq!(1, ledger.latest_block_height());
+        assert_eq!(latest_block, ledger.latest_block().unwrap());
+        assert!(ledger.contains_ledger_root(&latest_block.previous_ledger_root()));
+    }
+
+    #[test]
+    fn test_recover_from_partial_block_write() {
+        let rng = &mut thread_rng();
+        let directory = tempfile::tempdir().unwrap();
+        let log_path = directory.path().join(STORAGE_LOG_FILE_NAME);
+        let recipient = Account::<Testnet2>::new(rng);
+
+        let (first_block, length_after_first_block) = {
+            let mut ledger = Ledger::<Testnet2>::open(directory.path()).unwrap();
+            ledger
+                .mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng)
+                .unwrap();
+            let length_after_first_block = std::fs::metadata(&log_path).unwrap().len();
+
+            ledger
+                .mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng)
+                .unwrap();
+            (ledger.get_block(1).unwrap(), length_after_first_block)
+        };
+
+        // Simulate the process being killed partway through writing the second block.
+        let length_after_second_block = std::fs::metadata(&log_path).unwrap().len();
+        let partial_length = (length_after_first_block + length_after_second_block) / 2;
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(&log_path)
+            .unwrap()
+            .set_len(partial_length)
+            .unwrap();
+
+        // Ensure the ledger recovers to the last fully-written block.
+        let mut ledger = Ledger::<Testnet2>::open(directory.path()).unwrap();
+        assert_eq!(1, ledger.latest_block_height());
+        assert_eq!(first_block, ledger.latest_block().unwrap());
+        assert_eq!(length_after_first_block, std::fs::metadata(&log_path).unwrap().len());
+
+        // Ensure the recovered ledger can continue to extend the chain.
+        ledger
+            .mine_next_block(recipient.address(), true, &AtomicBool::new(false), rng)
+            .unwrap();
+        assert_eq!(2, ledger.latest_block_height());
+    }
 }
@@ -16,10 +16,15 @@
 
 use crate::prelude::*;
 use snarkvm_algorithms::merkle_tree::MerklePath;
-use snarkvm_utilities::{FromBytes, ToBytes};
+use snarkvm_utilities::{FromBytes, FromBytesDeserializer, ToBytes, ToBytesSerializer};
 
 use anyhow::{anyhow, Result};
-use std::io::{Read, Result as IoResult, Write};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt,
+    io::{Read, Result as IoResult, Write},
+    str::FromStr,
+};
 
 /// A ledger proof of inclusion.
 #[derive(Derivative)]
@@ -132,6 +137,14 @@ impl<N: Network> LedgerProof<N> {
     pub fn local_proof(&self) -> &LocalProof<N> {
         &self.record_proof.local_proof()
     }
+
+    /// Returns `true` if this ledger proof was constructed with `LedgerProof::new_dummy` or `LedgerProof::default`.
+    pub(crate) fn is_dummy(&self) -> bool {
+        let dummy = Self::new_dummy(self.local_proof().clone())
+            .ok()
+            .and_then(|dummy| dummy.to_bytes_le().ok());
+        dummy == self.to_bytes_le().ok()
+    }
 }
 
 impl<N: Network> FromBytes for LedgerProof<N> {
@@ -141,8 +154,22 @@ impl<N: Network> FromBytes for LedgerProof<N> {
         let ledger_root_inclusion_proof = FromBytes::read_le(&mut reader)?;
         let record_proof = FromBytes::read_le(&mut reader)?;
 
-        Ok(Self::new(ledger_root, ledger_root_inclusion_proof, record_proof)
-            .expect("Failed to deserialize a ledger inclusion proof"))
+        // Dummy ledger proofs, which are used for local and noop records, do not prove inclusion in the ledger.
+        let ledger_proof = Self {
+            ledger_root,
+            ledger_root_inclusion_proof,
+            record_proof,
+        };
+        if ledger_proof.is_dummy() {
+            return Ok(ledger_proof);
+        }
+
+        let Self {
+            ledger_root,
+            ledger_root_inclusion_proof,
+            record_proof,
+        } = ledger_proof;
+        Ok(Self::new(ledger_root, ledger_root_inclusion_proof, record_proof).map_err(VMError::from)?)
     }
 }
 
@@ -155,6 +182,39 @@ impl<N: Network> ToBytes for LedgerProof<N> {
     }
 }
 
+impl<N: Network> FromStr for LedgerProof<N> {
+    type Err = anyhow::Error;
+
+    fn from_str(ledger_proof_hex: &str) -> Result<Self, Self::Err> {
+        Ok(Self::read_le(&hex::decode(ledger_proof_hex)?[..])?)
+    }
+}
+
+impl<N: Network> fmt::Display for LedgerProof<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bytes = self.to_bytes_le().expect("Failed to convert ledger proof to bytes");
+        write!(f, "{}", hex::encode(bytes))
+    }
+}
+
+impl<N: Network> Serialize for LedgerProof<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => serializer.collect_str(self),
+            false => ToBytesSerializer::serialize_with_size_encoding(self, serializer),
+        }
+    }
+}
+
+impl<'de, N: Network> Deserialize<'de> for LedgerProof<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => FromStr::from_str(&String::deserialize(deserializer)?).map_err(de::Error::custom),
+            false => FromBytesDeserializer::<Self>::deserialize_with_size_encoding(deserializer, "ledger proof"),
+        }
+    }
+}
+
 impl<N: Network> Default for LedgerProof<N> {
     fn default() -> Self {
         Self {
@@ -16,7 +16,7 @@
 
 use crate::prelude::*;
 use snarkvm_algorithms::{
-    merkle_tree::{MerklePath, MerkleTree},
+    merkle_tree::{IncrementalMerkleTree, MerklePath},
     prelude::*,
 };
 use snarkvm_utilities::has_duplicates;
@@ -29,7 +29,7 @@ use std::{collections::HashMap, sync::Arc};
 #[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"))]
 pub struct LedgerTree<N: Network> {
     #[derivative(Debug = "ignore")]
-    tree: Arc<MerkleTree<N::LedgerRootParameters>>,
+    tree: IncrementalMerkleTree<N::LedgerRootParameters>,
     block_hashes: HashMap<N::BlockHash, u32>,
     current_index: u32,
 }
@@ -38,10 +38,10 @@ impl<N: Network> LedgerTreeScheme<N> for LedgerTree<N> {
     /// Initializes an empty ledger tree.
     fn new() -> Result<Self> {
         Ok(Self {
-            tree: Arc::new(MerkleTree::<N::LedgerRootParameters>::new::<N::BlockHash>(
+            tree: IncrementalMerkleTree::<N::LedgerRootParameters>::new::<N::BlockHash>(
                 Arc::new(N::ledger_root_parameters().clone()),
-                &vec![],
-            )?),
+                &[],
+            )?,
             block_hashes: Default::default(),
             current_index: 0,
         })
@@ -55,7 +55,7 @@ impl<N: Network> LedgerTreeScheme<N> for LedgerTree<N> {
             return Err(MerkleError::Message(format!("{} already exists in the ledger tree", block_hash)).into());
         }
 
-        self.tree = Arc::new(self.tree.rebuild(self.current_index as usize, &[block_hash])?);
+        self.tree.append(block_hash)?;
         self.block_hashes.insert(*block_hash, self.current_index);
         self.current_index += 1;
 
@@ -84,14 +84,8 @@ impl<N: Network> LedgerTreeScheme<N> for LedgerTree<N> {
         let start_index = self.current_index;
         let num_block_hashes = block_hashes.len();
 
-        // Add the block hashes to the tree. Start the tree from scratch if the tree is currently empty.
-        self.tree = match self.current_index {
-            0 => Arc::new(MerkleTree::<N::LedgerRootParameters>::new::<N::BlockHash>(
-                Arc::new(N::ledger_root_parameters().clone()),
-                &block_hashes,
-            )?),
-            _ => Arc::new(self.tree.rebuild(self.current_index as usize, &block_hashes)?),
-        };
+        // Add the block hashes to the tree.
+        self.tree.append_all(block_hashes)?;
 
         self.block_hashes.extend(
             block_hashes
@@ -105,6 +99,21 @@ impl<N: Network> LedgerTreeScheme<N> for LedgerTree<N> {
         Ok((start_index, end_index))
     }
 
+    /// Removes all block hashes at or after the given index from the tree.
+    fn truncate(&mut self, index: u32) -> Result<()> {
+        // Ensure the given index is within the tree.
+        if index > self.current_index {
+            return Err(anyhow!("The given index {} exceeds the number of block hashes", index));
+        }
+
+        self.tree.truncate(index as usize)?;
+        self.block_hashes
+            .retain(|_, block_hash_index| *block_hash_index < index);
+        self.current_index = index;
+
+        Ok(())
+    }
+
     /// Returns `true` if the given block hash exists.
     fn contains_block_hash(&self, block_hash: &N::BlockHash) -> bool {
         self.block_hashes.contains_key(block_hash)
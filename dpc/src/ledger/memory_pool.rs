@@ -15,31 +15,234 @@
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::prelude::*;
+use snarkvm_utilities::ToBytes;
 
 use anyhow::{anyhow, Result};
-use std::collections::{HashMap, HashSet};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
+};
+
+/// The configuration of a memory pool.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MemoryPoolConfig {
+    /// The maximum number of transactions in the memory pool.
+    pub maximum_num_transactions: usize,
+    /// The maximum total size of the transactions in the memory pool, in bytes.
+    pub maximum_size_in_bytes: usize,
+    /// The maximum number of blocks that a transaction ledger root may fall behind the latest block.
+    pub maximum_ledger_root_age: u32,
+}
+
+impl Default for MemoryPoolConfig {
+    fn default() -> Self {
+        Self {
+            maximum_num_transactions: 10_000,
+            maximum_size_in_bytes: 64 * 1024 * 1024,
+            maximum_ledger_root_age: 100,
+        }
+    }
+}
+
+/// The fee paid by a transaction per byte of its size, compared without division.
+#[derive(Copy, Clone, Debug)]
+pub struct FeeRate {
+    fee: AleoAmount,
+    size_in_bytes: usize,
+}
+
+impl FeeRate {
+    /// Initializes a new fee rate from the given fee and size.
+    pub fn new(fee: AleoAmount, size_in_bytes: usize) -> Self {
+        Self { fee, size_in_bytes }
+    }
+
+    /// Returns the fee.
+    pub fn fee(&self) -> AleoAmount {
+        self.fee
+    }
+
+    /// Returns the size in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.size_in_bytes
+    }
+}
+
+impl PartialEq for FeeRate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for FeeRate {}
+
+impl PartialOrd for FeeRate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FeeRate {
+    /// Compares `fee_a / size_a` with `fee_b / size_b` as `fee_a * size_b` with `fee_b * size_a`.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let a = self.fee.as_i64() as i128 * other.size_in_bytes.max(1) as i128;
+        let b = other.fee.as_i64() as i128 * self.size_in_bytes.max(1) as i128;
+        a.cmp(&b)
+    }
+}
+
+/// The transactions removed from the memory pool after a block is added to the canon chain.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryPoolRemovals<N: Network> {
+    /// The transactions that were included in the block.
+    confirmed: Vec<Transaction<N>>,
+    /// The transactions that spend a serial number, or reuse a commitment, from the block.
+    conflicting: Vec<Transaction<N>>,
+    /// The transactions that reference a ledger root that is no longer acceptable.
+    expired: Vec<Transaction<N>>,
+}
+
+impl<N: Network> MemoryPoolRemovals<N> {
+    /// Returns the transactions that were included in the block.
+    pub fn confirmed(&self) -> &[Transaction<N>] {
+        &self.confirmed
+    }
+
+    /// Returns the transactions that conflict with the block.
+    pub fn conflicting(&self) -> &[Transaction<N>] {
+        &self.conflicting
+    }
+
+    /// Returns the transactions that reference a ledger root that is no longer acceptable.
+    pub fn expired(&self) -> &[Transaction<N>] {
+        &self.expired
+    }
+
+    /// Returns all removed transactions.
+    pub fn transactions(&self) -> impl Iterator<Item = &Transaction<N>> + '_ {
+        self.confirmed.iter().chain(&self.conflicting).chain(&self.expired)
+    }
+
+    /// Returns `true` if no transactions were removed.
+    pub fn is_empty(&self) -> bool {
+        self.confirmed.is_empty() && self.conflicting.is_empty() && self.expired.is_empty()
+    }
+
+    /// Appends the removals from the given instance to this instance.
+    pub fn extend(&mut self, other: Self) {
+        self.confirmed.extend(other.confirmed);
+        self.conflicting.extend(other.conflicting);
+        self.expired.extend(other.expired);
+    }
+}
+
+/// The priority of a transaction in the memory pool, ordered from the highest to lowest priority.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct MemoryPoolPriority {
+    /// The fee rate of the transaction.
+    fee_rate: FeeRate,
+    /// The order in which the transaction was added, used to break ties in priority.
+    sequence: u64,
+}
+
+impl PartialOrd for MemoryPoolPriority {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MemoryPoolPriority {
+    /// Orders a higher fee rate first, and an earlier transaction first among equal fee rates.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .fee_rate
+            .cmp(&self.fee_rate)
+            .then_with(|| self.sequence.cmp(&other.sequence))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct MemoryPoolEntry<N: Network> {
+    /// The unconfirmed transaction.
+    transaction: Transaction<N>,
+    /// The priority of the transaction.
+    priority: MemoryPoolPriority,
+}
 
 #[derive(Clone, Debug)]
 pub struct MemoryPool<N: Network> {
+    /// The configuration of the memory pool.
+    config: MemoryPoolConfig,
     /// The pool of unconfirmed transactions.
-    transactions: HashMap<N::TransactionID, Transaction<N>>,
-    /// The list of unconfirmed serial numbers.
-    serial_numbers: HashSet<N::SerialNumber>,
+    transactions: HashMap<N::TransactionID, MemoryPoolEntry<N>>,
+    /// The unconfirmed transaction IDs, ordered from the highest to lowest priority.
+    priorities: BTreeMap<MemoryPoolPriority, N::TransactionID>,
+    /// The list of unconfirmed serial numbers, mapped to the transaction that spends them.
+    serial_numbers: HashMap<N::SerialNumber, N::TransactionID>,
     /// The list of unconfirmed commitments.
     commitments: HashSet<N::Commitment>,
+    /// The list of unconfirmed program deployments, mapped to the transaction that deploys them.
+    deployments: HashMap<N::ProgramID, N::TransactionID>,
     /// The set of open requests.
     requests: HashSet<Request<N>>,
+    /// The total size of the unconfirmed transactions, in bytes.
+    size_in_bytes: usize,
+    /// The sequence number for the next added transaction.
+    next_sequence: u64,
 }
 
 impl<N: Network> MemoryPool<N> {
     /// Initializes a new instance of a memory pool.
     pub fn new() -> Self {
+        Self::with_config(Default::default())
+    }
+
+    /// Initializes a new instance of a memory pool with the given configuration.
+    pub fn with_config(config: MemoryPoolConfig) -> Self {
         Self {
+            config,
             transactions: Default::default(),
+            priorities: Default::default(),
             serial_numbers: Default::default(),
             commitments: Default::default(),
+            deployments: Default::default(),
             requests: Default::default(),
+            size_in_bytes: 0,
+            next_sequence: 0,
+        }
+    }
+
+    /// Returns the configuration of the memory pool.
+    pub fn config(&self) -> &MemoryPoolConfig {
+        &self.config
+    }
+
+    /// Updates the configuration of the memory pool, evicting the lowest-fee transactions
+    /// until the memory pool is within its new limits. Returns the evicted transactions.
+    pub fn set_config(&mut self, config: MemoryPoolConfig) -> Vec<Transaction<N>> {
+        self.config = config;
+
+        let mut evicted = Vec::new();
+        while self.transactions.len() > self.config.maximum_num_transactions
+            || self.size_in_bytes > self.config.maximum_size_in_bytes
+        {
+            let lowest_id = match self.priorities.values().next_back() {
+                Some(lowest_id) => *lowest_id,
+                None => break,
+            };
+            evicted.extend(self.remove_entry(&lowest_id));
         }
+        evicted
+    }
+
+    /// Returns the number of transactions in the memory pool.
+    pub fn num_transactions(&self) -> usize {
+        self.transactions.len()
+    }
+
+    /// Returns the total size of the transactions in the memory pool, in bytes.
+    pub fn size_in_bytes(&self) -> usize {
+        self.size_in_bytes
     }
 
     /// Returns `true` if the given transaction exists in the memory pool.
@@ -47,13 +250,42 @@ impl<N: Network> MemoryPool<N> {
         self.transactions.contains_key(&transaction.transaction_id())
     }
 
-    /// Returns the transactions in the memory pool.
+    /// Returns the transactions in the memory pool, ordered from the highest to lowest fee per byte.
     pub fn transactions(&self) -> Vec<Transaction<N>> {
-        self.transactions.values().cloned().collect()
+        self.sorted_entries()
+            .into_iter()
+            .map(|entry| entry.transaction.clone())
+            .collect()
     }
 
-    /// Adds the given unconfirmed transaction to the memory pool.
-    pub fn add_transaction(&mut self, transaction: &Transaction<N>) -> Result<()> {
+    ///
+    /// Returns the transactions for a block template, ordered from the highest to lowest fee per byte,
+    /// with a total size that does not exceed the given number of bytes.
+    ///
+    pub fn block_template(&self, maximum_size_in_bytes: usize) -> Vec<Transaction<N>> {
+        let mut transactions = Vec::new();
+        let mut remaining_size_in_bytes = maximum_size_in_bytes;
+        for entry in self.sorted_entries() {
+            // Skip transactions that do not fit, as a smaller transaction may still fit.
+            let size_in_bytes = entry.priority.fee_rate.size_in_bytes();
+            if size_in_bytes <= remaining_size_in_bytes {
+                remaining_size_in_bytes -= size_in_bytes;
+                transactions.push(entry.transaction.clone());
+            }
+        }
+        transactions
+    }
+
+    ///
+    /// Adds the given unconfirmed transaction to the memory pool, returning the transactions
+    /// that were replaced or evicted to make room for it.
+    ///
+    /// A transaction that spends the same serial numbers as transactions in the memory pool
+    /// replaces them, if it pays a strictly higher fee per byte than each of them, and a higher
+    /// total fee than all of them combined. If the memory pool is full, the transactions with
+    /// the lowest fee per byte are evicted, if they pay less than the given transaction.
+    ///
+    pub fn add_transaction(&mut self, transaction: &Transaction<N>) -> Result<Vec<Transaction<N>>> {
         // Ensure the unconfirmed transaction itself is valid.
         if !transaction.is_valid() {
             return Err(anyhow!("The unconfirmed transaction is invalid"));
@@ -70,13 +302,6 @@ impl<N: Network> MemoryPool<N> {
             return Err(anyhow!("Transaction already exists in memory pool"));
         }
 
-        // Ensure the memory pool does not already contain a given serial numbers.
-        for serial_number in transaction.serial_numbers() {
-            if self.serial_numbers.contains(serial_number) {
-                return Err(anyhow!("Serial number already used in memory pool"));
-            }
-        }
-
         // Ensure the memory pool does not already contain a given commitments.
         for commitment in transaction.commitments() {
             if self.commitments.contains(commitment) {
@@ -84,64 +309,403 @@ impl<N: Network> MemoryPool<N> {
             }
         }
 
-        // Add the transaction to the memory pool. This code section executes atomically.
-        {
-            let mut memory_pool = self.clone();
+        let fee_rate = FeeRate::new(transaction.value_balance(), transaction.to_bytes_le()?.len());
 
-            memory_pool.transactions.insert(transaction_id, transaction.clone());
-            for serial_number in transaction.serial_numbers() {
-                memory_pool.serial_numbers.insert(*serial_number);
-            }
-            for commitment in transaction.commitments() {
-                memory_pool.commitments.insert(*commitment);
-            }
+        // Ensure the transaction fits in the memory pool.
+        if fee_rate.size_in_bytes() > self.config.maximum_size_in_bytes || self.config.maximum_num_transactions == 0 {
+            return Err(anyhow!("Transaction exceeds the memory pool capacity"));
+        }
+
+        // Find the transactions that spend the same serial numbers.
+        let conflicting_ids = transaction
+            .serial_numbers()
+            .filter_map(|serial_number| self.serial_numbers.get(serial_number))
+            .copied()
+            .collect::<HashSet<_>>();
 
-            *self = memory_pool;
+        // Ensure the memory pool does not already contain a given program deployments,
+        // unless they are deployed by the conflicting transactions that this transaction replaces.
+        for deployment in transaction.deployments() {
+            if let Some(deploying_id) = self.deployments.get(&deployment.program_id()) {
+                if !conflicting_ids.contains(deploying_id) {
+                    return Err(anyhow!("Program already deployed in memory pool"));
+                }
+            }
         }
 
-        Ok(())
-    }
+        // Ensure the transaction pays more than the conflicting transactions it replaces.
+        let mut conflicting_fee = AleoAmount::ZERO;
+        let mut num_transactions = self.transactions.len() + 1;
+        let mut size_in_bytes = self.size_in_bytes + fee_rate.size_in_bytes();
+        for conflicting_id in &conflicting_ids {
+            let conflicting_fee_rate = self.transactions[conflicting_id].priority.fee_rate;
+            if fee_rate <= conflicting_fee_rate {
+                return Err(anyhow!(
+                    "Serial number already used in memory pool by a transaction with a higher fee"
+                ));
+            }
+            conflicting_fee = conflicting_fee.add(conflicting_fee_rate.fee());
+            num_transactions -= 1;
+            size_in_bytes -= conflicting_fee_rate.size_in_bytes();
+        }
+        if !conflicting_ids.is_empty() && fee_rate.fee() <= conflicting_fee {
+            return Err(anyhow!(
+                "Replacement transaction must pay a higher fee than the transactions it replaces"
+            ));
+        }
 
-    /// Clear a transaction (and associated state) from the memory pool.
-    pub fn remove_transaction(&mut self, transaction: &Transaction<N>) {
-        // This code section executes atomically.
+        // Find the lowest-priority transactions to evict until the transaction fits,
+        // before modifying the memory pool, so that a rejected transaction leaves it unchanged.
+        let mut evicted_ids = Vec::new();
+        let mut lowest_priorities = self
+            .priorities
+            .iter()
+            .rev()
+            .filter(|(_, transaction_id)| !conflicting_ids.contains(transaction_id));
+        while num_transactions > self.config.maximum_num_transactions
+            || size_in_bytes > self.config.maximum_size_in_bytes
+        {
+            let (priority, evicted_id) = match lowest_priorities.next() {
+                Some(lowest) => lowest,
+                None => break,
+            };
+            if fee_rate <= priority.fee_rate {
+                return Err(anyhow!("Memory pool is full of transactions with a higher fee"));
+            }
+            num_transactions -= 1;
+            size_in_bytes -= priority.fee_rate.size_in_bytes();
+            evicted_ids.push(*evicted_id);
+        }
 
-        let mut memory_pool = self.clone();
+        // Remove the conflicting and evicted transactions.
+        let removed = conflicting_ids
+            .iter()
+            .chain(&evicted_ids)
+            .filter_map(|transaction_id| self.remove_entry(transaction_id))
+            .collect();
 
-        memory_pool.transactions.remove(&transaction.transaction_id());
+        // Add the transaction.
+        let priority = MemoryPoolPriority {
+            fee_rate,
+            sequence: self.next_sequence,
+        };
+        self.next_sequence += 1;
+        self.size_in_bytes += fee_rate.size_in_bytes();
+        self.priorities.insert(priority, transaction_id);
         for serial_number in transaction.serial_numbers() {
-            memory_pool.serial_numbers.remove(serial_number);
+            self.serial_numbers.insert(*serial_number, transaction_id);
         }
         for commitment in transaction.commitments() {
-            memory_pool.commitments.remove(commitment);
+            self.commitments.insert(*commitment);
+        }
+        for deployment in transaction.deployments() {
+            self.deployments.insert(deployment.program_id(), transaction_id);
         }
+        self.transactions.insert(
+            transaction_id,
+            MemoryPoolEntry {
+                transaction: transaction.clone(),
+                priority,
+            },
+        );
 
-        *self = memory_pool;
+        Ok(removed)
+    }
+
+    /// Clear a transaction (and associated state) from the memory pool.
+    pub fn remove_transaction(&mut self, transaction: &Transaction<N>) {
+        self.remove_entry(&transaction.transaction_id());
     }
 
     /// Clear a list of transactions (and associated state) from the memory pool.
-    pub fn remove_transactions(&mut self, transactions: &Vec<Transaction<N>>) {
-        // This code section executes atomically.
+    pub fn remove_transactions(&mut self, transactions: &[Transaction<N>]) {
+        for transaction in transactions {
+            self.remove_transaction(transaction);
+        }
+    }
 
-        let mut memory_pool = self.clone();
+    ///
+    /// Removes the transactions whose ledger root is expired, as determined by the given function,
+    /// returning the removed transactions.
+    ///
+    pub fn remove_expired_transactions<F: Fn(&N::LedgerRoot) -> bool>(&mut self, is_expired: F) -> Vec<Transaction<N>> {
+        let expired = self
+            .transactions
+            .values()
+            .filter(|entry| is_expired(&entry.transaction.ledger_root()))
+            .map(|entry| entry.transaction.clone())
+            .collect::<Vec<_>>();
+        self.remove_transactions(&expired);
+        expired
+    }
 
-        for transaction in transactions {
-            memory_pool.transactions.remove(&transaction.transaction_id());
-            for serial_number in transaction.serial_numbers() {
-                memory_pool.serial_numbers.remove(serial_number);
-            }
-            for commitment in transaction.commitments() {
-                memory_pool.commitments.remove(commitment);
+    ///
+    /// Removes the transactions that were included in the given block, the transactions that conflict
+    /// with the given block, and the transactions with an expired ledger root, as determined by the given function.
+    ///
+    pub fn revalidate<F: Fn(&N::LedgerRoot) -> bool>(
+        &mut self,
+        block: &Block<N>,
+        is_expired: F,
+    ) -> MemoryPoolRemovals<N> {
+        let transaction_ids = block.transactions().transaction_ids().collect::<HashSet<_>>();
+        let serial_numbers = block.serial_numbers().collect::<HashSet<_>>();
+        let commitments = block.commitments().collect::<HashSet<_>>();
+        let deployments = block
+            .transactions()
+            .iter()
+            .flat_map(Transaction::deployments)
+            .map(Deployment::program_id)
+            .collect::<HashSet<_>>();
+
+        let mut removals = MemoryPoolRemovals::default();
+        for entry in self.sorted_entries() {
+            let transaction = &entry.transaction;
+            if transaction_ids.contains(&transaction.transaction_id()) {
+                removals.confirmed.push(transaction.clone());
+            } else if transaction
+                .serial_numbers()
+                .any(|serial_number| serial_numbers.contains(serial_number))
+                || transaction
+                    .commitments()
+                    .any(|commitment| commitments.contains(commitment))
+                || transaction
+                    .deployments()
+                    .any(|deployment| deployments.contains(&deployment.program_id()))
+            {
+                removals.conflicting.push(transaction.clone());
+            } else if is_expired(&transaction.ledger_root()) {
+                removals.expired.push(transaction.clone());
             }
         }
 
-        *self = memory_pool;
+        for transaction in removals.transactions().cloned().collect::<Vec<_>>() {
+            self.remove_transaction(&transaction);
+        }
+        removals
     }
 
     /// Clears all transactions (and associated state) from the memory pool.
     pub fn clear_all_transactions(&mut self) {
         self.transactions = Default::default();
+        self.priorities = Default::default();
         self.serial_numbers = Default::default();
         self.commitments = Default::default();
+        self.deployments = Default::default();
+        self.size_in_bytes = 0;
+    }
+
+    /// Returns the entries in the memory pool, ordered from the highest to lowest priority.
+    fn sorted_entries(&self) -> Vec<&MemoryPoolEntry<N>> {
+        self.priorities
+            .values()
+            .map(|transaction_id| &self.transactions[transaction_id])
+            .collect()
+    }
+
+    /// Removes the transaction with the given ID (and associated state), returning the removed transaction.
+    fn remove_entry(&mut self, transaction_id: &N::TransactionID) -> Option<Transaction<N>> {
+        let entry = self.transactions.remove(transaction_id)?;
+        let transaction = entry.transaction;
+
+        self.priorities.remove(&entry.priority);
+        self.size_in_bytes = self
+            .size_in_bytes
+            .saturating_sub(entry.priority.fee_rate.size_in_bytes());
+        for serial_number in transaction.serial_numbers() {
+            self.serial_numbers.remove(serial_number);
+        }
+        for commitment in transaction.commitments() {
+            self.commitments.remove(commitment);
+        }
+        for deployment in transaction.deployments() {
+            self.deployments.remove(&deployment.program_id());
+        }
+        Some(transaction)
+    }
+}
+
+impl<N: Network> Default for MemoryPool<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ledger::test_helpers::{sample_fee_transaction, sample_noop_transaction},
+        testnet2::Testnet2,
+    };
+
+    use rand::thread_rng;
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn test_fee_rate_ordering() {
+        let low = FeeRate::new(AleoAmount::from_i64(100), 1000);
+        let high = FeeRate::new(AleoAmount::from_i64(100), 500);
+        assert!(low < high);
+        assert_eq!(
+            FeeRate::new(AleoAmount::from_i64(1), 2),
+            FeeRate::new(AleoAmount::from_i64(50), 100)
+        );
+        assert!(FeeRate::new(AleoAmount::ZERO, 1) < FeeRate::new(AleoAmount::ONE_BYTE, 1_000_000));
+    }
+
+    #[test]
+    fn test_maximum_num_transactions() {
+        let rng = &mut thread_rng();
+        let mut memory_pool = MemoryPool::<Testnet2>::with_config(MemoryPoolConfig {
+            maximum_num_transactions: 1,
+            ..Default::default()
+        });
+
+        let transaction_1 = sample_noop_transaction(rng);
+        let transaction_2 = sample_noop_transaction(rng);
+
+        assert!(memory_pool.add_transaction(&transaction_1).unwrap().is_empty());
+        // An equal-fee transaction does not evict an existing transaction.
+        assert!(memory_pool.add_transaction(&transaction_2).is_err());
+        assert_eq!(vec![transaction_1.clone()], memory_pool.transactions());
+        assert_eq!(transaction_1.to_bytes_le().unwrap().len(), memory_pool.size_in_bytes());
+
+        // Ensure lowering the limits evicts transactions.
+        let evicted = memory_pool.set_config(MemoryPoolConfig {
+            maximum_num_transactions: 0,
+            ..Default::default()
+        });
+        assert_eq!(vec![transaction_1], evicted);
+        assert_eq!(0, memory_pool.size_in_bytes());
+    }
+
+    #[test]
+    fn test_block_template_and_expiry() {
+        let rng = &mut thread_rng();
+        let mut memory_pool = MemoryPool::<Testnet2>::new();
+
+        let transaction_1 = sample_noop_transaction(rng);
+        let transaction_2 = sample_noop_transaction(rng);
+        memory_pool.add_transaction(&transaction_1).unwrap();
+        memory_pool.add_transaction(&transaction_2).unwrap();
+
+        // Transactions with equal fees are ordered by arrival.
+        assert_eq!(
+            vec![transaction_1.clone(), transaction_2.clone()],
+            memory_pool.transactions()
+        );
+
+        // Ensure the block template respects the size limit.
+        let size_in_bytes = transaction_1.to_bytes_le().unwrap().len();
+        assert_eq!(vec![transaction_1.clone()], memory_pool.block_template(size_in_bytes));
+        assert_eq!(2, memory_pool.block_template(2 * size_in_bytes).len());
+        assert!(memory_pool.block_template(size_in_bytes - 1).is_empty());
+
+        // Ensure expired transactions are removed.
+        let expired_root = transaction_1.ledger_root();
+        let expired = memory_pool.remove_expired_transactions(|ledger_root| *ledger_root == expired_root);
+        assert_eq!(2, expired.len());
+        assert_eq!(0, memory_pool.num_transactions());
+    }
+
+    #[test]
+    fn test_replace_by_fee() {
+        let rng = &mut thread_rng();
+        let caller = Account::<Testnet2>::new(rng);
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let record = ledger
+            .mine_next_block(caller.address(), true, &AtomicBool::new(false), rng)
+            .unwrap();
+
+        // Spend the same record in transactions with different fees.
+        let low_fee = sample_fee_transaction(&ledger, &caller, &record, AleoAmount(1), rng);
+        let equal_fee = sample_fee_transaction(&ledger, &caller, &record, AleoAmount(1), rng);
+        let high_fee = sample_fee_transaction(&ledger, &caller, &record, AleoAmount(2), rng);
+
+        let mut memory_pool = MemoryPool::<Testnet2>::new();
+        assert!(memory_pool.add_transaction(&low_fee).unwrap().is_empty());
+
+        // A conflicting transaction with an equal fee does not replace the existing transaction.
+        assert!(memory_pool.add_transaction(&equal_fee).is_err());
+        assert_eq!(vec![low_fee.clone()], memory_pool.transactions());
+
+        // A conflicting transaction with a higher fee replaces the existing transaction.
+        assert_eq!(vec![low_fee.clone()], memory_pool.add_transaction(&high_fee).unwrap());
+        assert_eq!(vec![high_fee.clone()], memory_pool.transactions());
+        assert_eq!(high_fee.to_bytes_le().unwrap().len(), memory_pool.size_in_bytes());
+
+        // The replaced transaction may not return.
+        assert!(memory_pool.add_transaction(&low_fee).is_err());
+    }
+
+    #[test]
+    fn test_evict_lowest_fee() {
+        let rng = &mut thread_rng();
+        let caller = Account::<Testnet2>::new(rng);
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let records = (0..2)
+            .map(|_| {
+                ledger
+                    .mine_next_block(caller.address(), true, &AtomicBool::new(false), rng)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let no_fee = sample_noop_transaction(rng);
+        let low_fee = sample_fee_transaction(&ledger, &caller, &records[0], AleoAmount(1), rng);
+        let high_fee = sample_fee_transaction(&ledger, &caller, &records[1], AleoAmount(2), rng);
+
+        let mut memory_pool = MemoryPool::<Testnet2>::with_config(MemoryPoolConfig {
+            maximum_num_transactions: 2,
+            ..Default::default()
+        });
+        assert!(memory_pool.add_transaction(&no_fee).unwrap().is_empty());
+        assert!(memory_pool.add_transaction(&low_fee).unwrap().is_empty());
+
+        // Ensure the full memory pool evicts the transaction with the lowest fee.
+        assert_eq!(vec![no_fee.clone()], memory_pool.add_transaction(&high_fee).unwrap());
+        assert_eq!(vec![high_fee.clone(), low_fee.clone()], memory_pool.transactions());
+
+        // Ensure a transaction with a lower fee than every transaction in the full memory pool is rejected.
+        assert!(memory_pool.add_transaction(&no_fee).is_err());
+        assert_eq!(vec![high_fee, low_fee], memory_pool.transactions());
+    }
+
+    #[test]
+    fn test_revalidate_removes_conflicting_transactions() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+        let caller = Account::<Testnet2>::new(rng);
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let record = ledger
+            .mine_next_block(caller.address(), true, &terminator, rng)
+            .unwrap();
+
+        // Spend the same record in two transactions, and keep the lower fee one in the memory pool.
+        let unrelated = sample_noop_transaction(rng);
+        let low_fee = sample_fee_transaction(&ledger, &caller, &record, AleoAmount(1), rng);
+        let high_fee = sample_fee_transaction(&ledger, &caller, &record, AleoAmount(2), rng);
+
+        let mut memory_pool = MemoryPool::<Testnet2>::new();
+        memory_pool.add_transaction(&unrelated).unwrap();
+        memory_pool.add_transaction(&low_fee).unwrap();
+
+        // Confirm the higher fee transaction in the canon chain, spending the serial number of the record.
+        ledger.add_unconfirmed_transaction(&high_fee).unwrap();
+        ledger
+            .mine_next_block(caller.address(), true, &terminator, rng)
+            .unwrap();
+        let block = ledger.latest_block().unwrap();
+
+        // Ensure the transaction with the spent serial number is removed as conflicting.
+        let removals = memory_pool.revalidate(&block, |_| false);
+        assert!(removals.confirmed().is_empty());
+        assert_eq!(std::slice::from_ref(&low_fee), removals.conflicting());
+        assert!(removals.expired().is_empty());
+        assert_eq!(vec![unrelated], memory_pool.transactions());
     }
 }
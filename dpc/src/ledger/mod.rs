@@ -17,6 +17,9 @@
 pub mod blocks;
 pub use blocks::*;
 
+pub mod difficulty;
+pub use difficulty::*;
+
 pub mod ledger;
 pub use ledger::*;
 
@@ -28,3 +31,12 @@ pub use ledger_tree::*;
 
 pub mod memory_pool;
 pub use memory_pool::*;
+
+pub mod record_scanner;
+pub use record_scanner::*;
+
+pub mod storage;
+pub use storage::*;
+
+#[cfg(test)]
+pub(crate) mod test_helpers;
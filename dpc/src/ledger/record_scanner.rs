@@ -0,0 +1,468 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::prelude::*;
+use snarkvm_utilities::{FromBytes, ToBytes};
+
+use anyhow::{anyhow, Result};
+use std::{
+    collections::HashMap,
+    io::{Read, Result as IoResult, Write},
+};
+
+/// A record owned by a scanned account, and its spend status on the ledger.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "N: Network"),
+    Debug(bound = "N: Network"),
+    PartialEq(bound = "N: Network"),
+    Eq(bound = "N: Network")
+)]
+pub struct ScannedRecord<N: Network> {
+    /// The owned record.
+    record: Record<N>,
+    /// The serial number of the record.
+    serial_number: N::SerialNumber,
+    /// The block height in which the record was created.
+    block_height: u32,
+    /// The block height in which the record was spent, if it has been spent.
+    spent_height: Option<u32>,
+}
+
+impl<N: Network> ScannedRecord<N> {
+    /// Returns the record.
+    pub fn record(&self) -> &Record<N> {
+        &self.record
+    }
+
+    /// Returns the serial number of the record.
+    pub fn serial_number(&self) -> N::SerialNumber {
+        self.serial_number
+    }
+
+    /// Returns the block height in which the record was created.
+    pub fn block_height(&self) -> u32 {
+        self.block_height
+    }
+
+    /// Returns the block height in which the record was spent, if it has been spent.
+    pub fn spent_height(&self) -> Option<u32> {
+        self.spent_height
+    }
+
+    /// Returns `true` if the record has been spent.
+    pub fn is_spent(&self) -> bool {
+        self.spent_height.is_some()
+    }
+}
+
+impl<N: Network> FromBytes for ScannedRecord<N> {
+    #[inline]
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let record = FromBytes::read_le(&mut reader)?;
+        let serial_number = FromBytes::read_le(&mut reader)?;
+        let block_height = FromBytes::read_le(&mut reader)?;
+        let is_spent: bool = FromBytes::read_le(&mut reader)?;
+        let spent_height = match is_spent {
+            true => Some(FromBytes::read_le(&mut reader)?),
+            false => None,
+        };
+
+        Ok(Self {
+            record,
+            serial_number,
+            block_height,
+            spent_height,
+        })
+    }
+}
+
+impl<N: Network> ToBytes for ScannedRecord<N> {
+    #[inline]
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.record.write_le(&mut writer)?;
+        self.serial_number.write_le(&mut writer)?;
+        self.block_height.write_le(&mut writer)?;
+        self.spent_height.is_some().write_le(&mut writer)?;
+        if let Some(spent_height) = self.spent_height {
+            spent_height.write_le(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// The resumable state of a record scanner.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "N: Network"),
+    Debug(bound = "N: Network"),
+    PartialEq(bound = "N: Network"),
+    Eq(bound = "N: Network")
+)]
+pub struct RecordScannerCheckpoint<N: Network> {
+    /// The next block height to scan.
+    next_height: u32,
+    /// The hash of the last scanned block, if any block has been scanned.
+    last_block_hash: Option<N::BlockHash>,
+    /// The scanned records.
+    records: Vec<ScannedRecord<N>>,
+}
+
+impl<N: Network> RecordScannerCheckpoint<N> {
+    /// Returns the next block height to scan.
+    pub fn next_height(&self) -> u32 {
+        self.next_height
+    }
+
+    /// Returns the hash of the last scanned block, if any block has been scanned.
+    pub fn last_block_hash(&self) -> Option<N::BlockHash> {
+        self.last_block_hash
+    }
+
+    /// Returns the scanned records.
+    pub fn records(&self) -> &[ScannedRecord<N>] {
+        &self.records
+    }
+}
+
+impl<N: Network> FromBytes for RecordScannerCheckpoint<N> {
+    #[inline]
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let next_height = FromBytes::read_le(&mut reader)?;
+        let has_last_block_hash: bool = FromBytes::read_le(&mut reader)?;
+        let last_block_hash = match has_last_block_hash {
+            true => Some(FromBytes::read_le(&mut reader)?),
+            false => None,
+        };
+        let num_records: u32 = FromBytes::read_le(&mut reader)?;
+        let mut records = Vec::new();
+        for _ in 0..num_records {
+            records.push(FromBytes::read_le(&mut reader)?);
+        }
+
+        Ok(Self {
+            next_height,
+            last_block_hash,
+            records,
+        })
+    }
+}
+
+impl<N: Network> ToBytes for RecordScannerCheckpoint<N> {
+    #[inline]
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.next_height.write_le(&mut writer)?;
+        self.last_block_hash.is_some().write_le(&mut writer)?;
+        if let Some(last_block_hash) = self.last_block_hash {
+            last_block_hash.write_le(&mut writer)?;
+        }
+        (self.records.len() as u32).write_le(&mut writer)?;
+        for record in &self.records {
+            record.write_le(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+///
+/// A record scanner walks the blocks of a ledger with one or more account view keys,
+/// collecting the records owned by the accounts, and marking them as spent when their
+/// serial numbers, computed with the matching compute keys, appear on the ledger.
+///
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"))]
+pub struct RecordScanner<N: Network> {
+    /// The view keys and compute keys of the scanned accounts.
+    accounts: Vec<(ViewKey<N>, ComputeKey<N>)>,
+    /// The next block height to scan.
+    next_height: u32,
+    /// The hash of the last scanned block, if any block has been scanned.
+    last_block_hash: Option<N::BlockHash>,
+    /// The scanned records, in the order they were found.
+    records: Vec<ScannedRecord<N>>,
+    /// The index of each scanned record, keyed by its serial number.
+    serial_numbers: HashMap<N::SerialNumber, usize>,
+}
+
+impl<N: Network> RecordScanner<N> {
+    /// Initializes a new record scanner that starts scanning from the given block height.
+    pub fn new(start_height: u32) -> Self {
+        Self {
+            accounts: Vec::new(),
+            next_height: start_height,
+            last_block_hash: None,
+            records: Vec::new(),
+            serial_numbers: Default::default(),
+        }
+    }
+
+    /// Initializes a record scanner that resumes scanning from the given checkpoint.
+    pub fn from_checkpoint(checkpoint: RecordScannerCheckpoint<N>) -> Self {
+        let serial_numbers = checkpoint
+            .records
+            .iter()
+            .enumerate()
+            .map(|(index, record)| (record.serial_number, index))
+            .collect();
+
+        Self {
+            accounts: Vec::new(),
+            next_height: checkpoint.next_height,
+            last_block_hash: checkpoint.last_block_hash,
+            records: checkpoint.records,
+            serial_numbers,
+        }
+    }
+
+    /// Adds the given account to the scanner. The view key and compute key must belong to the same account.
+    /// Note: Only blocks that are scanned after the account is added are checked for its records.
+    pub fn add_account(&mut self, view_key: ViewKey<N>, compute_key: ComputeKey<N>) -> Result<()> {
+        if Address::from_view_key(&view_key) != Address::from_compute_key(&compute_key) {
+            return Err(anyhow!(
+                "The given view key and compute key belong to different accounts"
+            ));
+        }
+        self.accounts.push((view_key, compute_key));
+        Ok(())
+    }
+
+    /// Returns the next block height to scan.
+    pub fn next_height(&self) -> u32 {
+        self.next_height
+    }
+
+    /// Returns all scanned records, including spent records.
+    pub fn records(&self) -> &[ScannedRecord<N>] {
+        &self.records
+    }
+
+    /// Returns the scanned records that have not been spent.
+    pub fn unspent_records(&self) -> impl Iterator<Item = &ScannedRecord<N>> + '_ {
+        self.records.iter().filter(|record| !record.is_spent())
+    }
+
+    /// Returns the total value of the unspent records.
+    pub fn balance(&self) -> AleoAmount {
+        self.unspent_records().map(|record| record.record().value()).sum()
+    }
+
+    /// Returns the balance of unspent records owned by the given address.
+    pub fn balance_of(&self, address: &Address<N>) -> AleoAmount {
+        self.unspent_records()
+            .filter(|record| record.record().owner() == *address)
+            .map(|record| record.record().value())
+            .sum()
+    }
+
+    /// Returns a checkpoint of the scanner state, from which scanning can be resumed.
+    pub fn to_checkpoint(&self) -> RecordScannerCheckpoint<N> {
+        RecordScannerCheckpoint {
+            next_height: self.next_height,
+            last_block_hash: self.last_block_hash,
+            records: self.records.clone(),
+        }
+    }
+
+    ///
+    /// Scans the blocks from the next block height up to the latest block, returning the number of scanned blocks.
+    ///
+    /// If the last scanned block is no longer in the canon chain, an error is returned, and
+    /// the scanner should be rolled back to a block height before the fork with `rollback_to`.
+    ///
+    pub fn scan(&mut self, blocks: &Blocks<N>) -> Result<u32> {
+        // Ensure the last scanned block is still in the canon chain.
+        if let Some(last_block_hash) = self.last_block_hash {
+            let last_height = self.next_height.saturating_sub(1);
+            if !blocks.contains_height(last_height) || blocks.get_block_hash(last_height)? != last_block_hash {
+                return Err(anyhow!(
+                    "The last scanned block at height {} is no longer canon",
+                    last_height
+                ));
+            }
+        }
+
+        let latest_height = blocks.latest_block_height();
+        let start_height = self.next_height;
+        for height in start_height..=latest_height {
+            self.scan_block(height, blocks.get_block_transactions(height)?)?;
+            self.next_height = height + 1;
+            self.last_block_hash = Some(blocks.get_block_hash(height)?);
+        }
+
+        Ok(self.next_height.saturating_sub(start_height))
+    }
+
+    ///
+    /// Rolls back the scanner to the given block height, so the next scan resumes from the block after it.
+    /// Records created after the given height are removed, and records spent after it are marked unspent.
+    ///
+    pub fn rollback_to(&mut self, height: u32, blocks: &Blocks<N>) -> Result<()> {
+        if height >= self.next_height {
+            return Ok(());
+        }
+
+        self.records.retain(|record| record.block_height <= height);
+        for record in self.records.iter_mut() {
+            if record.spent_height.map_or(false, |spent_height| spent_height > height) {
+                record.spent_height = None;
+            }
+        }
+        self.serial_numbers = self
+            .records
+            .iter()
+            .enumerate()
+            .map(|(index, record)| (record.serial_number, index))
+            .collect();
+
+        self.next_height = height + 1;
+        self.last_block_hash = Some(blocks.get_block_hash(height)?);
+        Ok(())
+    }
+
+    /// Collects the owned records, and marks the spent records, from the given block transactions.
+    fn scan_block(&mut self, height: u32, transactions: &Transactions<N>) -> Result<()> {
+        for transaction in transactions.iter() {
+            // Collect the records owned by the scanned accounts.
+            for (view_key, compute_key) in &self.accounts {
+                for record in transaction.to_decrypted_records(view_key) {
+                    let serial_number = record.to_serial_number(compute_key)?;
+                    if self.serial_numbers.contains_key(&serial_number) {
+                        continue;
+                    }
+                    self.serial_numbers.insert(serial_number, self.records.len());
+                    self.records.push(ScannedRecord {
+                        record,
+                        serial_number,
+                        block_height: height,
+                        spent_height: None,
+                    });
+                }
+            }
+        }
+
+        // Mark the records whose serial numbers appear in the block as spent.
+        for serial_number in transactions.serial_numbers() {
+            if let Some(index) = self.serial_numbers.get(serial_number) {
+                self.records[*index].spent_height = Some(height);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ledger::test_helpers::sample_fee_transaction, testnet2::Testnet2};
+
+    use rand::thread_rng;
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn test_scan_coinbase_records() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+
+        let account = Account::<Testnet2>::new(rng);
+        let other = Account::<Testnet2>::new(rng);
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let coinbase_record = ledger
+            .mine_next_block(account.address(), false, &terminator, rng)
+            .unwrap();
+        ledger
+            .mine_next_block(other.address(), false, &terminator, rng)
+            .unwrap();
+
+        let mut scanner = RecordScanner::<Testnet2>::new(1);
+        scanner
+            .add_account(account.view_key().clone(), account.private_key().to_compute_key())
+            .unwrap();
+        assert!(scanner
+            .add_account(other.view_key().clone(), account.private_key().to_compute_key())
+            .is_err());
+
+        assert_eq!(2, scanner.scan(ledger.to_blocks()).unwrap());
+        assert_eq!(1, scanner.records().len());
+        assert_eq!(&coinbase_record, scanner.records()[0].record());
+        assert_eq!(1, scanner.records()[0].block_height());
+        assert_eq!(coinbase_record.value(), scanner.balance());
+        assert_eq!(AleoAmount::ZERO, scanner.balance_of(&other.address()));
+
+        // Ensure scanning again without new blocks is a no-op.
+        assert_eq!(0, scanner.scan(ledger.to_blocks()).unwrap());
+
+        // Ensure the scanner resumes from a checkpoint.
+        let checkpoint = scanner.to_checkpoint();
+        let checkpoint = RecordScannerCheckpoint::<Testnet2>::read_le(&checkpoint.to_bytes_le().unwrap()[..]).unwrap();
+        let mut resumed = RecordScanner::from_checkpoint(checkpoint);
+        resumed
+            .add_account(account.view_key().clone(), account.private_key().to_compute_key())
+            .unwrap();
+        ledger
+            .mine_next_block(account.address(), false, &terminator, rng)
+            .unwrap();
+        assert_eq!(1, resumed.scan(ledger.to_blocks()).unwrap());
+        assert_eq!(2, resumed.unspent_records().count());
+
+        // Ensure rolling back removes the records after the given height.
+        resumed.rollback_to(2, ledger.to_blocks()).unwrap();
+        assert_eq!(1, resumed.records().len());
+        assert_eq!(3, resumed.next_height());
+    }
+
+    #[test]
+    fn test_scan_spent_records() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+
+        let account = Account::<Testnet2>::new(rng);
+        let other = Account::<Testnet2>::new(rng);
+
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let coinbase_record = ledger
+            .mine_next_block(account.address(), false, &terminator, rng)
+            .unwrap();
+
+        let mut scanner = RecordScanner::<Testnet2>::new(1);
+        scanner
+            .add_account(account.view_key().clone(), account.private_key().to_compute_key())
+            .unwrap();
+        assert_eq!(1, scanner.scan(ledger.to_blocks()).unwrap());
+        assert!(!scanner.records()[0].is_spent());
+
+        // Spend the coinbase record in the next block, paying a fee and returning the remainder to the account.
+        let fee = AleoAmount(1);
+        let transaction = sample_fee_transaction(&ledger, &account, &coinbase_record, fee, rng);
+        ledger.add_unconfirmed_transaction(&transaction).unwrap();
+        ledger
+            .mine_next_block(other.address(), false, &terminator, rng)
+            .unwrap();
+        assert!(ledger.latest_block_transactions().unwrap().contains(&transaction));
+
+        // Ensure the scanner marks the record as spent once its serial number is on chain.
+        assert_eq!(1, scanner.scan(ledger.to_blocks()).unwrap());
+        let spent_record = scanner
+            .records()
+            .iter()
+            .find(|scanned| scanned.record() == &coinbase_record)
+            .unwrap();
+        assert!(spent_record.is_spent());
+        assert_eq!(Some(2), spent_record.spent_height());
+        assert_eq!(coinbase_record.value().sub(fee), scanner.balance());
+    }
+}
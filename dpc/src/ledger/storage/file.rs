@@ -0,0 +1,458 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{ledger::storage::*, DataMap, Storage, StorageBatch, StorageOp};
+
+use anyhow::{anyhow, Result};
+use blake2::{Blake2s, Digest};
+use std::{
+    convert::TryInto,
+    fs::{self, File, OpenOptions},
+    io::{BufReader, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// The file name of the append-only log in the storage directory.
+pub const STORAGE_LOG_FILE_NAME: &str = "ledger.log";
+
+/// The number of bytes in a record header, consisting of a 4-byte length and a 32-byte checksum.
+const RECORD_HEADER_SIZE: usize = 4 + 32;
+
+const INSERT_OP: u8 = 0;
+const REMOVE_OP: u8 = 1;
+
+///
+/// An embedded storage backend that persists ledger state to an append-only log on disk.
+///
+/// Each committed batch is written as a single record, consisting of the payload length,
+/// a BLAKE2s checksum of the payload, and the payload itself. The log is fsynced before a
+/// commit returns. On open, the log is streamed from the start, and is truncated at the first
+/// record that is incomplete or fails its checksum (i.e. from a crash mid-write).
+///
+pub struct FileStorage {
+    /// The path to the log file.
+    path: PathBuf,
+    /// The log file handle and the replayed maps.
+    state: Mutex<FileState>,
+}
+
+struct FileState {
+    /// The handle to the log file.
+    file: File,
+    /// The maps replayed from the log.
+    maps: StorageMaps,
+    /// The length to truncate the log to before the next write, if a failed write was not rolled back.
+    rollback_length: Option<u64>,
+}
+
+impl FileStorage {
+    /// Opens the storage in the given directory, creating it if it does not exist.
+    pub fn open<P: AsRef<Path>>(directory: P) -> Result<Self> {
+        fs::create_dir_all(directory.as_ref())?;
+        let path = directory.as_ref().join(STORAGE_LOG_FILE_NAME);
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .write(true)
+            .truncate(false)
+            .open(&path)?;
+        let file_length = file.metadata()?.len();
+
+        // Replay the complete and well-formed records in the log, up to the first invalid record.
+        let mut maps = StorageMaps::default();
+        let mut offset = 0;
+        {
+            let mut reader = BufReader::new(&file);
+            while let Some((batch, length)) = read_record(&mut reader, file_length - offset)? {
+                apply_batch(&mut maps, &batch);
+                offset += length;
+            }
+        }
+
+        // Discard the log from the first invalid record, which was only partially written.
+        if offset < file_length {
+            file.set_len(offset)?;
+            file.sync_all()?;
+        }
+        file.seek(SeekFrom::Start(offset))?;
+
+        Ok(Self {
+            path,
+            state: Mutex::new(FileState {
+                file,
+                maps,
+                rollback_length: None,
+            }),
+        })
+    }
+
+    /// Returns the path to the log file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Rewrites the log as a single record containing the current state, discarding
+    /// any overwritten or removed entries.
+    pub fn compact(&self) -> Result<()> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| anyhow!("File storage lock is poisoned"))?;
+
+        // Construct a batch that inserts every current entry.
+        let mut batch = StorageBatch::new();
+        let mut map_ids = state.maps.keys().copied().collect::<Vec<_>>();
+        map_ids.sort();
+        for map in map_ids {
+            for (key, value) in &state.maps[&map] {
+                batch.insert(map, key.clone(), value.clone());
+            }
+        }
+
+        // Write the compacted log to a temporary file, and atomically replace the log.
+        let compacted_path = self.path.with_extension("log.compact");
+        {
+            let mut compacted = File::create(&compacted_path)?;
+            compacted.write_all(&encode_record(&batch)?)?;
+            compacted.sync_all()?;
+        }
+        fs::rename(&compacted_path, &self.path)?;
+
+        // Ensure the rename itself is persisted.
+        #[cfg(unix)]
+        if let Some(directory) = self.path.parent() {
+            File::open(directory)?.sync_all()?;
+        }
+
+        let mut file = OpenOptions::new().read(true).write(true).open(&self.path)?;
+        file.seek(SeekFrom::End(0))?;
+        state.file = file;
+        state.rollback_length = None;
+
+        Ok(())
+    }
+}
+
+impl Storage for FileStorage {
+    fn get(&self, map: DataMap, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        let state = self
+            .state
+            .lock()
+            .map_err(|_| anyhow!("File storage lock is poisoned"))?;
+        Ok(state.maps.get(&map).and_then(|entries| entries.get(key)).cloned())
+    }
+
+    fn get_all(&self, map: DataMap) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let state = self
+            .state
+            .lock()
+            .map_err(|_| anyhow!("File storage lock is poisoned"))?;
+        Ok(state
+            .maps
+            .get(&map)
+            .map(|entries| entries.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+            .unwrap_or_default())
+    }
+
+    fn commit(&self, batch: StorageBatch) -> Result<()> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        let record = encode_record(&batch)?;
+
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| anyhow!("File storage lock is poisoned"))?;
+        let FileState {
+            file,
+            maps,
+            rollback_length,
+        } = &mut *state;
+
+        // Ensure a partial record from a previous failed write is removed, so no record is written after it.
+        if let Some(length) = *rollback_length {
+            file.set_len(length)
+                .and_then(|_| file.sync_data())
+                .map_err(|error| anyhow!("Failed to roll back {}: {}", self.path.display(), error))?;
+            *rollback_length = None;
+        }
+
+        // Write the record to the log, and flush it to disk before applying it in memory.
+        let previous_length = file.seek(SeekFrom::End(0))?;
+        if let Err(error) = file.write_all(&record).and_then(|_| file.sync_data()) {
+            // Roll back the partial write. If this fails, it is retried before the next write.
+            if file.set_len(previous_length).and_then(|_| file.sync_data()).is_err() {
+                *rollback_length = Some(previous_length);
+            }
+            return Err(anyhow!("Failed to commit to {}: {}", self.path.display(), error));
+        }
+
+        apply_batch(maps, &batch);
+        Ok(())
+    }
+}
+
+/// Returns the encoded log record for the given batch.
+fn encode_record(batch: &StorageBatch) -> Result<Vec<u8>> {
+    let mut payload = Vec::new();
+    payload.extend_from_slice(&(batch.operations().len() as u32).to_le_bytes());
+    for operation in batch.operations() {
+        match operation {
+            StorageOp::Insert(map, key, value) => {
+                payload.push(INSERT_OP);
+                payload.push(map.id());
+                write_bytes(&mut payload, key)?;
+                write_bytes(&mut payload, value)?;
+            }
+            StorageOp::Remove(map, key) => {
+                payload.push(REMOVE_OP);
+                payload.push(map.id());
+                write_bytes(&mut payload, key)?;
+            }
+        }
+    }
+
+    let length: u32 = payload
+        .len()
+        .try_into()
+        .map_err(|_| anyhow!("Storage batch exceeds the maximum record size"))?;
+
+    let mut record = Vec::with_capacity(RECORD_HEADER_SIZE + payload.len());
+    record.extend_from_slice(&length.to_le_bytes());
+    record.extend_from_slice(&Blake2s::digest(&payload));
+    record.extend_from_slice(&payload);
+    Ok(record)
+}
+
+///
+/// Reads the next log record from the given reader, given the number of bytes remaining in the log,
+/// returning the batch and the number of bytes the record spans.
+/// Returns `None` if the record is cut off by the end of the log, fails its checksum, or is malformed.
+///
+fn read_record<R: Read>(reader: &mut R, remaining_length: u64) -> Result<Option<(StorageBatch, u64)>> {
+    if remaining_length < RECORD_HEADER_SIZE as u64 {
+        return Ok(None);
+    }
+    let mut header = [0u8; RECORD_HEADER_SIZE];
+    reader.read_exact(&mut header)?;
+
+    // Ensure the payload ends before the log does, before reading it.
+    let length = u32::from_le_bytes([header[0], header[1], header[2], header[3]]) as u64;
+    let record_length = RECORD_HEADER_SIZE as u64 + length;
+    if record_length > remaining_length {
+        return Ok(None);
+    }
+    let mut payload = vec![0u8; length as usize];
+    reader.read_exact(&mut payload)?;
+
+    // Ensure the payload matches its checksum.
+    if Blake2s::digest(&payload)[..] != header[4..] {
+        return Ok(None);
+    }
+
+    Ok(decode_payload(&payload).map(|batch| (batch, record_length)))
+}
+
+/// Returns the batch encoded in the given record payload, if it is well-formed.
+fn decode_payload(payload: &[u8]) -> Option<StorageBatch> {
+    let mut cursor = payload;
+    let num_operations = read_u32(&mut cursor)?;
+    let mut batch = StorageBatch::new();
+    for _ in 0..num_operations {
+        let (kind, map) = match cursor {
+            [kind, map, rest @ ..] => {
+                cursor = rest;
+                (*kind, DataMap::from_id(*map)?)
+            }
+            _ => return None,
+        };
+        match kind {
+            INSERT_OP => {
+                let key = read_bytes(&mut cursor)?;
+                let value = read_bytes(&mut cursor)?;
+                batch.insert(map, key, value);
+            }
+            REMOVE_OP => batch.remove(map, read_bytes(&mut cursor)?),
+            _ => return None,
+        }
+    }
+
+    // Ensure the payload was consumed entirely.
+    match cursor.is_empty() {
+        true => Some(batch),
+        false => None,
+    }
+}
+
+fn write_bytes(buffer: &mut Vec<u8>, bytes: &[u8]) -> Result<()> {
+    let length: u32 = bytes
+        .len()
+        .try_into()
+        .map_err(|_| anyhow!("Storage entry exceeds the maximum entry size"))?;
+    buffer.extend_from_slice(&length.to_le_bytes());
+    buffer.extend_from_slice(bytes);
+    Ok(())
+}
+
+fn read_u32(cursor: &mut &[u8]) -> Option<u32> {
+    let bytes = cursor.get(0..4)?;
+    let value = u32::from_le_bytes(bytes.try_into().ok()?);
+    *cursor = &cursor[4..];
+    Some(value)
+}
+
+fn read_bytes(cursor: &mut &[u8]) -> Option<Vec<u8>> {
+    let length = read_u32(cursor)? as usize;
+    let bytes = cursor.get(0..length)?.to_vec();
+    *cursor = &cursor[length..];
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_batch(index: u8) -> StorageBatch {
+        let mut batch = StorageBatch::new();
+        batch.insert(DataMap::Headers, vec![index], vec![index; 64]);
+        batch.insert(DataMap::Transactions, vec![index], vec![index; 128]);
+        batch
+    }
+
+    #[test]
+    fn test_commit_and_reopen() {
+        let directory = tempfile::tempdir().unwrap();
+        {
+            let storage = FileStorage::open(directory.path()).unwrap();
+            storage.commit(sample_batch(0)).unwrap();
+            storage.commit(sample_batch(1)).unwrap();
+
+            let mut batch = StorageBatch::new();
+            batch.remove(DataMap::Headers, vec![0]);
+            storage.commit(batch).unwrap();
+        }
+
+        let storage = FileStorage::open(directory.path()).unwrap();
+        assert_eq!(None, storage.get(DataMap::Headers, &[0]).unwrap());
+        assert_eq!(Some(vec![1; 64]), storage.get(DataMap::Headers, &[1]).unwrap());
+        assert_eq!(2, storage.get_all(DataMap::Transactions).unwrap().len());
+    }
+
+    #[test]
+    fn test_recover_from_partial_write() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = {
+            let storage = FileStorage::open(directory.path()).unwrap();
+            storage.commit(sample_batch(0)).unwrap();
+            storage.path().to_path_buf()
+        };
+        let valid_length = fs::metadata(&path).unwrap().len();
+
+        // Simulate a crash partway through writing the next record.
+        let record = encode_record(&sample_batch(1)).unwrap();
+        for cutoff in [1, RECORD_HEADER_SIZE, record.len() / 2, record.len() - 1] {
+            {
+                let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+                file.write_all(&record[..cutoff]).unwrap();
+            }
+
+            let storage = FileStorage::open(directory.path()).unwrap();
+            assert_eq!(Some(vec![0; 64]), storage.get(DataMap::Headers, &[0]).unwrap());
+            assert_eq!(None, storage.get(DataMap::Headers, &[1]).unwrap());
+            assert_eq!(valid_length, fs::metadata(&path).unwrap().len());
+        }
+
+        // Ensure the storage remains writable after recovery.
+        let storage = FileStorage::open(directory.path()).unwrap();
+        storage.commit(sample_batch(1)).unwrap();
+        drop(storage);
+        let storage = FileStorage::open(directory.path()).unwrap();
+        assert_eq!(Some(vec![1; 64]), storage.get(DataMap::Headers, &[1]).unwrap());
+    }
+
+    #[test]
+    fn test_recover_from_corrupted_record() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = {
+            let storage = FileStorage::open(directory.path()).unwrap();
+            storage.commit(sample_batch(0)).unwrap();
+            storage.commit(sample_batch(1)).unwrap();
+            storage.path().to_path_buf()
+        };
+
+        // Flip the last byte of the second record.
+        let mut bytes = fs::read(&path).unwrap();
+        *bytes.last_mut().unwrap() ^= 1;
+        fs::write(&path, &bytes).unwrap();
+
+        let storage = FileStorage::open(directory.path()).unwrap();
+        assert_eq!(Some(vec![0; 64]), storage.get(DataMap::Headers, &[0]).unwrap());
+        assert_eq!(None, storage.get(DataMap::Headers, &[1]).unwrap());
+    }
+
+    #[test]
+    fn test_truncate_at_corrupted_record() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = {
+            let storage = FileStorage::open(directory.path()).unwrap();
+            (0..3).for_each(|index| storage.commit(sample_batch(index)).unwrap());
+            storage.path().to_path_buf()
+        };
+        let bytes = fs::read(&path).unwrap();
+        let record_length = encode_record(&sample_batch(0)).unwrap().len();
+
+        // Flip a byte in the payload, and then in the length, of the middle record.
+        for position in [record_length + RECORD_HEADER_SIZE + 8, record_length + 2] {
+            let mut corrupted = bytes.clone();
+            corrupted[position] ^= 1;
+            fs::write(&path, &corrupted).unwrap();
+
+            // Ensure the log is truncated at the corrupted record.
+            let storage = FileStorage::open(directory.path()).unwrap();
+            assert_eq!(Some(vec![0; 64]), storage.get(DataMap::Headers, &[0]).unwrap());
+            assert_eq!(None, storage.get(DataMap::Headers, &[1]).unwrap());
+            assert_eq!(None, storage.get(DataMap::Headers, &[2]).unwrap());
+            assert_eq!(record_length as u64, fs::metadata(&path).unwrap().len());
+        }
+    }
+
+    #[test]
+    fn test_compact() {
+        let directory = tempfile::tempdir().unwrap();
+        let storage = FileStorage::open(directory.path()).unwrap();
+        for index in 0..10 {
+            storage.commit(sample_batch(index)).unwrap();
+        }
+        let mut batch = StorageBatch::new();
+        (0..9).for_each(|index| batch.remove(DataMap::Headers, vec![index]));
+        storage.commit(batch).unwrap();
+
+        let length_before = fs::metadata(storage.path()).unwrap().len();
+        storage.compact().unwrap();
+        assert!(fs::metadata(storage.path()).unwrap().len() < length_before);
+
+        // Ensure commits after compaction are appended, and the state survives a reopen.
+        storage.commit(sample_batch(10)).unwrap();
+        drop(storage);
+        let storage = FileStorage::open(directory.path()).unwrap();
+        assert_eq!(2, storage.get_all(DataMap::Headers).unwrap().len());
+        assert_eq!(11, storage.get_all(DataMap::Transactions).unwrap().len());
+    }
+}
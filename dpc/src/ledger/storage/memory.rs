@@ -0,0 +1,63 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{ledger::storage::*, DataMap, Storage, StorageBatch};
+
+use anyhow::{anyhow, Result};
+use std::sync::RwLock;
+
+/// A storage backend that keeps all ledger state in memory.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    maps: RwLock<StorageMaps>,
+}
+
+impl MemoryStorage {
+    /// Initializes a new, empty in-memory storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self, map: DataMap, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        let maps = self
+            .maps
+            .read()
+            .map_err(|_| anyhow!("Memory storage lock is poisoned"))?;
+        Ok(maps.get(&map).and_then(|entries| entries.get(key)).cloned())
+    }
+
+    fn get_all(&self, map: DataMap) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let maps = self
+            .maps
+            .read()
+            .map_err(|_| anyhow!("Memory storage lock is poisoned"))?;
+        Ok(maps
+            .get(&map)
+            .map(|entries| entries.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+            .unwrap_or_default())
+    }
+
+    fn commit(&self, batch: StorageBatch) -> Result<()> {
+        let mut maps = self
+            .maps
+            .write()
+            .map_err(|_| anyhow!("Memory storage lock is poisoned"))?;
+        apply_batch(&mut maps, &batch);
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+pub mod file;
+pub use file::*;
+
+pub mod memory;
+pub use memory::*;
+
+use crate::{DataMap, StorageBatch, StorageOp};
+
+use std::collections::{BTreeMap, HashMap};
+
+/// The in-memory representation of all maps in a storage backend.
+pub(crate) type StorageMaps = HashMap<DataMap, BTreeMap<Vec<u8>, Vec<u8>>>;
+
+/// Applies the given batch of operations to the given maps.
+pub(crate) fn apply_batch(maps: &mut StorageMaps, batch: &StorageBatch) {
+    for operation in batch.operations() {
+        match operation {
+            StorageOp::Insert(map, key, value) => {
+                maps.entry(*map).or_default().insert(key.clone(), value.clone());
+            }
+            StorageOp::Remove(map, key) => {
+                if let Some(entries) = maps.get_mut(map) {
+                    entries.remove(key);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{prelude::*, testnet2::Testnet2};
+
+use rand::{CryptoRng, Rng};
+
+/// Returns a noop transaction, which pays no fee.
+pub(crate) fn sample_noop_transaction<R: Rng + CryptoRng>(rng: &mut R) -> Transaction<Testnet2> {
+    let ledger_proofs = vec![LedgerProof::default(); Testnet2::NUM_INPUT_RECORDS];
+    let request = Request::new_noop(ledger_proofs, rng).unwrap();
+    Transaction::new(LedgerTree::new().unwrap(), &request, rng).unwrap()
+}
+
+/// Returns a transaction that pays the given fee from the given record, and returns the remainder to the caller.
+pub(crate) fn sample_fee_transaction<R: Rng + CryptoRng>(
+    ledger: &Ledger<Testnet2>,
+    caller: &Account<Testnet2>,
+    record: &Record<Testnet2>,
+    fee: AleoAmount,
+    rng: &mut R,
+) -> Transaction<Testnet2> {
+    let ledger_proof = ledger.to_ledger_proof(record.commitment()).unwrap();
+    TransactionBuilder::new()
+        .add_caller(caller.private_key().clone())
+        .add_input(record.clone(), ledger_proof)
+        .add_payment(caller.address(), record.value().sub(fee))
+        .set_fee(fee)
+        .build(rng)
+        .unwrap()
+}
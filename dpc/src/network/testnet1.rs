@@ -19,6 +19,7 @@ use crate::{
     posw::PoSW,
     AleoLocator,
     AleoObject,
+    BitcoinRetarget,
     Block,
     Ciphertext,
     InnerPublicVariables,
@@ -112,6 +113,7 @@ impl Network for Testnet1 {
     const SIGNATURE_PREFIX: u32 = hrp4!("sign");
 
     const ADDRESS_SIZE_IN_BYTES: usize = 32;
+    const COMPUTE_KEY_SIZE_IN_BYTES: usize = 128;
     const HEADER_SIZE_IN_BYTES: usize = 903;
     const HEADER_PROOF_SIZE_IN_BYTES: usize = 771;
     const INNER_PROOF_SIZE_IN_BYTES: usize = 193;
@@ -164,6 +166,8 @@ impl Network for Testnet1 {
     type PoSWProof = AleoObject<<Self::PoSWSNARK as SNARK>::Proof, { Self::HEADER_PROOF_PREFIX }, { Self::HEADER_PROOF_SIZE_IN_BYTES }>;
     type PoSW = PoSW<Self>;
 
+    type DifficultyRetarget = BitcoinRetarget;
+
     type AccountEncryptionScheme = ECIESPoseidonEncryption<Self::ProgramCurveParameters>;
     type AccountEncryptionGadget = ECIESPoseidonEncryptionGadget<Self::ProgramCurveParameters, Self::InnerScalarField>;
 
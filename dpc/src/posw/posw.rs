@@ -16,12 +16,21 @@
 
 //! Generic PoSW Miner and Verifier, compatible with any implementer of the SNARK trait.
 
-use crate::{posw::PoSWCircuit, BlockHeader, Network, PoSWScheme, PoswError};
-use core::sync::atomic::AtomicBool;
-use snarkvm_algorithms::{crh::sha256d_to_u64, traits::SNARK, SRS};
+use crate::{posw::PoSWCircuit, BlockHeader, MiningStatistics, Network, PoSWScheme, PoswError};
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use snarkvm_algorithms::{crh::sha256d_to_u64, errors::SNARKError, traits::SNARK, SRS};
 use snarkvm_utilities::{FromBytes, ToBytes, UniformRand};
 
-use rand::{CryptoRng, Rng};
+use anyhow::anyhow;
+use rand::{rngs::StdRng, CryptoRng, Rng, SeedableRng};
+use std::{
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// The interval at which parallel mining checks the terminator for cancellation.
+const TERMINATOR_POLL_INTERVAL: Duration = Duration::from_millis(10);
 
 /// A Proof of Succinct Work miner and verifier.
 #[derive(Clone)]
@@ -76,7 +85,7 @@ impl<N: Network> PoSWScheme<N> for PoSW<N> {
         terminator: &AtomicBool,
         rng: &mut R,
     ) -> Result<(), PoswError> {
-        let pk = self.proving_key.as_ref().expect("tried to mine without a PK set up");
+        let pk = self.proving_key.as_ref().ok_or(PoswError::MissingProvingKey)?;
 
         loop {
             // Sample a random nonce.
@@ -98,6 +107,107 @@ impl<N: Network> PoSWScheme<N> for PoSW<N> {
         Ok(())
     }
 
+    /// Given the leaves of the block header, it will calculate a PoSW and nonce
+    /// such that they are under the difficulty target, using the given number of workers.
+    /// The nonce space is split across the workers, and the first solution found is returned.
+    /// The `on_attempt` callback is invoked with the mining statistics after each proving attempt.
+    fn mine_parallel<R: Rng + CryptoRng, F: Fn(&MiningStatistics) + Sync>(
+        &self,
+        block_header: &mut BlockHeader<N>,
+        num_workers: usize,
+        terminator: &AtomicBool,
+        on_attempt: F,
+        rng: &mut R,
+    ) -> Result<(), PoswError> {
+        let pk = self.proving_key.as_ref().ok_or(PoswError::MissingProvingKey)?;
+        let num_workers = num_workers.max(1);
+
+        // Sample a random starting nonce, and a random seed for each worker.
+        let start_nonce: N::InnerScalarField = UniformRand::rand(rng);
+        let seeds = (0..num_workers).map(|_| rng.gen::<[u8; 32]>()).collect::<Vec<_>>();
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_workers)
+            .build()
+            .map_err(|error| anyhow!("Failed to initialize the mining thread pool: {}", error))?;
+
+        let is_finished = &AtomicBool::new(false);
+        let num_attempts = &AtomicU64::new(0);
+        let solution = &Mutex::new(None);
+        let failure = &Mutex::new(None);
+        let on_attempt = &on_attempt;
+        let start = Instant::now();
+
+        pool.in_place_scope(|scope| {
+            for (worker_index, seed) in seeds.into_iter().enumerate() {
+                let mut candidate = block_header.clone();
+                scope.spawn(move |_| {
+                    let mut rng = StdRng::from_seed(seed);
+
+                    // Each worker tries the nonces congruent to its index, modulo the number of workers.
+                    let step = N::InnerScalarField::from(num_workers as u64);
+                    let mut nonce = start_nonce + N::InnerScalarField::from(worker_index as u64);
+
+                    while !is_finished.load(Ordering::SeqCst) {
+                        candidate.set_nonce(nonce.into());
+                        nonce += step;
+
+                        // Generate the proof, stopping early if another worker succeeds or mining is cancelled.
+                        let proof = PoSWCircuit::<N>::new(&candidate)
+                            .map_err(PoswError::from)
+                            .and_then(|circuit| {
+                                Ok(<<N as Network>::PoSWSNARK as SNARK>::prove_with_terminator(
+                                    pk,
+                                    &circuit,
+                                    is_finished,
+                                    &mut rng,
+                                )?)
+                            });
+
+                        match proof {
+                            Ok(proof) => candidate.set_proof(proof.into()),
+                            Err(error) => {
+                                if !is_finished.swap(true, Ordering::SeqCst) {
+                                    *failure.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(error);
+                                }
+                                break;
+                            }
+                        }
+
+                        let num_attempts = num_attempts.fetch_add(1, Ordering::SeqCst) + 1;
+                        on_attempt(&MiningStatistics::new(num_attempts, start.elapsed()));
+
+                        if self.verify(&candidate) {
+                            if !is_finished.swap(true, Ordering::SeqCst) {
+                                *solution.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(candidate);
+                            }
+                            break;
+                        }
+                    }
+                });
+            }
+
+            // Stop all workers if mining is cancelled, watching from the calling thread.
+            while !is_finished.load(Ordering::SeqCst) {
+                if terminator.load(Ordering::SeqCst) {
+                    is_finished.store(true, Ordering::SeqCst);
+                }
+                thread::sleep(TERMINATOR_POLL_INTERVAL);
+            }
+        });
+
+        if let Some(candidate) = solution.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take() {
+            *block_header = candidate;
+            return Ok(());
+        }
+
+        let failure = failure.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+        match failure {
+            Some(error) => Err(error),
+            None => Err(SNARKError::Terminated.into()),
+        }
+    }
+
     /// Verifies the Proof of Succinct Work against the nonce, root, and difficulty target.
     fn verify(&self, block_header: &BlockHeader<N>) -> bool {
         // Retrieve the proof.
@@ -147,15 +257,27 @@ impl<N: Network> PoSWScheme<N> for PoSW<N> {
 
 #[cfg(test)]
 mod tests {
-    use core::sync::atomic::AtomicBool;
+    use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
-    use crate::{testnet2::Testnet2, Network, PoSWScheme};
+    use crate::{testnet2::Testnet2, Network, PoSWScheme, PoswError};
     use snarkvm_algorithms::{SNARK, SRS};
     use snarkvm_marlin::ahp::AHPForR1CS;
     use snarkvm_utilities::ToBytes;
 
     use rand::{rngs::ThreadRng, thread_rng};
 
+    /// Constructs an instance of PoSW from a universal setup.
+    fn sample_posw() -> <Testnet2 as Network>::PoSW {
+        let max_degree =
+            AHPForR1CS::<<Testnet2 as Network>::InnerScalarField>::max_degree(20000, 20000, 200000).unwrap();
+        let universal_srs =
+            <<Testnet2 as Network>::PoSWSNARK as SNARK>::universal_setup(&max_degree, &mut thread_rng()).unwrap();
+        <<Testnet2 as Network>::PoSW as PoSWScheme<Testnet2>>::setup::<ThreadRng>(&mut SRS::<ThreadRng, _>::Universal(
+            &universal_srs,
+        ))
+        .unwrap()
+    }
+
     #[test]
     fn test_load() {
         let _params = <<Testnet2 as Network>::PoSW as PoSWScheme<Testnet2>>::load(true).unwrap();
@@ -164,16 +286,7 @@ mod tests {
     #[test]
     fn test_posw_marlin() {
         // Construct an instance of PoSW.
-        let posw = {
-            let max_degree =
-                AHPForR1CS::<<Testnet2 as Network>::InnerScalarField>::max_degree(20000, 20000, 200000).unwrap();
-            let universal_srs =
-                <<Testnet2 as Network>::PoSWSNARK as SNARK>::universal_setup(&max_degree, &mut thread_rng()).unwrap();
-            <<Testnet2 as Network>::PoSW as PoSWScheme<Testnet2>>::setup::<ThreadRng>(
-                &mut SRS::<ThreadRng, _>::Universal(&universal_srs),
-            )
-            .unwrap()
-        };
+        let posw = sample_posw();
 
         // Construct a block header.
         let mut block_header = Testnet2::genesis_block().header().clone();
@@ -187,4 +300,54 @@ mod tests {
         ); // NOTE: Marlin proofs use compressed serialization
         assert!(posw.verify(&block_header));
     }
+
+    #[test]
+    fn test_posw_marlin_mine_parallel() {
+        // Construct an instance of PoSW.
+        let posw = sample_posw();
+
+        // Mine a block header with multiple workers, tracking the reported attempts.
+        let num_attempts = AtomicU64::new(0);
+        let mut block_header = Testnet2::genesis_block().header().clone();
+        posw.mine_parallel(
+            &mut block_header,
+            4,
+            &AtomicBool::new(false),
+            |statistics| {
+                num_attempts.fetch_max(statistics.num_attempts(), Ordering::SeqCst);
+            },
+            &mut thread_rng(),
+        )
+        .unwrap();
+
+        assert!(num_attempts.load(Ordering::SeqCst) >= 1);
+        assert!(posw.verify(&block_header));
+    }
+
+    #[test]
+    fn test_posw_marlin_mine_parallel_terminated() {
+        // Construct an instance of PoSW.
+        let posw = sample_posw();
+
+        // Ensure mining stops, and the block header is unchanged, when the terminator is set.
+        let expected_block_header = Testnet2::genesis_block().header().clone();
+        let mut block_header = expected_block_header.clone();
+        let result = posw.mine_parallel(&mut block_header, 4, &AtomicBool::new(true), |_| {}, &mut thread_rng());
+
+        assert!(result.is_err());
+        assert_eq!(expected_block_header, block_header);
+    }
+
+    #[test]
+    fn test_mine_without_proving_key() {
+        // Construct an instance of PoSW that only verifies.
+        let posw = <<Testnet2 as Network>::PoSW as PoSWScheme<Testnet2>>::load(false).unwrap();
+
+        // Ensure mining fails with an error instead of panicking.
+        let mut block_header = Testnet2::genesis_block().header().clone();
+        let result = posw.mine(&mut block_header, &AtomicBool::new(false), &mut thread_rng());
+        assert!(matches!(result, Err(PoswError::MissingProvingKey)));
+        let result = posw.mine_parallel(&mut block_header, 4, &AtomicBool::new(false), |_| {}, &mut thread_rng());
+        assert!(matches!(result, Err(PoswError::MissingProvingKey)));
+    }
 }
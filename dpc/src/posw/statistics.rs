@@ -0,0 +1,51 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::time::Duration;
+
+/// The progress of a PoSW miner, reported after each proving attempt.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MiningStatistics {
+    /// The number of proofs generated across all workers.
+    num_attempts: u64,
+    /// The time elapsed since mining started.
+    elapsed: Duration,
+}
+
+impl MiningStatistics {
+    /// Initializes a new instance of mining statistics.
+    pub fn new(num_attempts: u64, elapsed: Duration) -> Self {
+        Self { num_attempts, elapsed }
+    }
+
+    /// Returns the number of proofs generated across all workers.
+    pub fn num_attempts(&self) -> u64 {
+        self.num_attempts
+    }
+
+    /// Returns the time elapsed since mining started.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the number of proofs generated per second.
+    pub fn hashrate(&self) -> f64 {
+        match self.elapsed.as_secs_f64() > 0.0 {
+            true => self.num_attempts as f64 / self.elapsed.as_secs_f64(),
+            false => 0.0,
+        }
+    }
+}
@@ -157,6 +157,14 @@ impl<N: Network> RecordProof<N> {
     pub fn local_proof(&self) -> &LocalProof<N> {
         &self.local_proof
     }
+
+    /// Returns `true` if this record proof was constructed with `RecordProof::new_dummy`.
+    pub(crate) fn is_dummy(&self) -> bool {
+        let dummy = Self::new_dummy(self.local_proof.clone())
+            .ok()
+            .and_then(|dummy| dummy.to_bytes_le().ok());
+        dummy == self.to_bytes_le().ok()
+    }
 }
 
 impl<N: Network> FromBytes for RecordProof<N> {
@@ -170,6 +178,29 @@ impl<N: Network> FromBytes for RecordProof<N> {
         let transactions_inclusion_proof = FromBytes::read_le(&mut reader)?;
         let local_proof = FromBytes::read_le(&mut reader)?;
 
+        // Dummy record proofs, which are used for local and noop records, do not prove inclusion in a block.
+        let record_proof = Self {
+            block_hash,
+            previous_block_hash,
+            block_header_root,
+            block_header_inclusion_proof,
+            transactions_root,
+            transactions_inclusion_proof,
+            local_proof,
+        };
+        if record_proof.is_dummy() {
+            return Ok(record_proof);
+        }
+
+        let Self {
+            block_hash,
+            previous_block_hash,
+            block_header_root,
+            block_header_inclusion_proof,
+            transactions_root,
+            transactions_inclusion_proof,
+            local_proof,
+        } = record_proof;
         Ok(Self::new(
             block_hash,
             previous_block_hash,
@@ -179,7 +210,7 @@ impl<N: Network> FromBytes for RecordProof<N> {
             transactions_inclusion_proof,
             local_proof,
         )
-        .expect("Failed to deserialize a record inclusion proof"))
+        .map_err(RecordError::from)?)
     }
 }
 
@@ -30,6 +30,9 @@ pub trait LedgerTreeScheme<N: Network>: Sized {
     /// Adds all given block hashes to the tree, returning the start and ending index in the tree.
     fn add_all(&mut self, block_hashes: &[N::BlockHash]) -> Result<(u32, u32)>;
 
+    /// Removes all block hashes at or after the given index from the tree.
+    fn truncate(&mut self, index: u32) -> Result<()>;
+
     /// Returns `true` if the given block hash exists.
     fn contains_block_hash(&self, block_hash: &N::BlockHash) -> bool;
 
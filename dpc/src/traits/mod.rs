@@ -17,6 +17,9 @@
 pub mod account;
 pub use account::*;
 
+pub mod difficulty_retarget;
+pub use difficulty_retarget::*;
+
 pub mod function;
 pub use function::*;
 
@@ -28,3 +31,6 @@ pub use network::*;
 
 pub mod posw;
 pub use posw::*;
+
+pub mod storage_backend;
+pub use storage_backend::*;
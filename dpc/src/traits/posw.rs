@@ -16,7 +16,7 @@
 
 use core::sync::atomic::AtomicBool;
 
-use crate::{BlockHeader, Network, PoswError};
+use crate::{BlockHeader, MiningStatistics, Network, PoswError};
 use snarkvm_algorithms::{traits::SNARK, SRS};
 
 use anyhow::Result;
@@ -46,6 +46,19 @@ pub trait PoSWScheme<N: Network>: Clone + Send + Sync {
         rng: &mut R,
     ) -> Result<(), PoswError>;
 
+    /// Given the leaves of the block header, it will calculate a PoSW and nonce
+    /// such that they are under the difficulty target, using the given number of workers.
+    /// The nonce space is split across the workers, and the first solution found is returned.
+    /// The `on_attempt` callback is invoked with the mining statistics after each proving attempt.
+    fn mine_parallel<R: Rng + CryptoRng, F: Fn(&MiningStatistics) + Sync>(
+        &self,
+        block_header: &mut BlockHeader<N>,
+        num_workers: usize,
+        terminator: &AtomicBool,
+        on_attempt: F,
+        rng: &mut R,
+    ) -> Result<(), PoswError>;
+
     /// Verifies the Proof of Succinct Work against the nonce, root, and difficulty target.
     fn verify(&self, block_header: &BlockHeader<N>) -> bool;
 }
@@ -0,0 +1,102 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::Result;
+
+/// The logical maps persisted by a ledger storage backend.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum DataMap {
+    /// Maps a block height to its block hash.
+    BlockHashes = 0,
+    /// Maps a block height to its previous block hash.
+    PreviousHashes = 1,
+    /// Maps a block height to its block header.
+    Headers = 2,
+    /// Maps a block height to its block transactions.
+    Transactions = 3,
+}
+
+impl DataMap {
+    /// Returns the map for the given identifier, if it exists.
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::BlockHashes),
+            1 => Some(Self::PreviousHashes),
+            2 => Some(Self::Headers),
+            3 => Some(Self::Transactions),
+            _ => None,
+        }
+    }
+
+    /// Returns the identifier for the map.
+    pub fn id(&self) -> u8 {
+        *self as u8
+    }
+}
+
+/// A single write operation in a storage batch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StorageOp {
+    Insert(DataMap, Vec<u8>, Vec<u8>),
+    Remove(DataMap, Vec<u8>),
+}
+
+/// A list of write operations that are committed atomically.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StorageBatch {
+    operations: Vec<StorageOp>,
+}
+
+impl StorageBatch {
+    /// Initializes a new, empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an insertion of the given key and value into the given map.
+    pub fn insert(&mut self, map: DataMap, key: Vec<u8>, value: Vec<u8>) {
+        self.operations.push(StorageOp::Insert(map, key, value));
+    }
+
+    /// Adds a removal of the given key from the given map.
+    pub fn remove(&mut self, map: DataMap, key: Vec<u8>) {
+        self.operations.push(StorageOp::Remove(map, key));
+    }
+
+    /// Returns `true` if the batch contains no operations.
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Returns the operations in the batch.
+    pub fn operations(&self) -> &[StorageOp] {
+        &self.operations
+    }
+}
+
+/// A key-value storage backend for the ledger.
+pub trait Storage: Send + Sync {
+    /// Returns the value for the given key in the given map, if it exists.
+    fn get(&self, map: DataMap, key: &[u8]) -> Result<Option<Vec<u8>>>;
+
+    /// Returns all key-value pairs in the given map, sorted by key.
+    fn get_all(&self, map: DataMap) -> Result<Vec<(Vec<u8>, Vec<u8>)>>;
+
+    /// Atomically applies all operations in the given batch. Either every operation
+    /// is durably applied, or none of them are.
+    fn commit(&self, batch: StorageBatch) -> Result<()>;
+}
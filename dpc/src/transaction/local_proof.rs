@@ -94,6 +94,11 @@ impl<N: Network> LocalProof<N> {
     pub fn commitment(&self) -> N::Commitment {
         self.commitment
     }
+
+    /// Returns `true` if this is the default local proof.
+    pub(crate) fn is_dummy(&self) -> bool {
+        self.to_bytes_le().ok() == Self::default().to_bytes_le().ok()
+    }
 }
 
 impl<N: Network> FromBytes for LocalProof<N> {
@@ -105,6 +110,25 @@ impl<N: Network> FromBytes for LocalProof<N> {
         let transition_inclusion_proof = FromBytes::read_le(&mut reader)?;
         let commitment = FromBytes::read_le(&mut reader)?;
 
+        // Dummy local proofs, which are used for noop records, do not prove inclusion in a transition.
+        let local_proof = Self {
+            transaction_id,
+            transaction_inclusion_proof,
+            transition_id,
+            transition_inclusion_proof,
+            commitment,
+        };
+        if local_proof.is_dummy() {
+            return Ok(local_proof);
+        }
+
+        let Self {
+            transaction_id,
+            transaction_inclusion_proof,
+            transition_id,
+            transition_inclusion_proof,
+            commitment,
+        } = local_proof;
         Ok(Self::new(
             transaction_id,
             transaction_inclusion_proof,
@@ -112,7 +136,7 @@ impl<N: Network> FromBytes for LocalProof<N> {
             transition_inclusion_proof,
             commitment,
         )
-        .expect("Failed to deserialize a local inclusion proof"))
+        .map_err(VMError::from)?)
     }
 }
 
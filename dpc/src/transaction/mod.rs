@@ -14,6 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod transaction_builder;
+pub use transaction_builder::*;
+
 pub(crate) mod local_proof;
 pub(crate) use local_proof::*;
 
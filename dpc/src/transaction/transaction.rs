@@ -18,17 +18,21 @@ use crate::{
     record::*,
     Address,
     AleoAmount,
+    Deployment,
     Event,
     LedgerTree,
     LedgerTreeScheme,
     LocalProof,
     Network,
+    OuterPublicVariables,
     Request,
     Transition,
     Transitions,
+    VMError,
     ViewKey,
     VirtualMachine,
 };
+use snarkvm_algorithms::traits::SNARK;
 use snarkvm_utilities::{
     has_duplicates,
     io::{Read, Result as IoResult, Write},
@@ -40,7 +44,7 @@ use snarkvm_utilities::{
 
 use anyhow::{anyhow, Result};
 use itertools::Itertools;
-use rand::{CryptoRng, Rng};
+use rand::{thread_rng, CryptoRng, Rng};
 use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     fmt,
@@ -114,6 +118,23 @@ impl<N: Network> Transaction<N> {
     /// correct ciphertext IDs, and a valid proof.
     #[inline]
     pub fn is_valid(&self) -> bool {
+        // Returns `false` if the transaction is malformed.
+        if !self.is_well_formed() {
+            return false;
+        }
+
+        // Returns `false` if any transition proof is invalid.
+        if let Err(error) = Self::verify_transition_proofs(std::slice::from_ref(self), &mut thread_rng()) {
+            eprintln!("{}", error);
+            return false;
+        }
+
+        true
+    }
+
+    /// Returns `true` if the transaction is well-formed, without verifying its transition proofs.
+    #[inline]
+    pub(crate) fn is_well_formed(&self) -> bool {
         // Ensure the number of transitions is between 1 and N::NUM_TRANSITIONS.
         let num_transitions = self.transitions.len();
         if num_transitions < 1 || num_transitions > N::NUM_TRANSITIONS as usize {
@@ -185,8 +206,8 @@ impl<N: Network> Transaction<N> {
 
         // Returns `false` if any transition is invalid.
         for transition in &self.transitions {
-            // Returns `false` if the transition is invalid.
-            if !transition.verify(self.inner_circuit_id, self.ledger_root, transitions.root()) {
+            // Returns `false` if the transition is malformed.
+            if !transition.is_well_formed() {
                 eprintln!("Transaction contains an invalid transition");
                 return false;
             }
@@ -213,6 +234,71 @@ impl<N: Network> Transaction<N> {
         true
     }
 
+    ///
+    /// Verifies the transition proofs of the given transactions as a single batch.
+    ///
+    /// If the batch is invalid, each transition proof is verified individually,
+    /// and the returned error names the offending transaction and transition.
+    ///
+    pub(crate) fn verify_transition_proofs<R: Rng + CryptoRng>(transactions: &[Self], rng: &mut R) -> Result<()> {
+        let transition_proofs = transactions
+            .iter()
+            .map(Self::to_transition_proofs)
+            .collect::<Result<Vec<_>>>()?;
+
+        let inputs_and_proofs = transition_proofs
+            .iter()
+            .flatten()
+            .map(|(inputs, proof)| (inputs, &***proof))
+            .collect::<Vec<_>>();
+        if let Ok(true) = N::OuterSNARK::verify_batch(N::outer_verifying_key(), &inputs_and_proofs, rng) {
+            return Ok(());
+        }
+
+        // Find the offending transition proof.
+        for (transaction, transition_proofs) in transactions.iter().zip_eq(&transition_proofs) {
+            for (transition, (inputs, proof)) in transaction.transitions.iter().zip_eq(transition_proofs) {
+                match N::OuterSNARK::verify(N::outer_verifying_key(), inputs, proof) {
+                    Ok(true) => continue,
+                    Ok(false) => {
+                        return Err(anyhow!(
+                            "Transaction {} contains an invalid proof for transition {}",
+                            transaction.transaction_id,
+                            transition.transition_id()
+                        ));
+                    }
+                    Err(error) => {
+                        return Err(anyhow!(
+                            "Transaction {} failed to verify the proof for transition {}: {}",
+                            transaction.transaction_id,
+                            transition.transition_id(),
+                            error
+                        ));
+                    }
+                }
+            }
+        }
+
+        Err(anyhow!("Failed to verify the batch of transition proofs"))
+    }
+
+    /// Returns the public variables and proof of each transition, in the order of the transitions.
+    pub(crate) fn to_transition_proofs(&self) -> Result<Vec<(OuterPublicVariables<N>, &N::OuterProof)>> {
+        // Initialize a local transitions tree.
+        let mut transitions = Transitions::<N>::new()?;
+
+        let mut transition_proofs = Vec::with_capacity(self.transitions.len());
+        for transition in &self.transitions {
+            let inputs =
+                transition.to_outer_public_variables(self.inner_circuit_id, self.ledger_root, transitions.root());
+            transition_proofs.push((inputs, transition.proof()));
+
+            // Update the local transitions tree.
+            transitions.add(transition)?;
+        }
+        Ok(transition_proofs)
+    }
+
     /// Returns `true` if the given transition ID exists.
     pub fn contains_transition_id(&self, transition_id: &N::TransitionID) -> bool {
         self.transitions
@@ -290,6 +376,12 @@ impl<N: Network> Transaction<N> {
         self.transitions.iter().flat_map(Transition::events)
     }
 
+    /// Returns the program deployments.
+    #[inline]
+    pub fn deployments(&self) -> impl Iterator<Item = &Deployment<N>> + fmt::Debug + '_ {
+        self.transitions.iter().flat_map(Transition::deployments)
+    }
+
     /// Returns a reference to the state transitions.
     #[inline]
     pub fn transitions(&self) -> &Vec<Transition<N>> {
@@ -344,12 +436,12 @@ impl<N: Network> FromBytes for Transaction<N> {
         let ledger_root = FromBytes::read_le(&mut reader)?;
 
         let num_transitions: u16 = FromBytes::read_le(&mut reader)?;
-        let mut transitions = Vec::with_capacity(num_transitions as usize);
+        let mut transitions = Vec::new();
         for _ in 0..num_transitions {
             transitions.push(FromBytes::read_le(&mut reader)?);
         }
 
-        Ok(Self::from(inner_circuit_id, ledger_root, transitions).expect("Failed to deserialize a transaction"))
+        Ok(Self::from(inner_circuit_id, ledger_root, transitions).map_err(VMError::from)?)
     }
 }
 
@@ -482,6 +574,42 @@ mod tests {
         assert_eq!(expected_record.program_id(), candidate_record.program_id());
     }
 
+    #[test]
+    fn test_verify_transition_proofs() {
+        let rng = &mut thread_rng();
+        let account = Account::<Testnet2>::new(rng);
+
+        let (first, _) = Transaction::<Testnet2>::new_coinbase(account.address(), AleoAmount(1234), true, rng).unwrap();
+        let (second, _) =
+            Transaction::<Testnet2>::new_coinbase(account.address(), AleoAmount(1234), true, rng).unwrap();
+        assert!(Transaction::verify_transition_proofs(&[first.clone(), second.clone()], rng).is_ok());
+
+        // Craft a transaction that reuses the transition proof of another transaction.
+        let transition = &first.transitions()[0];
+        let forged_transition = Transition::from(
+            transition.transition_id(),
+            transition.serial_numbers().cloned().collect(),
+            transition.ciphertexts().cloned().collect(),
+            *transition.value_balance(),
+            transition.events().cloned().collect(),
+            second.transitions()[0].proof().clone(),
+        )
+        .unwrap();
+        let transitions = vec![forged_transition];
+        let forged = Transaction {
+            transaction_id: Transaction::compute_transaction_id(&transitions).unwrap(),
+            inner_circuit_id: first.inner_circuit_id(),
+            ledger_root: first.ledger_root(),
+            transitions,
+        };
+        assert!(forged.is_well_formed());
+        assert!(!forged.is_valid());
+
+        // Ensure the batch error names the forged transaction.
+        let error = Transaction::verify_transition_proofs(&[first, forged.clone(), second], rng).unwrap_err();
+        assert!(error.to_string().contains(&forged.transaction_id().to_string()));
+    }
+
     #[test]
     fn test_transaction_serde_json() {
         let rng = &mut thread_rng();
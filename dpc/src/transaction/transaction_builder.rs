@@ -0,0 +1,371 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::prelude::*;
+
+use anyhow::{anyhow, Result};
+use once_cell::sync::OnceCell;
+use rand::{CryptoRng, Rng};
+use std::cmp::Reverse;
+
+/// An unspent record, with its ledger proof.
+type UnspentRecord<N> = (Record<N>, LedgerProof<N>);
+
+///
+/// A builder for a transaction that pays a recipient from the unspent records of a caller.
+///
+/// The builder selects input records by descending value until the payment and fee are covered.
+/// As each transition consumes at most `N::NUM_INPUT_RECORDS` records, the selected records are
+/// spent over a chain of transitions, where each transition returns its change to the caller,
+/// and the next transition consumes that change as a local record of the transaction.
+/// The final transition pays the recipient and the fee.
+///
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"))]
+pub struct TransactionBuilder<N: Network> {
+    /// The private key of the caller.
+    caller: OnceCell<PrivateKey<N>>,
+    /// The unspent records of the caller, with their ledger proofs.
+    inputs: Vec<UnspentRecord<N>>,
+    /// The recipient and amount of the payment.
+    payment: OnceCell<(Address<N>, AleoAmount)>,
+    /// The network fee being paid.
+    fee: AleoAmount,
+    /// A list of errors accumulated from calling the builder.
+    errors: Vec<String>,
+}
+
+impl<N: Network> TransactionBuilder<N> {
+    ///
+    /// Initializes a new instance of `TransactionBuilder`.
+    ///
+    pub fn new() -> Self {
+        Self {
+            caller: OnceCell::new(),
+            inputs: Vec::new(),
+            payment: OnceCell::new(),
+            fee: AleoAmount::ZERO,
+            errors: Vec::new(),
+        }
+    }
+
+    ///
+    /// Adds the given caller into the builder.
+    ///
+    pub fn add_caller(mut self, caller: PrivateKey<N>) -> Self {
+        if self.caller.set(caller).is_err() {
+            self.errors.push("Builder already set a caller".into());
+        }
+        self
+    }
+
+    ///
+    /// Adds the given unspent record and its ledger proof into the builder.
+    ///
+    pub fn add_input(mut self, record: Record<N>, ledger_proof: LedgerProof<N>) -> Self {
+        if record.commitment() != ledger_proof.commitment() {
+            self.errors.push(format!(
+                "Ledger proof does not belong to record {}",
+                record.commitment()
+            ));
+        }
+        self.inputs.push((record, ledger_proof));
+        self
+    }
+
+    ///
+    /// Adds the given unspent records and their ledger proofs into the builder.
+    ///
+    pub fn add_inputs(mut self, inputs: Vec<UnspentRecord<N>>) -> Self {
+        for (record, ledger_proof) in inputs {
+            self = self.add_input(record, ledger_proof);
+        }
+        self
+    }
+
+    ///
+    /// Adds the given payment of `amount` to `recipient` into the builder.
+    ///
+    pub fn add_payment(mut self, recipient: Address<N>, amount: AleoAmount) -> Self {
+        if amount.is_negative() {
+            self.errors.push("Builder received a negative payment amount".into());
+        } else if self.payment.set((recipient, amount)).is_err() {
+            self.errors.push("Builder already set a payment".into());
+        }
+        self
+    }
+
+    ///
+    /// Sets the network fee of the transaction.
+    ///
+    pub fn set_fee(mut self, fee: AleoAmount) -> Self {
+        match fee.is_negative() {
+            true => self.errors.push("Builder received a negative fee".into()),
+            false => self.fee = fee,
+        }
+        self
+    }
+
+    ///
+    /// Finalizes the builder and returns a new instance of `Transaction`.
+    ///
+    pub fn build<R: Rng + CryptoRng>(&self, rng: &mut R) -> Result<Transaction<N>> {
+        // Ensure there are no errors in the build process yet.
+        if !self.errors.is_empty() {
+            for error in &self.errors {
+                eprintln!("{}", error);
+            }
+            return Err(anyhow!(
+                "Transaction builder encountered build errors: {:?}",
+                self.errors
+            ));
+        }
+
+        // Fetch the caller and payment.
+        let caller = self.caller.get().ok_or_else(|| anyhow!("Builder is missing caller"))?;
+        let (recipient, amount) = *self
+            .payment
+            .get()
+            .ok_or_else(|| anyhow!("Builder is missing payment"))?;
+
+        // Select the input records, and chain them into a list of transition inputs.
+        let inputs = self.select_inputs(caller.to_address(), amount.add(self.fee))?;
+        let ledger_root = Self::ledger_root(&inputs)?;
+        let chunks = Self::chain_inputs(inputs)?;
+
+        let num_transitions = chunks.len();
+        let mut vm = VirtualMachine::<N>::new(ledger_root)?;
+        let mut change: Option<Record<N>> = None;
+
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            let mut records = Vec::with_capacity(N::NUM_INPUT_RECORDS);
+            let mut ledger_proofs = Vec::with_capacity(N::NUM_INPUT_RECORDS);
+
+            // Consume the change of the previous transition, which is a local record of this transaction.
+            if let Some(change) = change.take() {
+                ledger_proofs.push(LedgerProof::new_dummy(vm.to_local_proof(change.commitment())?)?);
+                records.push(change);
+            }
+            for (record, ledger_proof) in chunk {
+                records.push(record);
+                ledger_proofs.push(ledger_proof);
+            }
+
+            // Pad the ledger proofs for the noop records, which are padded by the request.
+            while ledger_proofs.len() < N::NUM_INPUT_RECORDS {
+                ledger_proofs.push(LedgerProof::default());
+            }
+
+            // Only the final transition pays the recipient and the fee, as the prior transitions
+            // return their entire balance to the caller.
+            let (operation, fee) = match i + 1 == num_transitions {
+                true => (Operation::Transfer(caller.to_address(), recipient, amount), self.fee),
+                false => (
+                    Operation::Transfer(caller.to_address(), caller.to_address(), AleoAmount::ZERO),
+                    AleoAmount::ZERO,
+                ),
+            };
+
+            let request = Request::new(caller, records, ledger_proofs, operation, fee, false, rng)?;
+            let (next_vm, response) = vm.execute(&request, rng)?;
+            vm = next_vm;
+
+            // The first output of a transfer is the change of the caller.
+            change = response.records().first().cloned();
+        }
+
+        vm.finalize()
+    }
+
+    ///
+    /// Returns the input records of the caller with the greatest values, whose total value is at least `target`.
+    ///
+    fn select_inputs(&self, caller: Address<N>, target: AleoAmount) -> Result<Vec<UnspentRecord<N>>> {
+        // Only records of the noop program that are owned by the caller may be spent in a transfer.
+        let mut candidates: Vec<_> = self
+            .inputs
+            .iter()
+            .filter(|(record, _)| {
+                record.owner() == caller
+                    && !record.is_dummy()
+                    && record.program_id() == *N::noop_program_id()
+                    && !record.value().is_zero()
+            })
+            .cloned()
+            .collect();
+        candidates.sort_by_key(|(record, _)| Reverse(record.value()));
+
+        let mut selected = Vec::new();
+        let mut balance = AleoAmount::ZERO;
+        for candidate in candidates {
+            if balance >= target {
+                break;
+            }
+            balance = balance.add(candidate.0.value());
+            selected.push(candidate);
+        }
+
+        match balance >= target {
+            true => Ok(selected),
+            false => Err(VMError::BalanceInsufficient.into()),
+        }
+    }
+
+    ///
+    /// Returns the ledger root of the given inputs, which must be the same for all inputs.
+    ///
+    fn ledger_root(inputs: &[UnspentRecord<N>]) -> Result<N::LedgerRoot> {
+        let ledger_root = match inputs.first() {
+            Some((_, ledger_proof)) => ledger_proof.ledger_root(),
+            None => LedgerTree::<N>::new()?.root(),
+        };
+
+        match inputs
+            .iter()
+            .all(|(_, ledger_proof)| ledger_proof.ledger_root() == ledger_root)
+        {
+            true => Ok(ledger_root),
+            false => Err(anyhow!("Builder received ledger proofs with different ledger roots")),
+        }
+    }
+
+    ///
+    /// Returns the given inputs as a list of chunks, one for each transition.
+    ///
+    /// The first transition consumes up to `N::NUM_INPUT_RECORDS` inputs, and each subsequent
+    /// transition consumes the change of the previous transition and up to `N::NUM_INPUT_RECORDS - 1` inputs.
+    ///
+    fn chain_inputs(inputs: Vec<UnspentRecord<N>>) -> Result<Vec<Vec<UnspentRecord<N>>>> {
+        let mut inputs = inputs.into_iter().peekable();
+
+        let mut chunks = vec![inputs.by_ref().take(N::NUM_INPUT_RECORDS).collect::<Vec<_>>()];
+        while inputs.peek().is_some() {
+            chunks.push(inputs.by_ref().take(N::NUM_INPUT_RECORDS - 1).collect());
+        }
+
+        match chunks.len() <= N::NUM_TRANSITIONS as usize {
+            true => Ok(chunks),
+            false => Err(anyhow!(
+                "Builder requires {} transitions, which exceeds the maximum of {}",
+                chunks.len(),
+                N::NUM_TRANSITIONS
+            )),
+        }
+    }
+}
+
+impl<N: Network> Default for TransactionBuilder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testnet2::Testnet2;
+
+    use rand::thread_rng;
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn test_insufficient_balance() {
+        let rng = &mut thread_rng();
+        let caller = PrivateKey::<Testnet2>::new(rng);
+        let recipient = PrivateKey::<Testnet2>::new(rng).to_address();
+
+        let result = TransactionBuilder::new()
+            .add_caller(caller)
+            .add_payment(recipient, AleoAmount(1))
+            .build(rng);
+        assert!(result.is_err());
+    }
+
+    fn chunk_sizes(num_inputs: usize) -> Result<Vec<usize>> {
+        let rng = &mut thread_rng();
+        let owner = PrivateKey::<Testnet2>::new(rng).to_address();
+
+        let inputs = (0..num_inputs)
+            .map(|_| Ok((Record::new_noop(owner, rng)?, LedgerProof::default())))
+            .collect::<Result<Vec<_>>>()?;
+
+        let chunks = TransactionBuilder::<Testnet2>::chain_inputs(inputs)?;
+        Ok(chunks.iter().map(Vec::len).collect())
+    }
+
+    #[test]
+    fn test_chain_inputs() {
+        assert_eq!(vec![0], chunk_sizes(0).unwrap());
+        assert_eq!(vec![2], chunk_sizes(2).unwrap());
+        assert_eq!(vec![2, 1, 1], chunk_sizes(4).unwrap());
+
+        // Ensure the number of transitions does not exceed the maximum.
+        let max_inputs = Testnet2::NUM_INPUT_RECORDS + (Testnet2::NUM_TRANSITIONS as usize - 1);
+        assert!(chunk_sizes(max_inputs).is_ok());
+        assert!(chunk_sizes(max_inputs + 1).is_err());
+    }
+
+    #[test]
+    fn test_build_chained_transfer() {
+        let rng = &mut thread_rng();
+        let terminator = AtomicBool::new(false);
+        let caller = Account::<Testnet2>::new(rng);
+        let recipient = Account::<Testnet2>::new(rng);
+
+        // Mine three blocks to the caller.
+        let mut ledger = Ledger::<Testnet2>::new().unwrap();
+        let mut records = Vec::new();
+        for _ in 0..3 {
+            ledger
+                .mine_next_block(caller.address(), true, &terminator, rng)
+                .unwrap();
+            let coinbase = ledger.latest_block().unwrap().to_coinbase_transaction().unwrap();
+            records.extend(coinbase.to_decrypted_records(caller.view_key()));
+        }
+        assert_eq!(3, records.len());
+
+        let inputs = records
+            .iter()
+            .map(|record| (record.clone(), ledger.to_ledger_proof(record.commitment()).unwrap()))
+            .collect();
+
+        // Pay more than two records hold, so that all three records are spent over two transitions.
+        let fee = AleoAmount(1);
+        let amount = records[0].value().add(records[1].value());
+        let transaction = TransactionBuilder::new()
+            .add_caller(caller.private_key().clone())
+            .add_inputs(inputs)
+            .add_payment(recipient.address(), amount)
+            .set_fee(fee)
+            .build(rng)
+            .unwrap();
+
+        assert!(transaction.is_valid());
+        assert_eq!(2, transaction.transitions().len());
+        assert_eq!(fee, transaction.value_balance());
+
+        // Ensure the recipient receives the payment.
+        let received: AleoAmount = transaction
+            .to_decrypted_records(recipient.view_key())
+            .iter()
+            .map(Record::value)
+            .sum();
+        assert_eq!(amount, received);
+
+        // Ensure the ledger accepts the transaction.
+        assert!(ledger.add_unconfirmed_transaction(&transaction).is_ok());
+    }
+}
@@ -69,9 +69,14 @@ impl<N: Network> Transitions<N> {
 
     /// Adds all given transitions to the tree, returning the start and ending index in the tree.
     pub(crate) fn add_all(&mut self, transitions: &Vec<Transition<N>>) -> Result<(u8, u8)> {
+        // Ensure the list of given transitions is not empty.
+        if transitions.is_empty() {
+            return Err(anyhow!("The list of given transitions is empty"));
+        }
+
         // Ensure the current index has not reached the maximum number of transitions permitted in software.
         if self.current_index >= N::NUM_TRANSITIONS
-            || self.current_index + transitions.len() as u8 >= N::NUM_TRANSITIONS
+            || self.current_index as usize + transitions.len() >= N::NUM_TRANSITIONS as usize
         {
             return Err(anyhow!("The transitions tree has reached its maximum size"));
         }
@@ -14,17 +14,31 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Address, AleoAmount, ComputeKey, FunctionType, LedgerProof, Network, Operation, PrivateKey, Record};
+use crate::{
+    Address,
+    AleoAmount,
+    ComputeKey,
+    Deployment,
+    FunctionType,
+    LedgerProof,
+    Network,
+    Operation,
+    PrivateKey,
+    Record,
+    VMError,
+};
 use snarkvm_algorithms::SignatureScheme;
-use snarkvm_utilities::{to_bytes_le, FromBytes, ToBytes};
+use snarkvm_utilities::{to_bytes_le, FromBytes, FromBytesDeserializer, ToBytes, ToBytesSerializer};
 
 use anyhow::{anyhow, Result};
 use once_cell::sync::OnceCell;
 use rand::{CryptoRng, Rng};
+use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     collections::HashSet,
     fmt,
     io::{Read, Result as IoResult, Write},
+    str::FromStr,
 };
 
 #[derive(Clone, Debug)]
@@ -80,6 +94,20 @@ impl<N: Network> Request<N> {
         Self::new(caller, records, ledger_proofs, operation, fee, is_public, rng)
     }
 
+    /// Returns a new instance of a deployment request.
+    pub fn new_deploy<R: Rng + CryptoRng>(
+        caller: &PrivateKey<N>,
+        records: Vec<Record<N>>,
+        ledger_proofs: Vec<LedgerProof<N>>,
+        deployment: Deployment<N>,
+        fee: AleoAmount,
+        is_public: bool,
+        rng: &mut R,
+    ) -> Result<Self> {
+        let operation = Operation::Deploy(deployment);
+        Self::new(caller, records, ledger_proofs, operation, fee, is_public, rng)
+    }
+
     /// Returns a new instance of a noop request.
     pub fn new_noop<R: Rng + CryptoRng>(ledger_proofs: Vec<LedgerProof<N>>, rng: &mut R) -> Result<Self> {
         // Sample a burner noop private key.
@@ -195,10 +223,21 @@ impl<N: Network> Request<N> {
 
         // Ensure the records contains a total value that is at least the fee amount.
         if !self.operation.is_coinbase() {
-            let balance: AleoAmount = self.records.iter().map(|record| record.value()).sum();
-            if balance < self.fee {
-                eprintln!("Request records do not contain sufficient value for fee");
-                return false;
+            // The record values are untrusted, so the balance is summed without overflowing.
+            let balance = self
+                .records
+                .iter()
+                .try_fold(0i64, |balance, record| balance.checked_add(record.value().as_i64()));
+            match balance {
+                Some(balance) if balance >= self.fee.as_i64() => (),
+                Some(_) => {
+                    eprintln!("Request records do not contain sufficient value for fee");
+                    return false;
+                }
+                None => {
+                    eprintln!("Request records overflow the balance");
+                    return false;
+                }
             }
         }
 
@@ -206,11 +245,11 @@ impl<N: Network> Request<N> {
         let program_id = {
             let program_id = OnceCell::new();
             for record in &self.records {
-                if record.program_id() != *N::noop_program_id() {
-                    if program_id.set(record.program_id()).is_err() {
-                        eprintln!("Request records contains more than 1 distinct program ID");
-                        return false;
-                    }
+                if record.program_id() != *N::noop_program_id()
+                    && *program_id.get_or_init(|| record.program_id()) != record.program_id()
+                {
+                    eprintln!("Request records contains more than 1 distinct program ID");
+                    return false;
                 }
             }
             *program_id.get_or_init(|| *N::noop_program_id())
@@ -319,10 +358,10 @@ impl<N: Network> Request<N> {
     pub fn to_program_id(&self) -> Result<N::ProgramID> {
         let program_id = OnceCell::new();
         for record in &self.records {
-            if record.program_id() != *N::noop_program_id() {
-                if program_id.set(record.program_id()).is_err() {
-                    return Err(anyhow!("Request records contains more than 1 distinct program ID"));
-                }
+            if record.program_id() != *N::noop_program_id()
+                && *program_id.get_or_init(|| record.program_id()) != record.program_id()
+            {
+                return Err(anyhow!("Request records contains more than 1 distinct program ID"));
             }
         }
         Ok(*program_id.get_or_init(|| *N::noop_program_id()))
@@ -360,8 +399,7 @@ impl<N: Network> FromBytes for Request<N> {
         let signature = FromBytes::read_le(&mut reader)?;
         let is_public = FromBytes::read_le(&mut reader)?;
 
-        Ok(Self::from(records, ledger_proofs, operation, fee, signature, is_public)
-            .expect("Failed to deserialize a request"))
+        Ok(Self::from(records, ledger_proofs, operation, fee, signature, is_public).map_err(VMError::from)?)
     }
 }
 
@@ -377,8 +415,110 @@ impl<N: Network> ToBytes for Request<N> {
     }
 }
 
+impl<N: Network> FromStr for Request<N> {
+    type Err = anyhow::Error;
+
+    fn from_str(request: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(request)?)
+    }
+}
+
 impl<N: Network> fmt::Display for Request<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err::<fmt::Error, _>(serde::ser::Error::custom)?
+        )
+    }
+}
+
+impl<N: Network> Serialize for Request<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => {
+                let mut request = serializer.serialize_struct("Request", 6)?;
+                request.serialize_field("records", &self.records)?;
+                request.serialize_field("ledger_proofs", &self.ledger_proofs)?;
+                request.serialize_field("operation", &self.operation)?;
+                request.serialize_field("fee", &self.fee)?;
+                request.serialize_field("signature", &self.signature)?;
+                request.serialize_field("is_public", &self.is_public)?;
+                request.end()
+            }
+            false => ToBytesSerializer::serialize_with_size_encoding(self, serializer),
+        }
+    }
+}
+
+impl<'de, N: Network> Deserialize<'de> for Request<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => {
+                let request = serde_json::Value::deserialize(deserializer)?;
+                // Recover the request, which ensures the signature is valid.
+                Self::from(
+                    serde_json::from_value(request["records"].clone()).map_err(de::Error::custom)?,
+                    serde_json::from_value(request["ledger_proofs"].clone()).map_err(de::Error::custom)?,
+                    serde_json::from_value(request["operation"].clone()).map_err(de::Error::custom)?,
+                    serde_json::from_value(request["fee"].clone()).map_err(de::Error::custom)?,
+                    serde_json::from_value(request["signature"].clone()).map_err(de::Error::custom)?,
+                    serde_json::from_value(request["is_public"].clone()).map_err(de::Error::custom)?,
+                )
+                .map_err(de::Error::custom)
+            }
+            false => FromBytesDeserializer::<Self>::deserialize_with_size_encoding(deserializer, "request"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testnet2::Testnet2;
+
+    use rand::thread_rng;
+
+    #[test]
+    fn test_request_serde_json() {
+        let rng = &mut thread_rng();
+        let ledger_proofs = vec![LedgerProof::default(); Testnet2::NUM_INPUT_RECORDS];
+        let expected_request = Request::<Testnet2>::new_noop(ledger_proofs, rng).unwrap();
+
+        // Serialize
+        let expected_string = expected_request.to_string();
+        let candidate_string = serde_json::to_string(&expected_request).unwrap();
+        assert_eq!(expected_string, candidate_string);
+
+        // Deserialize
+        let candidate_request = Request::<Testnet2>::from_str(&candidate_string).unwrap();
+        assert_eq!(
+            expected_request.to_bytes_le().unwrap(),
+            candidate_request.to_bytes_le().unwrap()
+        );
+        let candidate_request: Request<Testnet2> = serde_json::from_str(&candidate_string).unwrap();
+        assert_eq!(
+            expected_request.to_bytes_le().unwrap(),
+            candidate_request.to_bytes_le().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_request_bincode() {
+        let rng = &mut thread_rng();
+        let ledger_proofs = vec![LedgerProof::default(); Testnet2::NUM_INPUT_RECORDS];
+        let expected_request = Request::<Testnet2>::new_noop(ledger_proofs, rng).unwrap();
+
+        // Serialize
+        let expected_bytes = expected_request.to_bytes_le().unwrap();
+        let candidate_bytes = bincode::serialize(&expected_request).unwrap();
+        // TODO (howardwu): Serialization - Handle the inconsistency between ToBytes and Serialize (off by a length encoding).
+        assert_eq!(&expected_bytes[..], &candidate_bytes[8..]);
+
+        // Deserialize
+        let candidate_request = Request::<Testnet2>::read_le(&expected_bytes[..]).unwrap();
+        assert_eq!(expected_bytes, candidate_request.to_bytes_le().unwrap());
+        let candidate_request: Request<Testnet2> = bincode::deserialize(&candidate_bytes[..]).unwrap();
+        assert_eq!(expected_bytes, candidate_request.to_bytes_le().unwrap());
     }
 }
@@ -15,12 +15,14 @@
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{AleoAmount, Event, Network, Record};
-use snarkvm_utilities::{FromBytes, ToBytes};
+use snarkvm_utilities::{FromBytes, FromBytesDeserializer, ToBytes, ToBytesSerializer};
 
 use anyhow::Result;
+use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     fmt,
     io::{Read, Result as IoResult, Write},
+    str::FromStr,
 };
 
 // TODO (howardwu): TEMPORARY - Merge this into the Network trait.
@@ -114,20 +116,20 @@ impl<N: Network> FromBytes for Response<N> {
     fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
         let transition_id = FromBytes::read_le(&mut reader)?;
 
-        let mut records = Vec::with_capacity(N::NUM_INPUT_RECORDS);
-        for _ in 0..N::NUM_INPUT_RECORDS {
+        let mut records = Vec::with_capacity(N::NUM_OUTPUT_RECORDS);
+        for _ in 0..N::NUM_OUTPUT_RECORDS {
             records.push(FromBytes::read_le(&mut reader)?);
         }
 
-        let mut encryption_randomness = Vec::with_capacity(N::NUM_INPUT_RECORDS);
-        for _ in 0..N::NUM_INPUT_RECORDS {
+        let mut encryption_randomness = Vec::with_capacity(N::NUM_OUTPUT_RECORDS);
+        for _ in 0..N::NUM_OUTPUT_RECORDS {
             encryption_randomness.push(FromBytes::read_le(&mut reader)?);
         }
 
         let value_balance = FromBytes::read_le(&mut reader)?;
 
         let num_events: u16 = FromBytes::read_le(&mut reader)?;
-        let mut events = Vec::with_capacity(num_events as usize);
+        let mut events = Vec::new();
         for _ in 0..num_events {
             events.push(FromBytes::read_le(&mut reader)?);
         }
@@ -154,8 +156,126 @@ impl<N: Network> ToBytes for Response<N> {
     }
 }
 
+impl<N: Network> FromStr for Response<N> {
+    type Err = anyhow::Error;
+
+    fn from_str(response: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(response)?)
+    }
+}
+
 impl<N: Network> fmt::Display for Response<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err::<fmt::Error, _>(serde::ser::Error::custom)?
+        )
+    }
+}
+
+impl<N: Network> Serialize for Response<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => {
+                let encryption_randomness = self
+                    .encryption_randomness
+                    .iter()
+                    .map(|randomness| Ok(hex::encode(randomness.to_bytes_le()?)))
+                    .collect::<Result<Vec<_>>>()
+                    .map_err(serde::ser::Error::custom)?;
+
+                let mut response = serializer.serialize_struct("Response", 5)?;
+                response.serialize_field("transition_id", &self.transition_id)?;
+                response.serialize_field("records", &self.records)?;
+                response.serialize_field("encryption_randomness", &encryption_randomness)?;
+                response.serialize_field("value_balance", &self.value_balance)?;
+                response.serialize_field("events", &self.events)?;
+                response.end()
+            }
+            false => ToBytesSerializer::serialize_with_size_encoding(self, serializer),
+        }
+    }
+}
+
+impl<'de, N: Network> Deserialize<'de> for Response<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => {
+                let response = serde_json::Value::deserialize(deserializer)?;
+                let encryption_randomness: Vec<String> =
+                    serde_json::from_value(response["encryption_randomness"].clone()).map_err(de::Error::custom)?;
+                let encryption_randomness = encryption_randomness
+                    .iter()
+                    .map(|randomness| Ok(FromBytes::read_le(&hex::decode(randomness)?[..])?))
+                    .collect::<Result<Vec<_>>>()
+                    .map_err(de::Error::custom)?;
+
+                // Recover the response.
+                Self::new(
+                    serde_json::from_value(response["transition_id"].clone()).map_err(de::Error::custom)?,
+                    serde_json::from_value(response["records"].clone()).map_err(de::Error::custom)?,
+                    encryption_randomness,
+                    serde_json::from_value(response["value_balance"].clone()).map_err(de::Error::custom)?,
+                    serde_json::from_value(response["events"].clone()).map_err(de::Error::custom)?,
+                )
+                .map_err(de::Error::custom)
+            }
+            false => FromBytesDeserializer::<Self>::deserialize_with_size_encoding(deserializer, "response"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testnet2::Testnet2, LedgerProof, Request, ResponseBuilder};
+
+    use rand::thread_rng;
+
+    fn sample_response() -> Response<Testnet2> {
+        let rng = &mut thread_rng();
+        let ledger_proofs = vec![LedgerProof::default(); Testnet2::NUM_INPUT_RECORDS];
+        let request = Request::new_noop(ledger_proofs, rng).unwrap();
+        ResponseBuilder::new().add_request(request).build(rng).unwrap()
+    }
+
+    #[test]
+    fn test_response_serde_json() {
+        let expected_response = sample_response();
+
+        // Serialize
+        let expected_string = expected_response.to_string();
+        let candidate_string = serde_json::to_string(&expected_response).unwrap();
+        assert_eq!(expected_string, candidate_string);
+
+        // Deserialize
+        let candidate_response = Response::<Testnet2>::from_str(&candidate_string).unwrap();
+        assert_eq!(
+            expected_response.to_bytes_le().unwrap(),
+            candidate_response.to_bytes_le().unwrap()
+        );
+        let candidate_response: Response<Testnet2> = serde_json::from_str(&candidate_string).unwrap();
+        assert_eq!(
+            expected_response.to_bytes_le().unwrap(),
+            candidate_response.to_bytes_le().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_response_bincode() {
+        let expected_response = sample_response();
+
+        // Serialize
+        let expected_bytes = expected_response.to_bytes_le().unwrap();
+        let candidate_bytes = bincode::serialize(&expected_response).unwrap();
+        // TODO (howardwu): Serialization - Handle the inconsistency between ToBytes and Serialize (off by a length encoding).
+        assert_eq!(&expected_bytes[..], &candidate_bytes[8..]);
+
+        // Deserialize
+        let candidate_response = Response::<Testnet2>::read_le(&expected_bytes[..]).unwrap();
+        assert_eq!(expected_bytes, candidate_response.to_bytes_le().unwrap());
+        let candidate_response: Response<Testnet2> = bincode::deserialize(&candidate_bytes[..]).unwrap();
+        assert_eq!(expected_bytes, candidate_response.to_bytes_le().unwrap());
     }
 }
@@ -112,25 +112,15 @@ impl<N: Network> Transition<N> {
         ledger_root: N::LedgerRoot,
         local_transitions_root: N::TransactionID,
     ) -> bool {
-        // Ensure the number of events is less than `N::NUM_EVENTS`.
-        if self.events.len() > N::NUM_EVENTS as usize {
-            eprintln!("Transition contains an invalid number of events");
+        // Returns `false` if the transition is malformed.
+        if !self.is_well_formed() {
             return false;
         }
 
         // Returns `false` if the transition proof is invalid.
         match N::OuterSNARK::verify(
             N::outer_verifying_key(),
-            &OuterPublicVariables::new(
-                InnerPublicVariables::new(
-                    self.transition_id,
-                    self.value_balance,
-                    ledger_root,
-                    local_transitions_root,
-                    None,
-                ),
-                &inner_circuit_id,
-            ),
+            &self.to_outer_public_variables(inner_circuit_id, ledger_root, local_transitions_root),
             &self.proof,
         ) {
             Ok(is_valid) => match is_valid {
@@ -147,6 +137,57 @@ impl<N: Network> Transition<N> {
         }
     }
 
+    /// Returns `true` if the transition is well-formed, without verifying the transition proof.
+    #[inline]
+    pub fn is_well_formed(&self) -> bool {
+        // Ensure the number of events is less than `N::NUM_EVENTS`.
+        if self.events.len() > N::NUM_EVENTS as usize {
+            eprintln!("Transition contains an invalid number of events");
+            return false;
+        }
+
+        // Ensure the first output record is revealed, as it carries the program ID of the transition.
+        if let Err(error) = self.to_program_id() {
+            eprintln!("Transition contains an invalid first output record: {}", error);
+            return false;
+        }
+
+        // Ensure the deployments match the revealed deployment records, which bind them to the transition ID.
+        let program_ids = match Deployment::<N>::to_program_ids(self.to_records()) {
+            Ok(program_ids) => program_ids,
+            Err(error) => {
+                eprintln!("Transition contains an invalid deployment record: {}", error);
+                return false;
+            }
+        };
+        if !self.deployments().map(Deployment::program_id).eq(program_ids) {
+            eprintln!("Transition deployments do not match its deployment records");
+            return false;
+        }
+
+        true
+    }
+
+    /// Returns the public variables of the transition proof.
+    #[inline]
+    pub(crate) fn to_outer_public_variables(
+        &self,
+        inner_circuit_id: N::InnerCircuitID,
+        ledger_root: N::LedgerRoot,
+        local_transitions_root: N::TransactionID,
+    ) -> OuterPublicVariables<N> {
+        OuterPublicVariables::new(
+            InnerPublicVariables::new(
+                self.transition_id,
+                self.value_balance,
+                ledger_root,
+                local_transitions_root,
+                None,
+            ),
+            &inner_circuit_id,
+        )
+    }
+
     /// Returns `true` if the given serial number exists.
     pub fn contains_serial_number(&self, serial_number: &N::SerialNumber) -> bool {
         self.serial_numbers.contains(serial_number)
@@ -193,12 +234,36 @@ impl<N: Network> Transition<N> {
         self.events.iter()
     }
 
+    /// Returns a reference to the program deployments emitted by this transition.
+    #[inline]
+    pub fn deployments(&self) -> impl Iterator<Item = &Deployment<N>> + fmt::Debug + '_ {
+        self.events.iter().filter_map(|event| match event {
+            Event::Operation(Operation::Deploy(deployment)) => Some(deployment),
+            _ => None,
+        })
+    }
+
     /// Returns a reference to the transition proof.
     #[inline]
     pub fn proof(&self) -> &N::OuterProof {
         &self.proof
     }
 
+    /// Returns the program ID of the transition, from its revealed first output record.
+    #[inline]
+    pub fn to_program_id(&self) -> Result<N::ProgramID> {
+        let record_view_key = self.events.iter().find_map(|event| match event {
+            Event::RecordViewKey(0, record_view_key) => Some(record_view_key),
+            _ => None,
+        });
+        match (record_view_key, self.ciphertexts.first()) {
+            (Some(record_view_key), Some(ciphertext)) => {
+                Ok(Record::<N>::from_record_view_key(record_view_key.clone(), ciphertext)?.program_id())
+            }
+            _ => Err(anyhow!("Transition does not reveal its first output record")),
+        }
+    }
+
     /// Returns the decrypted records using record view key events, if they exist.
     #[inline]
     pub fn to_records(&self) -> impl Iterator<Item = Record<N>> + fmt::Debug + '_ {
@@ -304,7 +369,7 @@ impl<N: Network> FromBytes for Transition<N> {
         let value_balance: AleoAmount = FromBytes::read_le(&mut reader)?;
 
         let num_events: u16 = FromBytes::read_le(&mut reader)?;
-        let mut events = Vec::with_capacity(num_events as usize);
+        let mut events = Vec::new();
         for _ in 0..num_events {
             events.push(FromBytes::read_le(&mut reader)?);
         }
@@ -313,7 +378,7 @@ impl<N: Network> FromBytes for Transition<N> {
 
         Ok(
             Self::from(transition_id, serial_numbers, ciphertexts, value_balance, events, proof)
-                .expect("Failed to deserialize a transition from bytes"),
+                .map_err(VMError::from)?,
         )
     }
 }
@@ -26,6 +26,8 @@ pub struct ResponseBuilder<N: Network> {
     request: OnceCell<Request<N>>,
     /// A list of expected outputs for a state transition.
     outputs: Vec<Output<N>>,
+    /// The indices of the outputs whose record view keys are always revealed.
+    public_outputs: Vec<usize>,
     /// A publicly-visible field encoding events from the state transition.
     events: Vec<Event<N>>,
     /// A list of errors accumulated from calling the builder.
@@ -40,6 +42,7 @@ impl<N: Network> ResponseBuilder<N> {
         Self {
             request: OnceCell::new(),
             outputs: Vec::with_capacity(N::NUM_OUTPUT_RECORDS),
+            public_outputs: Vec::new(),
             events: Vec::new(),
             errors: Vec::new(),
         }
@@ -74,6 +77,14 @@ impl<N: Network> ResponseBuilder<N> {
         self
     }
 
+    ///
+    /// Adds the given output into the builder, revealing its record view key in the events.
+    ///
+    pub fn add_public_output(mut self, output: Output<N>) -> Self {
+        self.public_outputs.push(self.outputs.len());
+        self.add_output(output)
+    }
+
     ///
     /// Adds the given outputs into the builder.
     ///
@@ -140,8 +151,15 @@ impl<N: Network> ResponseBuilder<N> {
                 let (record, encryption_randomness) = output.to_record(rng)?;
 
                 // Add the record view key event if the output record is public.
-                if request.is_public() && events.len() < N::NUM_EVENTS as usize {
+                // The first output record is always public, as it carries the program ID of the transition.
+                let is_public_output = i == 0 || self.public_outputs.contains(&i);
+                if (request.is_public() || is_public_output) && events.len() < N::NUM_EVENTS as usize {
                     events.push(Event::RecordViewKey(i as u8, record.record_view_key().clone()))
+                } else if is_public_output {
+                    return Err(anyhow!(
+                        "Builder exceeded maximum number of events to reveal output {}",
+                        i
+                    ));
                 }
 
                 Ok((record, encryption_randomness))
@@ -0,0 +1,388 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+use crate::{Address, AleoAmount, Network, Output, Payload, PrivateKey, Program, Record, VMError};
+use snarkvm_algorithms::merkle_tree::{MerklePath, MerkleTree};
+use snarkvm_utilities::{error, has_duplicates, FromBytes, FromBytesDeserializer, ToBytes, ToBytesSerializer};
+
+use anyhow::{anyhow, Result};
+use rand::{CryptoRng, Rng};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{Read, Result as IoResult, Write},
+    str::FromStr,
+    sync::Arc,
+};
+
+/// A deployment of a program, publishing the verifying keys of its functions on the ledger.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "N: Network"),
+    Debug(bound = "N: Network"),
+    PartialEq(bound = "N: Network"),
+    Eq(bound = "N: Network")
+)]
+pub struct Deployment<N: Network> {
+    /// The ID of the deployed program.
+    program_id: N::ProgramID,
+    /// The function IDs, in the order of the program tree.
+    function_ids: Vec<N::FunctionID>,
+    /// The verifying keys of the functions, in the order of the program tree.
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    verifying_keys: Vec<N::ProgramVerifyingKey>,
+}
+
+impl<N: Network> Deployment<N> {
+    /// The prefix of the payload of a deployment record.
+    const RECORD_TAG: &[u8] = b"deployment";
+
+    /// Initializes a new deployment of the given program, with the verifying keys of its functions.
+    pub fn new(program: &Program<N>, verifying_keys: Vec<N::ProgramVerifyingKey>) -> Result<Self> {
+        let mut verifying_keys = verifying_keys
+            .into_iter()
+            .map(|verifying_key| Ok((N::function_id(&verifying_key)?, verifying_key)))
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        // Order the verifying keys by the index of their function in the program tree.
+        let ordered_verifying_keys = (0..program.num_functions())
+            .map(|index| {
+                let function_id = match program.find_function_by_index(index as u8) {
+                    Some(function) => function.function_id(),
+                    None => {
+                        return Err(anyhow!(
+                            "Missing function {} in program {}",
+                            index,
+                            program.program_id()
+                        ))
+                    }
+                };
+                match verifying_keys.remove(&function_id) {
+                    Some(verifying_key) => Ok(verifying_key),
+                    None => Err(anyhow!("Missing the verifying key for function {}", function_id)),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Ensure there are no verifying keys for functions outside of the program.
+        if !verifying_keys.is_empty() {
+            return Err(anyhow!(
+                "Found {} verifying keys for functions outside of program {}",
+                verifying_keys.len(),
+                program.program_id()
+            ));
+        }
+
+        let deployment = Self::from(ordered_verifying_keys)?;
+        match deployment.program_id == program.program_id() {
+            true => Ok(deployment),
+            false => Err(anyhow!(
+                "The deployment does not match program {}",
+                program.program_id()
+            )),
+        }
+    }
+
+    /// Initializes a new deployment from the verifying keys of the functions, in the order of the program tree.
+    pub fn from(verifying_keys: Vec<N::ProgramVerifyingKey>) -> Result<Self> {
+        // Ensure the number of functions is within the bounds of a program.
+        if verifying_keys.is_empty() || verifying_keys.len() > u8::MAX as usize {
+            return Err(anyhow!(
+                "A deployment requires between 1 and {} verifying keys, found {}",
+                u8::MAX,
+                verifying_keys.len()
+            ));
+        }
+
+        // Ensure the function IDs are unique.
+        let function_ids = verifying_keys.iter().map(N::function_id).collect::<Result<Vec<_>>>()?;
+        if has_duplicates(function_ids.iter()) {
+            return Err(anyhow!("A deployment must not contain duplicate verifying keys"));
+        }
+
+        // Compute the program ID.
+        let tree =
+            MerkleTree::<N::ProgramIDParameters>::new(Arc::new(N::program_id_parameters().clone()), &function_ids)?;
+
+        Ok(Self {
+            program_id: (*tree.root()).into(),
+            function_ids,
+            verifying_keys,
+        })
+    }
+
+    /// Returns the program ID.
+    pub fn program_id(&self) -> N::ProgramID {
+        self.program_id
+    }
+
+    /// Returns the function IDs, in the order of the program tree.
+    pub fn function_ids(&self) -> &[N::FunctionID] {
+        &self.function_ids
+    }
+
+    /// Returns the verifying keys, in the order of the program tree.
+    pub fn verifying_keys(&self) -> &[N::ProgramVerifyingKey] {
+        &self.verifying_keys
+    }
+
+    /// Returns `true` if the given function ID exists in the deployment.
+    pub fn contains_function(&self, function_id: &N::FunctionID) -> bool {
+        self.function_ids.contains(function_id)
+    }
+
+    /// Returns the verifying key for the given function ID.
+    pub fn get_verifying_key(&self, function_id: &N::FunctionID) -> Result<&N::ProgramVerifyingKey> {
+        match self.function_ids.iter().position(|id| id == function_id) {
+            Some(index) => Ok(&self.verifying_keys[index]),
+            None => Err(anyhow!(
+                "Function {} is not deployed in program {}",
+                function_id,
+                self.program_id
+            )),
+        }
+    }
+
+    /// Returns `true` if the given program path proves the given function is a member of the deployed program.
+    pub fn verify_program_path(
+        &self,
+        program_path: &MerklePath<N::ProgramIDParameters>,
+        function_id: &N::FunctionID,
+    ) -> Result<bool> {
+        Ok(self.contains_function(function_id) && program_path.verify(&self.program_id, function_id)?)
+    }
+
+    /// Returns the output of the unspendable deployment record, which binds this deployment to its transition ID.
+    pub fn to_output<R: Rng + CryptoRng>(&self, rng: &mut R) -> Result<Output<N>> {
+        let burner = Address::from_private_key(&PrivateKey::new(rng));
+        Output::new(burner, AleoAmount::ZERO, Self::to_payload(&self.program_id)?, None)
+    }
+
+    /// Returns the program IDs of the deployment records in the given revealed records.
+    pub fn to_program_ids(records: impl Iterator<Item = Record<N>>) -> Result<Vec<N::ProgramID>> {
+        records
+            .filter(Self::is_deployment_record)
+            .map(|record| -> Result<N::ProgramID> {
+                let program_id: N::ProgramID =
+                    FromBytes::read_le(&record.payload().to_bytes_le()?[Self::RECORD_TAG.len()..])?;
+                match Self::to_payload(&program_id)? == *record.payload() {
+                    true => Ok(program_id),
+                    false => Err(anyhow!("Invalid payload in the deployment record")),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the payload of the deployment record for the given program ID.
+    fn to_payload(program_id: &N::ProgramID) -> Result<Payload<N>> {
+        Ok(Payload::from(&[Self::RECORD_TAG, &program_id.to_bytes_le()?].concat()))
+    }
+
+    /// Returns `true` if the given record is a deployment record, regardless of the validity of its payload.
+    fn is_deployment_record(record: &Record<N>) -> bool {
+        record.value().is_zero()
+            && record.program_id() == *N::noop_program_id()
+            && record
+                .payload()
+                .to_bytes_le()
+                .map_or(false, |payload| payload.starts_with(Self::RECORD_TAG))
+    }
+}
+
+impl<N: Network> FromBytes for Deployment<N> {
+    #[inline]
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let program_id: N::ProgramID = FromBytes::read_le(&mut reader)?;
+        let num_verifying_keys: u8 = FromBytes::read_le(&mut reader)?;
+        let mut verifying_keys = Vec::new();
+        for _ in 0..num_verifying_keys {
+            verifying_keys.push(FromBytes::read_le(&mut reader)?);
+        }
+
+        // Ensure the verifying keys match the program ID.
+        let deployment = Self::from(verifying_keys).map_err(VMError::from)?;
+        match deployment.program_id == program_id {
+            true => Ok(deployment),
+            false => Err(error("Incorrect program ID during deserialization")),
+        }
+    }
+}
+
+impl<N: Network> ToBytes for Deployment<N> {
+    #[inline]
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.program_id.write_le(&mut writer)?;
+        (self.verifying_keys.len() as u8).write_le(&mut writer)?;
+        self.verifying_keys.write_le(&mut writer)
+    }
+}
+
+impl<N: Network> FromStr for Deployment<N> {
+    type Err = anyhow::Error;
+
+    fn from_str(deployment_hex: &str) -> Result<Self, Self::Err> {
+        Ok(Self::read_le(&hex::decode(deployment_hex)?[..])?)
+    }
+}
+
+impl<N: Network> fmt::Display for Deployment<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bytes = self.to_bytes_le().map_err(|_| fmt::Error)?;
+        write!(f, "{}", hex::encode(bytes))
+    }
+}
+
+impl<N: Network> Serialize for Deployment<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => serializer.collect_str(self),
+            false => ToBytesSerializer::serialize_with_size_encoding(self, serializer),
+        }
+    }
+}
+
+impl<'de, N: Network> Deserialize<'de> for Deployment<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => FromStr::from_str(&String::deserialize(deserializer)?).map_err(de::Error::custom),
+            false => FromBytesDeserializer::<Self>::deserialize_with_size_encoding(deserializer, "deployment"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testnet1::Testnet1, virtual_machine::test_functions::factors_function, Noop, Operation};
+
+    /// Returns a program with the factors and noop functions, and its deployment.
+    fn sample_deployment() -> (Program<Testnet1>, Deployment<Testnet1>) {
+        let (function, verifying_key) = factors_function::<Testnet1>();
+        let program = Program::new(vec![function, Arc::new(Noop::<Testnet1>::new())]).unwrap();
+
+        // The verifying keys are given out of order.
+        let verifying_keys = vec![Testnet1::noop_circuit_verifying_key().clone(), verifying_key];
+        let deployment = Deployment::new(&program, verifying_keys).unwrap();
+        (program, deployment)
+    }
+
+    #[test]
+    fn test_new() {
+        let (program, deployment) = sample_deployment();
+        assert_eq!(program.program_id(), deployment.program_id());
+        assert_eq!(2, deployment.function_ids().len());
+        assert_eq!(Testnet1::noop_function_id(), &deployment.function_ids()[1]);
+
+        for function_id in deployment.function_ids() {
+            let verifying_key = deployment.get_verifying_key(function_id).unwrap();
+            assert_eq!(*function_id, Testnet1::function_id(verifying_key).unwrap());
+
+            // The program path of each function leads to the deployed program ID.
+            let program_path = program.to_program_path(function_id).unwrap();
+            assert!(deployment.verify_program_path(&program_path, function_id).unwrap());
+        }
+
+        // The program path of the noop program does not lead to the deployed program ID.
+        let noop_function_id = Testnet1::noop_function_id();
+        let noop_program_path = Testnet1::noop_program_path();
+        assert!(!deployment
+            .verify_program_path(noop_program_path, noop_function_id)
+            .unwrap());
+
+        // The deployment of the verifying keys in program order matches.
+        assert_eq!(
+            deployment,
+            Deployment::from(deployment.verifying_keys().to_vec()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_new_with_invalid_verifying_keys() {
+        let (function, verifying_key) = factors_function::<Testnet1>();
+        let noop_verifying_key = Testnet1::noop_circuit_verifying_key().clone();
+        let program = Program::new(vec![function]).unwrap();
+
+        assert!(Deployment::new(&program, vec![]).is_err());
+        assert!(Deployment::new(&program, vec![noop_verifying_key.clone()]).is_err());
+        assert!(Deployment::new(&program, vec![verifying_key.clone(), noop_verifying_key]).is_err());
+        assert!(Deployment::<Testnet1>::from(vec![verifying_key.clone(), verifying_key]).is_err());
+    }
+
+    #[test]
+    fn test_deployment_bytes() {
+        let (_, expected_deployment) = sample_deployment();
+
+        let expected_bytes = expected_deployment.to_bytes_le().unwrap();
+        assert_eq!(expected_deployment, Deployment::read_le(&expected_bytes[..]).unwrap());
+        assert_eq!(
+            expected_deployment,
+            bincode::deserialize(&bincode::serialize(&expected_deployment).unwrap()).unwrap()
+        );
+
+        // A deployment with an incorrect program ID is rejected.
+        let mut candidate_bytes = expected_bytes.clone();
+        candidate_bytes[0] ^= 1;
+        assert!(Deployment::<Testnet1>::read_le(&candidate_bytes[..]).is_err());
+    }
+
+    #[test]
+    fn test_deployment_record() {
+        let rng = &mut rand::thread_rng();
+        let (_, deployment) = sample_deployment();
+        let program_id = deployment.program_id();
+
+        // Ensure the program ID is recovered from the deployment record.
+        let (record, _) = deployment.to_output(rng).unwrap().to_record(rng).unwrap();
+        assert!(!record.is_dummy());
+        assert_eq!(
+            vec![program_id],
+            Deployment::to_program_ids(vec![record].into_iter()).unwrap()
+        );
+
+        // Ensure a record without the deployment tag is not a deployment record.
+        let payload = Payload::<Testnet1>::from(&program_id.to_bytes_le().unwrap());
+        let address = Address::from_private_key(&PrivateKey::new(rng));
+        let (record, _) = Output::new(address, AleoAmount::ZERO, payload, None)
+            .unwrap()
+            .to_record(rng)
+            .unwrap();
+        assert!(Deployment::to_program_ids(vec![record].into_iter()).unwrap().is_empty());
+
+        // Ensure a deployment record with an invalid payload is rejected.
+        let payload = Payload::from(&[Deployment::<Testnet1>::RECORD_TAG, &[1u8; 64]].concat());
+        let (record, _) = Output::new(address, AleoAmount::ZERO, payload, None)
+            .unwrap()
+            .to_record(rng)
+            .unwrap();
+        assert!(Deployment::to_program_ids(vec![record].into_iter()).is_err());
+    }
+
+    #[test]
+    fn test_deploy_operation_serde_json() {
+        let (_, deployment) = sample_deployment();
+        let expected_operation = Operation::Deploy(deployment);
+
+        let candidate_string = serde_json::to_string(&expected_operation).unwrap();
+        assert_eq!(expected_operation, serde_json::from_str(&candidate_string).unwrap());
+        assert_eq!(
+            expected_operation,
+            Operation::from_str(&expected_operation.to_string()).unwrap()
+        );
+
+        let candidate_bytes = expected_operation.to_bytes_le().unwrap();
+        assert_eq!(expected_operation, Operation::read_le(&candidate_bytes[..]).unwrap());
+    }
+}
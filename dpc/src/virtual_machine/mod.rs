@@ -20,6 +20,9 @@ pub use amount::*;
 pub mod builder;
 pub use builder::*;
 
+pub mod deployment;
+pub use deployment::*;
+
 pub mod event;
 pub use event::*;
 
@@ -47,5 +50,11 @@ pub use program::*;
 pub mod program_public_variables;
 pub use program_public_variables::*;
 
+pub mod programs;
+pub use programs::*;
+
+#[cfg(test)]
+pub(crate) mod test_functions;
+
 pub mod virtual_machine;
 pub use virtual_machine::*;
@@ -14,9 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Address, AleoAmount, FunctionInputs, FunctionType, Network};
+use crate::{Address, AleoAmount, Deployment, FunctionInputs, FunctionType, Network};
 use snarkvm_fields::{ConstraintFieldError, ToConstraintField};
-use snarkvm_utilities::{FromBytes, FromBytesDeserializer, ToBytes, ToBytesSerializer};
+use snarkvm_utilities::{error, FromBytes, FromBytesDeserializer, ToBytes, ToBytesSerializer};
 
 use anyhow::{anyhow, Result};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
@@ -45,6 +45,8 @@ pub enum Operation<N: Network> {
     Transfer(Caller<N>, Recipient<N>, AleoAmount),
     /// Invokes the given records on the function and inputs.
     Evaluate(N::FunctionID, FunctionType, FunctionInputs<N>),
+    /// Deploys the given program, publishing the verifying keys of its functions.
+    Deploy(Deployment<N>),
 }
 
 impl<N: Network> Operation<N> {
@@ -55,12 +57,13 @@ impl<N: Network> Operation<N> {
             Self::Coinbase(..) => 1,
             Self::Transfer(..) => 2,
             Self::Evaluate(..) => 3,
+            Self::Deploy(..) => 4,
         }
     }
 
     pub fn function_id(&self) -> N::FunctionID {
         match self {
-            Self::Noop | Self::Coinbase(..) | Self::Transfer(..) => *N::noop_function_id(),
+            Self::Noop | Self::Coinbase(..) | Self::Transfer(..) | Self::Deploy(..) => *N::noop_function_id(),
             Self::Evaluate(function_id, _, _) => *function_id,
         }
     }
@@ -69,7 +72,7 @@ impl<N: Network> Operation<N> {
         match self {
             Self::Noop => FunctionType::Noop,
             Self::Coinbase(..) => FunctionType::Insert,
-            Self::Transfer(..) => FunctionType::Full,
+            Self::Transfer(..) | Self::Deploy(..) => FunctionType::Full,
             Self::Evaluate(_, function_type, _) => *function_type,
         }
     }
@@ -108,6 +111,13 @@ impl<N: Network> Operation<N> {
             _ => false,
         }
     }
+
+    pub fn is_deploy(&self) -> bool {
+        match self {
+            Self::Deploy(..) => true,
+            _ => false,
+        }
+    }
 }
 
 impl<N: Network> FromBytes for Operation<N> {
@@ -133,7 +143,8 @@ impl<N: Network> FromBytes for Operation<N> {
                 let function_inputs = FromBytes::read_le(&mut reader)?;
                 Ok(Self::Evaluate(function_id, function_type, function_inputs))
             }
-            _ => unreachable!("Invalid operation during deserialization"),
+            4 => Ok(Self::Deploy(FromBytes::read_le(&mut reader)?)),
+            _ => Err(error("Invalid operation ID during deserialization")),
         }
     }
 }
@@ -158,6 +169,7 @@ impl<N: Network> ToBytes for Operation<N> {
                 function_type.write_le(&mut writer)?;
                 function_inputs.write_le(&mut writer)
             }
+            Self::Deploy(deployment) => deployment.write_le(&mut writer),
         }
     }
 }
@@ -188,7 +200,8 @@ impl<N: Network> FromStr for Operation<N> {
                 let function_inputs = serde_json::from_value(operation["function_inputs"].clone())?;
                 Ok(Self::Evaluate(function_id, function_type, function_inputs))
             }
-            _ => unreachable!(format!("Invalid operation id {}", operation_id)),
+            4 => Ok(Self::Deploy(serde_json::from_value(operation["deployment"].clone())?)),
+            _ => Err(anyhow!("Invalid operation id {}", operation_id)),
         }
     }
 }
@@ -224,6 +237,12 @@ impl<N: Network> fmt::Display for Operation<N> {
                     "function_inputs": function_inputs
                 })
             }
+            Self::Deploy(deployment) => {
+                serde_json::json!({
+                    "id": self.operation_id(),
+                    "deployment": deployment
+                })
+            }
         };
 
         write!(f, "{}", operation)
@@ -73,6 +73,11 @@ impl<N: Network> Program<N> {
         (*self.tree.root()).into()
     }
 
+    /// Returns the number of functions in the program.
+    pub fn num_functions(&self) -> usize {
+        self.functions.len()
+    }
+
     /// Returns `true` if the given function ID exists in the program.
     pub fn contains_function(&self, function_id: &N::FunctionID) -> bool {
         self.functions.get(function_id).is_some()
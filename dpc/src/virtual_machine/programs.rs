@@ -0,0 +1,235 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+use crate::{Deployment, Function, Network, Program};
+use snarkvm_utilities::has_duplicates;
+
+use anyhow::{anyhow, Result};
+use std::{collections::HashMap, sync::Arc};
+
+/// A registry of programs, and the verifying keys of their functions, that the virtual machine may execute.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "N: Network"), Debug(bound = "N: Network"))]
+pub struct Programs<N: Network> {
+    /// The programs, indexed by program ID.
+    programs: HashMap<N::ProgramID, Arc<Program<N>>>,
+    /// The verifying keys of the program functions, indexed by program ID and function ID.
+    #[derivative(Debug = "ignore")]
+    verifying_keys: HashMap<(N::ProgramID, N::FunctionID), N::ProgramVerifyingKey>,
+}
+
+impl<N: Network> Programs<N> {
+    /// Initializes a new registry of programs, containing the noop program.
+    pub fn new() -> Result<Self> {
+        let mut programs = Self {
+            programs: Default::default(),
+            verifying_keys: Default::default(),
+        };
+        programs.add(Program::new_noop()?, vec![N::noop_circuit_verifying_key().clone()])?;
+        Ok(programs)
+    }
+
+    /// Adds the given program and the verifying keys of its functions, returning the program ID.
+    pub fn add(&mut self, program: Program<N>, verifying_keys: Vec<N::ProgramVerifyingKey>) -> Result<N::ProgramID> {
+        // Ensure the program does not already exist.
+        let program_id = program.program_id();
+        if self.contains_program(&program_id) {
+            return Err(anyhow!("Program {} already exists", program_id));
+        }
+
+        // Ensure there is exactly one verifying key for each function in the program.
+        if verifying_keys.len() != program.num_functions() {
+            return Err(anyhow!(
+                "Program {} requires {} verifying keys, found {}",
+                program_id,
+                program.num_functions(),
+                verifying_keys.len()
+            ));
+        }
+
+        let function_ids = verifying_keys.iter().map(N::function_id).collect::<Result<Vec<_>>>()?;
+        if has_duplicates(function_ids.iter()) {
+            return Err(anyhow!("Program {} contains duplicate verifying keys", program_id));
+        }
+        if let Some(function_id) = function_ids.iter().find(|id| !program.contains_function(id)) {
+            return Err(anyhow!(
+                "Function {} does not exist in program {}",
+                function_id,
+                program_id
+            ));
+        }
+
+        self.verifying_keys.extend(
+            function_ids
+                .into_iter()
+                .map(|function_id| (program_id, function_id))
+                .zip(verifying_keys),
+        );
+        self.programs.insert(program_id, Arc::new(program));
+
+        Ok(program_id)
+    }
+
+    ///
+    /// Adds the given program with the verifying keys from its deployment, returning the program ID.
+    ///
+    /// Each deployed function must be a member of the given program, as witnessed by its program path.
+    ///
+    pub fn add_deployed(&mut self, program: Program<N>, deployment: &Deployment<N>) -> Result<N::ProgramID> {
+        for function_id in deployment.function_ids() {
+            let program_path = program.to_program_path(function_id)?;
+            if !deployment.verify_program_path(&program_path, function_id)? {
+                return Err(anyhow!(
+                    "Function {} is not a member of deployed program {}",
+                    function_id,
+                    deployment.program_id()
+                ));
+            }
+        }
+
+        self.add(program, deployment.verifying_keys().to_vec())
+    }
+
+    /// Returns `true` if the given program ID exists.
+    pub fn contains_program(&self, program_id: &N::ProgramID) -> bool {
+        self.programs.contains_key(program_id)
+    }
+
+    /// Returns the program for the given program ID.
+    pub fn get_program(&self, program_id: &N::ProgramID) -> Result<&Program<N>> {
+        match self.programs.get(program_id) {
+            Some(program) => Ok(program),
+            None => Err(anyhow!("Program {} does not exist", program_id)),
+        }
+    }
+
+    /// Returns the function for the given program ID and function ID.
+    pub fn get_function(&self, program_id: &N::ProgramID, function_id: &N::FunctionID) -> Result<Arc<dyn Function<N>>> {
+        self.get_program(program_id)?.to_function(function_id)
+    }
+
+    /// Returns the verifying key of the function for the given program ID and function ID.
+    pub fn get_verifying_key(
+        &self,
+        program_id: &N::ProgramID,
+        function_id: &N::FunctionID,
+    ) -> Result<&N::ProgramVerifyingKey> {
+        match self.verifying_keys.get(&(*program_id, *function_id)) {
+            Some(verifying_key) => Ok(verifying_key),
+            None => Err(anyhow!(
+                "Function {} does not exist in program {}",
+                function_id,
+                program_id
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testnet1::Testnet1, virtual_machine::test_functions::factors_function, FunctionType};
+    use snarkvm_utilities::ToBytes;
+
+    #[test]
+    fn test_new() {
+        let programs = Programs::<Testnet1>::new().unwrap();
+        let program_id = Testnet1::noop_program_id();
+        let function_id = Testnet1::noop_function_id();
+
+        assert!(programs.contains_program(program_id));
+        assert_eq!(
+            *function_id,
+            programs.get_function(program_id, function_id).unwrap().function_id()
+        );
+        assert_eq!(
+            Testnet1::noop_circuit_verifying_key().to_bytes_le().unwrap(),
+            programs
+                .get_verifying_key(program_id, function_id)
+                .unwrap()
+                .to_bytes_le()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_add() {
+        let (function, verifying_key) = factors_function::<Testnet1>();
+        let function_id = function.function_id();
+        let program = Program::new(vec![function.clone()]).unwrap();
+        let expected_program_id = program.program_id();
+
+        let mut programs = Programs::<Testnet1>::new().unwrap();
+        let program_id = programs.add(program, vec![verifying_key.clone()]).unwrap();
+        assert_eq!(expected_program_id, program_id);
+        assert!(programs.contains_program(&program_id));
+
+        let candidate = programs.get_function(&program_id, &function_id).unwrap();
+        assert_eq!(function_id, candidate.function_id());
+        assert_eq!(FunctionType::Full, candidate.function_type());
+
+        // The noop function does not exist in the factors program.
+        assert!(programs
+            .get_function(&program_id, Testnet1::noop_function_id())
+            .is_err());
+        assert!(programs
+            .get_verifying_key(&program_id, Testnet1::noop_function_id())
+            .is_err());
+
+        // The factors program may only be added once.
+        let program = Program::new(vec![function]).unwrap();
+        assert!(programs.add(program, vec![verifying_key]).is_err());
+    }
+
+    #[test]
+    fn test_add_with_invalid_verifying_keys() {
+        let (function, verifying_key) = factors_function::<Testnet1>();
+        let program_id = Program::new(vec![function.clone()]).unwrap().program_id();
+        let noop_verifying_key = Testnet1::noop_circuit_verifying_key().clone();
+
+        // The verifying keys must match the functions of the program.
+        let mut programs = Programs::<Testnet1>::new().unwrap();
+        for verifying_keys in [
+            vec![],
+            vec![noop_verifying_key],
+            vec![verifying_key.clone(), verifying_key.clone()],
+        ] {
+            let program = Program::new(vec![function.clone()]).unwrap();
+            assert!(programs.add(program, verifying_keys).is_err());
+            assert!(!programs.contains_program(&program_id));
+        }
+
+        let program = Program::new(vec![function]).unwrap();
+        programs.add(program, vec![verifying_key]).unwrap();
+        assert!(programs.contains_program(&program_id));
+    }
+
+    #[test]
+    fn test_add_deployed() {
+        let (function, verifying_key) = factors_function::<Testnet1>();
+        let program = Program::new(vec![function.clone()]).unwrap();
+        let deployment = Deployment::new(&program, vec![verifying_key]).unwrap();
+
+        // The noop program is not a witness for the functions of the deployment.
+        let mut programs = Programs::<Testnet1>::new().unwrap();
+        assert!(programs
+            .add_deployed(Program::new_noop().unwrap(), &deployment)
+            .is_err());
+
+        let program_id = programs.add_deployed(program, &deployment).unwrap();
+        assert_eq!(deployment.program_id(), program_id);
+        assert!(programs.get_function(&program_id, &function.function_id()).is_ok());
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Function, FunctionType, Network, ProgramPrivateVariables, ProgramPublicVariables};
+use snarkvm_algorithms::SNARK;
+use snarkvm_fields::{ConstraintFieldError, Field, One};
+use snarkvm_gadgets::prelude::*;
+use snarkvm_r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError, ToConstraintField};
+use snarkvm_utilities::{FromBytes, ToBytes};
+
+use anyhow::{anyhow, Result};
+use rand::thread_rng;
+use std::{
+    io::{Read, Result as IoResult, Write},
+    sync::Arc,
+};
+
+/// The product that the factors function proves a nontrivial factorization of.
+const PRODUCT: u64 = 143;
+
+/// The private factors of `PRODUCT`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Factors(pub(crate) u64, pub(crate) u64);
+
+impl<N: Network> ProgramPrivateVariables<N> for Factors {
+    fn new_blank() -> Result<Self> {
+        Ok(Self::default())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl<F: Field> ToConstraintField<F> for Factors {
+    fn to_field_elements(&self) -> Result<Vec<F>, ConstraintFieldError> {
+        Ok(Vec::new())
+    }
+}
+
+impl FromBytes for Factors {
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        Ok(Self(FromBytes::read_le(&mut reader)?, FromBytes::read_le(&mut reader)?))
+    }
+}
+
+impl ToBytes for Factors {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.0.write_le(&mut writer)?;
+        self.1.write_le(&mut writer)
+    }
+}
+
+/// A circuit proving that neither factor is one, and that their product is `PRODUCT`.
+struct FactorsCircuit<N: Network> {
+    public: ProgramPublicVariables<N>,
+    factors: Factors,
+}
+
+impl<N: Network> ConstraintSynthesizer<N::InnerScalarField> for FactorsCircuit<N> {
+    fn generate_constraints<CS: ConstraintSystem<N::InnerScalarField>>(
+        &self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        // Allocate the program public variables, in the layout of the noop circuit.
+        let _position = UInt8::alloc_input_vec_le(cs.ns(|| "Alloc position"), &[0u8])?;
+        let _transition_id = <N::TransitionIDCRHGadget as CRHGadget<_, _>>::OutputGadget::alloc_input(
+            cs.ns(|| "Alloc the transition ID"),
+            || Ok(self.public.transition_id),
+        )?;
+
+        // Enforce a * b = PRODUCT.
+        let values = [self.factors.0, self.factors.1];
+        let a = cs.alloc(|| "a", || Ok(N::InnerScalarField::from(values[0])))?;
+        let b = cs.alloc(|| "b", || Ok(N::InnerScalarField::from(values[1])))?;
+        cs.enforce(
+            || "a * b = product",
+            |lc| lc + a,
+            |lc| lc + b,
+            |lc| lc + (N::InnerScalarField::from(PRODUCT), CS::one()),
+        );
+
+        // Enforce that neither factor is one, by witnessing the inverse of (factor - 1).
+        for (i, (factor, value)) in [a, b].iter().zip(values.iter()).enumerate() {
+            let inverse = cs.alloc(
+                || format!("inverse {}", i),
+                || {
+                    (N::InnerScalarField::from(*value) - N::InnerScalarField::one())
+                        .inverse()
+                        .ok_or(SynthesisError::DivisionByZero)
+                },
+            )?;
+            cs.enforce(
+                || format!("(factor {} - 1) * inverse = 1", i),
+                |lc| lc + *factor - CS::one(),
+                |lc| lc + inverse,
+                |lc| lc + CS::one(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// A function proving knowledge of a nontrivial factorization of `PRODUCT`.
+pub(crate) struct FactorsFunction<N: Network> {
+    function_id: N::FunctionID,
+    proving_key: N::ProgramProvingKey,
+    verifying_key: N::ProgramVerifyingKey,
+}
+
+impl<N: Network> FactorsFunction<N> {
+    /// Runs the circuit-specific setup of the factors function.
+    pub(crate) fn setup() -> Result<Self> {
+        let (proving_key, verifying_key) = <N::ProgramSNARK as SNARK>::setup(
+            &FactorsCircuit::<N> {
+                public: Default::default(),
+                factors: Default::default(),
+            },
+            &mut *N::program_srs(&mut thread_rng()).borrow_mut(),
+        )?;
+
+        Ok(Self {
+            function_id: N::function_id(&verifying_key)?,
+            proving_key,
+            verifying_key,
+        })
+    }
+
+    pub(crate) fn verifying_key(&self) -> &N::ProgramVerifyingKey {
+        &self.verifying_key
+    }
+}
+
+impl<N: Network> Function<N> for FactorsFunction<N> {
+    fn function_id(&self) -> N::FunctionID {
+        self.function_id
+    }
+
+    fn function_type(&self) -> FunctionType {
+        FunctionType::Full
+    }
+
+    fn execute(
+        &self,
+        public: ProgramPublicVariables<N>,
+        private: &dyn ProgramPrivateVariables<N>,
+    ) -> Result<N::ProgramProof> {
+        let factors = private
+            .as_any()
+            .downcast_ref::<Factors>()
+            .ok_or_else(|| anyhow!("Invalid private variables for the factors function"))?;
+
+        let circuit = FactorsCircuit {
+            public,
+            factors: factors.clone(),
+        };
+        Ok(<N::ProgramSNARK as SNARK>::prove(&self.proving_key, &circuit, &mut thread_rng())?.into())
+    }
+
+    fn verify(&self, public: &ProgramPublicVariables<N>, proof: &N::ProgramProof) -> bool {
+        <N::ProgramSNARK as SNARK>::verify(&self.verifying_key, public, proof).unwrap_or(false)
+    }
+
+    fn synthesize<CS: ConstraintSystem<N::InnerScalarField>>(
+        &self,
+        cs: &mut CS,
+        public: &ProgramPublicVariables<N>,
+    ) -> Result<(), SynthesisError> {
+        FactorsCircuit::<N> {
+            public: *public,
+            factors: Default::default(),
+        }
+        .generate_constraints(cs)
+    }
+}
+
+/// Returns the factors function, and its verifying key.
+pub(crate) fn factors_function<N: Network>() -> (Arc<dyn Function<N>>, N::ProgramVerifyingKey) {
+    let function = FactorsFunction::<N>::setup().unwrap();
+    let verifying_key = function.verifying_key().clone();
+    (Arc::new(function), verifying_key)
+}
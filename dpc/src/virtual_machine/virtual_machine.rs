@@ -26,6 +26,8 @@ use std::sync::Arc;
 pub struct VirtualMachine<N: Network> {
     /// The root of the ledger tree used to prove inclusion of ledger-consumed records.
     ledger_root: N::LedgerRoot,
+    /// The programs that may be evaluated by the virtual machine.
+    programs: Arc<Programs<N>>,
     /// The local transitions tree.
     local_transitions: Transitions<N>,
     /// The current list of transitions.
@@ -35,8 +37,14 @@ pub struct VirtualMachine<N: Network> {
 impl<N: Network> VirtualMachine<N> {
     /// Initializes a new instance of the virtual machine, with the given request.
     pub fn new(ledger_root: N::LedgerRoot) -> Result<Self> {
+        Self::new_with_programs(ledger_root, Arc::new(Programs::new()?))
+    }
+
+    /// Initializes a new instance of the virtual machine, with the given registry of programs.
+    pub fn new_with_programs(ledger_root: N::LedgerRoot, programs: Arc<Programs<N>>) -> Result<Self> {
         Ok(Self {
             ledger_root,
+            programs,
             local_transitions: Transitions::new()?,
             transitions: Default::default(),
         })
@@ -53,7 +61,44 @@ impl<N: Network> VirtualMachine<N> {
     }
 
     /// Executes the request, returning a transaction.
-    pub fn execute<R: Rng + CryptoRng>(mut self, request: &Request<N>, rng: &mut R) -> Result<(Self, Response<N>)> {
+    pub fn execute<R: Rng + CryptoRng>(self, request: &Request<N>, rng: &mut R) -> Result<(Self, Response<N>)> {
+        self.execute_with_private_variables(request, &NoopPrivateVariables::<N>::new_blank()?, rng)
+    }
+
+    /// Executes the request, proving the requested function with the given private variables.
+    pub fn execute_with_private_variables<R: Rng + CryptoRng>(
+        mut self,
+        request: &Request<N>,
+        private_variables: &dyn ProgramPrivateVariables<N>,
+        rng: &mut R,
+    ) -> Result<(Self, Response<N>)> {
+        let (transition, response) = self.prove_with_private_variables(request, private_variables, rng)?;
+
+        // Update the state of the virtual machine.
+        self.local_transitions.add(&transition)?;
+        self.transitions.push(transition);
+
+        Ok((self, response))
+    }
+
+    ///
+    /// Proves the signed request against the current state of the virtual machine,
+    /// returning the transition and its response, without updating the virtual machine.
+    ///
+    /// The request carries the signature of the caller, so the prover does not
+    /// require the private key of the caller, and may run on a separate machine.
+    ///
+    pub fn prove<R: Rng + CryptoRng>(&self, request: &Request<N>, rng: &mut R) -> Result<(Transition<N>, Response<N>)> {
+        self.prove_with_private_variables(request, &NoopPrivateVariables::<N>::new_blank()?, rng)
+    }
+
+    /// Proves the signed request, proving the requested function with the given private variables.
+    pub fn prove_with_private_variables<R: Rng + CryptoRng>(
+        &self,
+        request: &Request<N>,
+        private_variables: &dyn ProgramPrivateVariables<N>,
+        rng: &mut R,
+    ) -> Result<(Transition<N>, Response<N>)> {
         // Ensure the request is valid.
         if !request.is_valid() {
             return Err(anyhow!("Virtual machine received an invalid request"));
@@ -65,49 +110,119 @@ impl<N: Network> VirtualMachine<N> {
             Operation::Noop => Self::noop(request, rng)?,
             Operation::Coinbase(recipient, amount) => Self::coinbase(request, recipient, amount, rng)?,
             Operation::Transfer(caller, recipient, amount) => Self::transfer(request, caller, recipient, amount, rng)?,
-            Operation::Evaluate(function_id, function_type, function_inputs) => self.evaluate(
+            Operation::Evaluate(function_id, function_type, function_inputs) => {
+                let function = self.programs.get_function(&request.to_program_id()?, &function_id)?;
+                Self::evaluate(
+                    request,
+                    request.to_program_id()?,
+                    &*function,
+                    &function_type,
+                    &function_inputs,
+                    vec![], // custom_events
+                    rng,
+                )?
+            }
+            Operation::Deploy(..) => Self::deploy(request, rng)?,
+        };
+
+        // Compute the execution of the requested function.
+        let program_id = request.to_program_id()?;
+        let execution = self.to_execution(
+            &program_id,
+            &request.function_id(),
+            response.transition_id(),
+            private_variables,
+        )?;
+
+        let transition = self.to_transition(request, &response, execution, rng)?;
+
+        Ok((transition, response))
+    }
+
+    ///
+    /// Executes the request of a particular program execution, with the given function,
+    /// its program path, and its verifying key, which need not be registered in the virtual machine.
+    ///
+    #[allow(clippy::too_many_arguments)]
+    pub fn execute_program<R: Rng + CryptoRng>(
+        mut self,
+        request: &Request<N>,
+        program_id: N::ProgramID,
+        function: &Arc<dyn Function<N>>,
+        function_path: &MerklePath<N::ProgramIDParameters>,
+        function_verifying_key: N::ProgramVerifyingKey,
+        private_variables: &dyn ProgramPrivateVariables<N>,
+        custom_events: Vec<Vec<u8>>,
+        rng: &mut R,
+    ) -> Result<(Self, Response<N>)> {
+        // Ensure the request is valid.
+        if !request.is_valid() {
+            return Err(anyhow!("Virtual machine received an invalid request"));
+        }
+
+        // Compute the operation.
+        let response = match request.operation() {
+            Operation::Evaluate(_, function_type, function_inputs) => Self::evaluate(
                 request,
-                request.to_program_id()?,
-                &function_id,
-                &function_type,
-                &function_inputs,
-                vec![], // custom_events
+                program_id,
+                &**function,
+                function_type,
+                function_inputs,
+                custom_events,
                 rng,
             )?,
+            _ => return Err(anyhow!("Invalid Operation")),
         };
 
-        let program_id = request.to_program_id()?;
-        let transition_id = response.transition_id();
-        let value_balance = response.value_balance();
+        // Compute the program proof, and verify it against the given verifying key and program path.
+        let function_id = function.function_id();
+        let public = ProgramPublicVariables::new(response.transition_id());
+        let proof = function.execute(public, private_variables)?;
+
+        Self::verify_program_proof(&function_verifying_key, &program_id, &function_id, &public, &proof)?;
+        Self::verify_program_path(function_path, &program_id, &function_id)?;
 
-        // Compute the noop execution, for now.
         let execution = Execution {
-            program_id: *N::noop_program_id(),
-            program_path: N::noop_program_path().clone(),
-            verifying_key: N::noop_circuit_verifying_key().clone(),
-            proof: Noop::<N>::new().execute(
-                ProgramPublicVariables::new(transition_id),
-                &NoopPrivateVariables::<N>::new_blank()?,
-            )?,
+            program_id,
+            program_path: function_path.clone(),
+            verifying_key: function_verifying_key,
+            proof,
         };
+        let transition = self.to_transition(request, &response, execution, rng)?;
 
+        // Update the state of the virtual machine.
+        self.local_transitions.add(&transition)?;
+        self.transitions.push(transition);
+
+        Ok((self, response))
+    }
+
+    /// Finalizes the virtual machine state and returns a transaction.
+    pub fn finalize(&self) -> Result<Transaction<N>> {
+        Transaction::from(*N::inner_circuit_id(), self.ledger_root, self.transitions.clone())
+    }
+
+    /// Proves the inner and outer circuits of the given response and execution, returning the transition.
+    fn to_transition<R: Rng + CryptoRng>(
+        &self,
+        request: &Request<N>,
+        response: &Response<N>,
+        execution: Execution<N>,
+        rng: &mut R,
+    ) -> Result<Transition<N>> {
         // Compute the inner circuit proof, and verify that the inner proof passes.
         let inner_public = InnerPublicVariables::new(
-            transition_id,
-            value_balance,
+            response.transition_id(),
+            response.value_balance(),
             self.ledger_root,
             self.local_transitions.root(),
-            Some(program_id),
+            Some(execution.program_id),
         );
-        let inner_private = InnerPrivateVariables::new(request, &response)?;
+        let inner_private = InnerPrivateVariables::new(request, response)?;
         let inner_circuit = InnerCircuit::<N>::new(inner_public, inner_private);
         let inner_proof = N::InnerSNARK::prove(N::inner_proving_key(), &inner_circuit, rng)?;
 
-        assert!(N::InnerSNARK::verify(
-            N::inner_verifying_key(),
-            &inner_public,
-            &inner_proof
-        )?);
+        Self::verify_inner_proof(N::inner_verifying_key(), &inner_public, &inner_proof)?;
 
         // Construct the outer circuit public and private variables.
         let outer_public = OuterPublicVariables::new(inner_public, N::inner_circuit_id());
@@ -115,25 +230,10 @@ impl<N: Network> VirtualMachine<N> {
         let outer_circuit = OuterCircuit::<N>::new(outer_public.clone(), outer_private);
         let outer_proof = N::OuterSNARK::prove(N::outer_proving_key(), &outer_circuit, rng)?;
 
-        assert!(N::OuterSNARK::verify(
-            N::outer_verifying_key(),
-            &outer_public,
-            &outer_proof
-        )?);
+        Self::verify_outer_proof(N::outer_verifying_key(), &outer_public, &outer_proof)?;
 
         // Construct the transition.
-        let transition = Transition::<N>::new(request, &response, outer_proof.into())?;
-
-        // Update the state of the virtual machine.
-        self.local_transitions.add(&transition)?;
-        self.transitions.push(transition);
-
-        Ok((self, response))
-    }
-
-    /// Finalizes the virtual machine state and returns a transaction.
-    pub fn finalize(&self) -> Result<Transaction<N>> {
-        Transaction::from(*N::inner_circuit_id(), self.ledger_root, self.transitions.clone())
+        Transition::<N>::new(request, response, outer_proof.into())
     }
 
     /// Performs a noop transition.
@@ -186,26 +286,71 @@ impl<N: Network> VirtualMachine<N> {
             .build(rng)
     }
 
-    /// Returns a response based on the current state of the virtual machine.
+    /// Deploys the program in the requested deployment, paying the fee from the caller.
+    fn deploy<R: Rng + CryptoRng>(request: &Request<N>, rng: &mut R) -> Result<Response<N>> {
+        let deployment = match request.operation() {
+            Operation::Deploy(deployment) => deployment,
+            _ => return Err(anyhow!("Invalid Operation")),
+        };
+
+        // Ensure the deployment pays a fee, so that programs may not be deployed for free.
+        if !request.fee().is_positive() {
+            return Err(anyhow!("Program {} is deployed without a fee", deployment.program_id()));
+        }
+
+        // Compute the final balance of the caller.
+        let caller_balance = request.to_balance().sub(request.fee());
+        if caller_balance.is_negative() {
+            return Err(VMError::BalanceInsufficient.into());
+        }
+
+        // Add the deployment record, which binds the deployment to the transition ID, and reveal it.
+        let mut response_builder = ResponseBuilder::new()
+            .add_request(request.clone())
+            .add_public_output(deployment.to_output(rng)?);
+
+        // Add the change address if the balance is not zero.
+        if !caller_balance.is_zero() {
+            response_builder = response_builder.add_output(Output::new(
+                request.caller()?,
+                caller_balance,
+                Default::default(),
+                None,
+            )?);
+        }
+
+        // Add the deployment to the response, as the verifying keys must be public for the ledger to record them.
+        response_builder
+            .add_event(Event::Operation(request.operation().clone()))
+            .build(rng)
+    }
+
+    /// Returns a response for evaluating the given function of the given program.
     fn evaluate<R: Rng + CryptoRng>(
-        &self,
         request: &Request<N>,
         program_id: N::ProgramID,
-        function_id: &N::FunctionID,
-        _function_type: &FunctionType,
+        function: &dyn Function<N>,
+        function_type: &FunctionType,
         function_inputs: &FunctionInputs<N>,
         custom_events: Vec<Vec<u8>>,
         rng: &mut R,
     ) -> Result<Response<N>> {
-        // TODO (raychu86): Do function type checks.
-
-        // Check that the function id exists in the program.
-
         // Check that the function id is the same as the request.
-        if function_id != &request.function_id() {
+        let function_id = function.function_id();
+        if function_id != request.function_id() {
             return Err(anyhow!("Invalid function id"));
         }
 
+        // Check that the function is of the given function type.
+        if function.function_type() != *function_type {
+            return Err(anyhow!(
+                "Function {} is of type {:?}, found {:?}",
+                function_id,
+                function.function_type(),
+                function_type
+            ));
+        }
+
         // Fetch the caller.
         if request.caller()? != function_inputs.caller {
             return Err(anyhow!("Caller in instruction does not match request caller"));
@@ -255,93 +400,489 @@ impl<N: Network> VirtualMachine<N> {
         response_builder.build(rng)
     }
 
-    // TODO (raychu86): Temporary solution. Handle execution elsewhere.
-    /// Executes the request of a particular program execution and returns a transaction.
-    pub fn execute_program<R: Rng + CryptoRng>(
-        mut self,
-        request: &Request<N>,
-        program_id: <N as Network>::ProgramID,
-        function: &Arc<dyn Function<N>>,
-        function_path: &MerklePath<<N as Network>::ProgramIDParameters>,
-        function_verifying_key: <<N as Network>::ProgramSNARK as SNARK>::VerifyingKey,
+    /// Returns the execution of the given function, proving it with the given private variables.
+    fn to_execution(
+        &self,
+        program_id: &N::ProgramID,
+        function_id: &N::FunctionID,
+        transition_id: N::TransitionID,
         private_variables: &dyn ProgramPrivateVariables<N>,
-        custom_events: Vec<Vec<u8>>,
-        rng: &mut R,
-    ) -> Result<(Self, Response<N>)> {
-        // Ensure the request is valid.
-        if !request.is_valid() {
-            return Err(anyhow!("Virtual machine received an invalid request"));
+    ) -> Result<Execution<N>> {
+        // Fetch the function, its verifying key, and its membership path in the program.
+        let program = self.programs.get_program(program_id)?;
+        let function = program.to_function(function_id)?;
+        let verifying_key = self.programs.get_verifying_key(program_id, function_id)?;
+        let program_path = program.to_program_path(function_id)?;
+
+        // Compute the program proof, and verify it against the registered verifying key.
+        let public = ProgramPublicVariables::new(transition_id);
+        let proof = function.execute(public, private_variables)?;
+
+        Self::verify_program_proof(verifying_key, program_id, function_id, &public, &proof)?;
+        Self::verify_program_path(&program_path, program_id, function_id)?;
+
+        Ok(Execution {
+            program_id: *program_id,
+            program_path,
+            verifying_key: verifying_key.clone(),
+            proof,
+        })
+    }
+
+    /// Returns an error if the inner proof does not verify under the given verifying key.
+    fn verify_inner_proof(
+        verifying_key: &<N::InnerSNARK as SNARK>::VerifyingKey,
+        inner_public: &InnerPublicVariables<N>,
+        inner_proof: &<N::InnerSNARK as SNARK>::Proof,
+    ) -> Result<(), VirtualMachineError> {
+        match Self::to_rejection_reason(N::InnerSNARK::verify(verifying_key, inner_public, inner_proof)) {
+            None => Ok(()),
+            Some(reason) => Err(VirtualMachineError::InvalidInnerProof {
+                transition_id: inner_public.transition_id().to_string(),
+                reason,
+            }),
         }
+    }
 
-        // Compute the operation.
-        let operation = request.operation().clone();
-        let response = match operation {
-            Operation::Evaluate(function_id, function_type, function_inputs) => self.evaluate(
-                request,
-                program_id,
-                &function_id,
-                &function_type,
-                &function_inputs,
-                custom_events,
-                rng,
-            )?,
-            _ => return Err(anyhow!("Invalid Operation")),
+    /// Returns an error if the program proof does not verify under the given verifying key.
+    fn verify_program_proof(
+        verifying_key: &<N::ProgramSNARK as SNARK>::VerifyingKey,
+        program_id: &N::ProgramID,
+        function_id: &N::FunctionID,
+        program_public: &ProgramPublicVariables<N>,
+        program_proof: &<N::ProgramSNARK as SNARK>::Proof,
+    ) -> Result<(), VirtualMachineError> {
+        match Self::to_rejection_reason(N::ProgramSNARK::verify(verifying_key, program_public, program_proof)) {
+            None => Ok(()),
+            Some(reason) => Err(VirtualMachineError::InvalidProgramProof {
+                transition_id: program_public.transition_id.to_string(),
+                program_id: program_id.to_string(),
+                function_id: function_id.to_string(),
+                reason,
+            }),
+        }
+    }
+
+    /// Returns an error if the given program path does not prove the function is in the program.
+    fn verify_program_path(
+        program_path: &MerklePath<N::ProgramIDParameters>,
+        program_id: &N::ProgramID,
+        function_id: &N::FunctionID,
+    ) -> Result<(), VirtualMachineError> {
+        let reason = match program_path.verify(program_id, function_id) {
+            Ok(true) => return Ok(()),
+            Ok(false) => "the path does not lead to the program ID".to_string(),
+            Err(error) => error.to_string(),
         };
+        Err(VirtualMachineError::InvalidProgramPath {
+            program_id: program_id.to_string(),
+            function_id: function_id.to_string(),
+            reason,
+        })
+    }
 
-        let transition_id = response.transition_id();
-        let value_balance = response.value_balance();
+    /// Returns an error if the outer proof does not verify under the given verifying key.
+    fn verify_outer_proof(
+        verifying_key: &<N::OuterSNARK as SNARK>::VerifyingKey,
+        outer_public: &OuterPublicVariables<N>,
+        outer_proof: &<N::OuterSNARK as SNARK>::Proof,
+    ) -> Result<(), VirtualMachineError> {
+        match Self::to_rejection_reason(N::OuterSNARK::verify(verifying_key, outer_public, outer_proof)) {
+            None => Ok(()),
+            Some(reason) => Err(VirtualMachineError::InvalidOuterProof {
+                transition_id: outer_public.transition_id().to_string(),
+                inner_circuit_id: outer_public.inner_circuit_id().to_string(),
+                reason,
+            }),
+        }
+    }
 
-        // Compute the inner circuit proof, and verify that the inner proof passes.
-        let inner_public = InnerPublicVariables::new(
-            transition_id,
-            value_balance,
-            self.ledger_root,
-            self.local_transitions.root(),
-            Some(program_id),
-        );
-        let inner_private = InnerPrivateVariables::new(request, &response)?;
-        let inner_circuit = InnerCircuit::<N>::new(inner_public, inner_private.clone());
-        let inner_proof = N::InnerSNARK::prove(N::inner_proving_key(), &inner_circuit, rng)?;
+    /// Returns the reason a proof was rejected, or `None` if the proof is valid.
+    fn to_rejection_reason(verification: Result<bool, SNARKError>) -> Option<String> {
+        match verification {
+            Ok(true) => None,
+            Ok(false) => Some("the proof is rejected by the verifying key".to_string()),
+            Err(error) => Some(error.to_string()),
+        }
+    }
+}
 
-        assert!(N::InnerSNARK::verify(
-            N::inner_verifying_key(),
-            &inner_public,
-            &inner_proof
-        )?);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testnet1::Testnet1,
+        testnet2::Testnet2,
+        virtual_machine::test_functions::{factors_function, Factors},
+    };
+
+    use rand::thread_rng;
+    use std::{sync::mpsc, thread};
+
+    #[test]
+    fn test_delegated_proving() {
+        let ledger_root = LedgerTree::<Testnet2>::new().unwrap().root();
+
+        // The signer and prover only share serialized bytes.
+        let (request_sender, request_receiver) = mpsc::channel::<Vec<u8>>();
+        let (transition_sender, transition_receiver) = mpsc::channel::<Vec<u8>>();
+
+        let signer = thread::spawn(move || {
+            let rng = &mut thread_rng();
+            let private_key = PrivateKey::<Testnet2>::new(rng);
+            let recipient = PrivateKey::<Testnet2>::new(rng).to_address();
+
+            // Sign the request, and send it to the prover.
+            let request = Request::new_transfer(
+                &private_key,
+                vec![],
+                vec![LedgerProof::default(); Testnet2::NUM_INPUT_RECORDS],
+                recipient,
+                AleoAmount::ZERO,
+                AleoAmount::ZERO,
+                false,
+                rng,
+            )
+            .unwrap();
+            request_sender.send(bincode::serialize(&request).unwrap()).unwrap();
+
+            // Receive the transition, and assemble the transaction.
+            let transition: Transition<Testnet2> = bincode::deserialize(&transition_receiver.recv().unwrap()).unwrap();
+            assert_eq!(
+                request.to_serial_numbers().unwrap(),
+                transition.serial_numbers().cloned().collect::<Vec<_>>()
+            );
+
+            let transaction = Transaction::from(*Testnet2::inner_circuit_id(), ledger_root, vec![transition]).unwrap();
+            assert!(transaction.is_valid());
+        });
+
+        let prover = thread::spawn(move || {
+            let rng = &mut thread_rng();
+
+            // Prove the request, and send the transition back to the signer.
+            let request: Request<Testnet2> = bincode::deserialize(&request_receiver.recv().unwrap()).unwrap();
+            let (transition, _) = VirtualMachine::new(ledger_root).unwrap().prove(&request, rng).unwrap();
+            transition_sender
+                .send(bincode::serialize(&transition).unwrap())
+                .unwrap();
+        });
+
+        prover.join().unwrap();
+        signer.join().unwrap();
+    }
 
-        // Compute the execution.
-        let proof = function.execute(ProgramPublicVariables::new(transition_id), private_variables)?;
-        let public_variables = ProgramPublicVariables::new(transition_id);
+    #[test]
+    fn test_verify_program_proof_with_corrupted_key() {
+        let transition_id = <Testnet1 as Network>::TransitionID::default();
+        let public = ProgramPublicVariables::new(transition_id);
+        let proof = Noop::<Testnet1>::new()
+            .execute(public, &NoopPrivateVariables::<Testnet1>::new_blank().unwrap())
+            .unwrap();
+
+        let program_id = Testnet1::noop_program_id();
+        let function_id = Testnet1::noop_function_id();
+
+        // The noop proof verifies under the noop verifying key.
+        let verifying_key = Testnet1::noop_circuit_verifying_key();
+        VirtualMachine::<Testnet1>::verify_program_proof(verifying_key, program_id, function_id, &public, &proof)
+            .unwrap();
+
+        // The noop proof is rejected under a corrupted noop verifying key.
+        let mut corrupted_key = verifying_key.clone();
+        corrupted_key.alpha_g1 = -corrupted_key.alpha_g1;
+        match VirtualMachine::<Testnet1>::verify_program_proof(&corrupted_key, program_id, function_id, &public, &proof)
+        {
+            Err(VirtualMachineError::InvalidProgramProof {
+                transition_id: id,
+                program_id: program,
+                function_id: function,
+                ..
+            }) => {
+                assert_eq!(transition_id.to_string(), id);
+                assert_eq!(program_id.to_string(), program);
+                assert_eq!(function_id.to_string(), function);
+            }
+            result => panic!("Expected an invalid program proof, found {:?}", result),
+        }
+    }
 
-        assert!(function.verify(&public_variables, &proof));
-        assert!(function_path.verify(&program_id, &function.function_id())?);
+    #[test]
+    fn test_verify_program_path_with_wrong_program_id() {
+        let program_path = Testnet2::noop_program_path();
+        let function_id = Testnet2::noop_function_id();
+
+        // The noop program path leads to the noop program ID.
+        VirtualMachine::<Testnet2>::verify_program_path(program_path, Testnet2::noop_program_id(), function_id)
+            .unwrap();
+
+        // The noop program path does not lead to any other program ID.
+        let program_id = <Testnet2 as Network>::ProgramID::default();
+        match VirtualMachine::<Testnet2>::verify_program_path(program_path, &program_id, function_id) {
+            Err(VirtualMachineError::InvalidProgramPath {
+                program_id: program, ..
+            }) => {
+                assert_eq!(program_id.to_string(), program)
+            }
+            result => panic!("Expected an invalid program path, found {:?}", result),
+        }
+    }
 
-        let execution = Execution {
-            program_id,
-            program_path: function_path.clone(),
-            verifying_key: function_verifying_key,
-            proof,
-        };
+    #[test]
+    fn test_verify_outer_proof_with_corrupted_key() {
+        let transaction = &Testnet2::genesis_block().transactions()[0];
+        let (outer_public, outer_proof) = transaction.to_transition_proofs().unwrap().remove(0);
+
+        // The genesis outer proof verifies under the outer verifying key.
+        let verifying_key = Testnet2::outer_verifying_key();
+        VirtualMachine::<Testnet2>::verify_outer_proof(verifying_key, &outer_public, outer_proof).unwrap();
+
+        // The genesis outer proof is rejected under a corrupted outer verifying key.
+        let mut corrupted_key = verifying_key.clone();
+        corrupted_key.gamma_abc_g1.reverse();
+        match VirtualMachine::<Testnet2>::verify_outer_proof(&corrupted_key, &outer_public, outer_proof) {
+            Err(VirtualMachineError::InvalidOuterProof {
+                transition_id,
+                inner_circuit_id,
+                ..
+            }) => {
+                assert_eq!(outer_public.transition_id().to_string(), transition_id);
+                assert_eq!(Testnet2::inner_circuit_id().to_string(), inner_circuit_id);
+            }
+            result => panic!("Expected an invalid outer proof, found {:?}", result),
+        }
+    }
 
-        // Construct the outer circuit public and private variables.
-        let outer_public = OuterPublicVariables::new(inner_public, N::inner_circuit_id());
-        let outer_private = OuterPrivateVariables::new(N::inner_verifying_key().clone(), inner_proof.into(), execution);
-        let outer_circuit = OuterCircuit::<N>::new(outer_public.clone(), outer_private.clone());
-        let outer_proof = N::OuterSNARK::prove(N::outer_proving_key(), &outer_circuit, rng)?;
+    #[test]
+    fn test_verify_inner_proof_with_corrupted_key() {
+        let rng = &mut thread_rng();
+        let ledger_root = LedgerTree::<Testnet2>::new().unwrap().root();
+        let local_transitions_root = Transitions::<Testnet2>::new().unwrap().root();
 
-        assert!(N::OuterSNARK::verify(
-            N::outer_verifying_key(),
-            &outer_public,
-            &outer_proof
-        )?);
+        let request =
+            Request::<Testnet2>::new_noop(vec![LedgerProof::default(); Testnet2::NUM_INPUT_RECORDS], rng).unwrap();
+        let response = VirtualMachine::<Testnet2>::noop(&request, rng).unwrap();
 
-        // Construct the transition.
-        let transition = Transition::<N>::new(&request, &response, outer_proof.into())?;
+        let inner_public = InnerPublicVariables::new(
+            response.transition_id(),
+            response.value_balance(),
+            ledger_root,
+            local_transitions_root,
+            Some(request.to_program_id().unwrap()),
+        );
+        let inner_private = InnerPrivateVariables::new(&request, &response).unwrap();
+        let inner_circuit = InnerCircuit::<Testnet2>::new(inner_public, inner_private);
+        let inner_proof =
+            <Testnet2 as Network>::InnerSNARK::prove(Testnet2::inner_proving_key(), &inner_circuit, rng).unwrap();
+
+        // The noop inner proof verifies under the inner verifying key.
+        let verifying_key = Testnet2::inner_verifying_key();
+        VirtualMachine::<Testnet2>::verify_inner_proof(verifying_key, &inner_public, &inner_proof).unwrap();
+
+        // The noop inner proof is rejected under a corrupted inner verifying key.
+        let mut corrupted_key = verifying_key.clone();
+        corrupted_key.gamma_abc_g1.reverse();
+        match VirtualMachine::<Testnet2>::verify_inner_proof(&corrupted_key, &inner_public, &inner_proof) {
+            Err(VirtualMachineError::InvalidInnerProof { transition_id, .. }) => {
+                assert_eq!(response.transition_id().to_string(), transition_id);
+            }
+            result => panic!("Expected an invalid inner proof, found {:?}", result),
+        }
+    }
 
-        // Update the state of the virtual machine.
-        self.local_transitions.add(&transition)?;
-        self.transitions.push(transition);
+    #[test]
+    fn test_to_execution_with_custom_function() {
+        let (function, verifying_key) = factors_function::<Testnet1>();
+        let function_id = function.function_id();
+
+        let mut programs = Programs::<Testnet1>::new().unwrap();
+        let program = Program::new(vec![function.clone()]).unwrap();
+        let program_id = programs.add(program, vec![verifying_key]).unwrap();
+
+        let ledger_root = LedgerTree::<Testnet1>::new().unwrap().root();
+        let vm = VirtualMachine::<Testnet1>::new_with_programs(ledger_root, Arc::new(programs)).unwrap();
+        let transition_id = <Testnet1 as Network>::TransitionID::default();
+
+        // A nontrivial factorization is proven, and verifies under the registered verifying key.
+        let execution = vm
+            .to_execution(&program_id, &function_id, transition_id, &Factors(11, 13))
+            .unwrap();
+        assert_eq!(program_id, execution.program_id);
+        assert!(function.verify(&ProgramPublicVariables::new(transition_id), &execution.proof));
+
+        // An invalid factorization is rejected.
+        let error = vm
+            .to_execution(&program_id, &function_id, transition_id, &Factors(11, 12))
+            .unwrap_err();
+        match error.downcast::<VirtualMachineError>() {
+            Ok(VirtualMachineError::InvalidProgramProof { .. }) => {}
+            result => panic!("Expected an invalid program proof, found {:?}", result),
+        }
 
-        Ok((self, response))
+        // The private variables of another function are rejected.
+        let noop_private_variables = NoopPrivateVariables::<Testnet1>::new_blank().unwrap();
+        assert!(vm
+            .to_execution(&program_id, &function_id, transition_id, &noop_private_variables)
+            .is_err());
+    }
+
+    #[test]
+    fn test_execute_custom_function() {
+        let rng = &mut thread_rng();
+
+        let (function, verifying_key) = factors_function::<Testnet1>();
+        let function_id = function.function_id();
+        let program = Program::new(vec![function.clone()]).unwrap();
+        let program_id = program.program_id();
+        let program_path = program.to_program_path(&function_id).unwrap();
+
+        // Construct a request to evaluate the factors function.
+        let private_key = PrivateKey::<Testnet1>::new(rng);
+        let caller = private_key.to_address();
+        let records = (0..Testnet1::NUM_INPUT_RECORDS)
+            .map(|_| Record::new(caller, AleoAmount::ZERO, Payload::default(), program_id, rng).unwrap())
+            .collect();
+        let function_inputs = FunctionInputs::new(&caller, &caller, AleoAmount::ZERO, Payload::default());
+        let request = Request::new(
+            &private_key,
+            records,
+            vec![LedgerProof::default(); Testnet1::NUM_INPUT_RECORDS],
+            Operation::Evaluate(function_id, FunctionType::Full, function_inputs),
+            AleoAmount::ZERO,
+            false,
+            rng,
+        )
+        .unwrap();
+
+        // The factors function is executed from the registered programs.
+        let mut programs = Programs::<Testnet1>::new().unwrap();
+        programs.add(program, vec![verifying_key.clone()]).unwrap();
+        let ledger_root = LedgerTree::<Testnet1>::new().unwrap().root();
+        let vm = VirtualMachine::<Testnet1>::new_with_programs(ledger_root, Arc::new(programs)).unwrap();
+        let (vm, response) = vm
+            .execute_with_private_variables(&request, &Factors(11, 13), rng)
+            .unwrap();
+        let transaction = vm.finalize().unwrap();
+        assert!(transaction.is_valid());
+        assert_eq!(response.transition_id(), transaction.transitions()[0].transition_id());
+
+        // The factors function is executed from the given function, program path, and verifying key.
+        let vm = VirtualMachine::<Testnet1>::new(ledger_root).unwrap();
+        let (vm, response) = vm
+            .execute_program(
+                &request,
+                program_id,
+                &function,
+                &program_path,
+                verifying_key,
+                &Factors(11, 13),
+                vec![],
+                rng,
+            )
+            .unwrap();
+        let transaction = vm.finalize().unwrap();
+        assert!(transaction.is_valid());
+        assert_eq!(response.transition_id(), transaction.transitions()[0].transition_id());
+    }
+
+    #[test]
+    fn test_execute_program_with_corrupted_key() {
+        let rng = &mut thread_rng();
+
+        let (function, verifying_key) = factors_function::<Testnet1>();
+        let function_id = function.function_id();
+        let program = Program::new(vec![function.clone()]).unwrap();
+        let program_id = program.program_id();
+        let program_path = program.to_program_path(&function_id).unwrap();
+
+        // Construct a request to evaluate the factors function.
+        let private_key = PrivateKey::<Testnet1>::new(rng);
+        let caller = private_key.to_address();
+        let records = (0..Testnet1::NUM_INPUT_RECORDS)
+            .map(|_| Record::new(caller, AleoAmount::ZERO, Payload::default(), program_id, rng).unwrap())
+            .collect();
+        let function_inputs = FunctionInputs::new(&caller, &caller, AleoAmount::ZERO, Payload::default());
+        let request = Request::new(
+            &private_key,
+            records,
+            vec![LedgerProof::default(); Testnet1::NUM_INPUT_RECORDS],
+            Operation::Evaluate(function_id, FunctionType::Full, function_inputs),
+            AleoAmount::ZERO,
+            false,
+            rng,
+        )
+        .unwrap();
+
+        // The factors function is rejected under a corrupted function verifying key.
+        let mut corrupted_key = verifying_key;
+        corrupted_key.alpha_g1 = -corrupted_key.alpha_g1;
+        let ledger_root = LedgerTree::<Testnet1>::new().unwrap().root();
+        let error = VirtualMachine::<Testnet1>::new(ledger_root)
+            .unwrap()
+            .execute_program(
+                &request,
+                program_id,
+                &function,
+                &program_path,
+                corrupted_key,
+                &Factors(11, 13),
+                vec![],
+                rng,
+            )
+            .unwrap_err();
+        match error.downcast::<VirtualMachineError>() {
+            Ok(VirtualMachineError::InvalidProgramProof {
+                program_id: program,
+                function_id: function,
+                ..
+            }) => {
+                assert_eq!(program_id.to_string(), program);
+                assert_eq!(function_id.to_string(), function);
+            }
+            result => panic!("Expected an invalid program proof, found {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_prove_with_unregistered_function() {
+        let rng = &mut thread_rng();
+
+        let (function, verifying_key) = factors_function::<Testnet1>();
+        let function_id = function.function_id();
+        let program = Program::new(vec![function]).unwrap();
+        let program_id = program.program_id();
+
+        // Construct a request to evaluate the factors function.
+        let private_key = PrivateKey::<Testnet1>::new(rng);
+        let caller = private_key.to_address();
+        let record = Record::new(caller, AleoAmount::ZERO, Payload::default(), program_id, rng).unwrap();
+        let function_inputs = FunctionInputs::new(&caller, &caller, AleoAmount::ZERO, Payload::default());
+        let mut new_request = |function_type| {
+            Request::new(
+                &private_key,
+                vec![record.clone()],
+                vec![LedgerProof::default(); Testnet1::NUM_INPUT_RECORDS],
+                Operation::Evaluate(function_id, function_type, function_inputs.clone()),
+                AleoAmount::ZERO,
+                false,
+                rng,
+            )
+            .unwrap()
+        };
+        let request = new_request(FunctionType::Full);
+        let mistyped_request = new_request(FunctionType::Noop);
+
+        // The factors function may not be evaluated before its program is registered.
+        let ledger_root = LedgerTree::<Testnet1>::new().unwrap().root();
+        let vm = VirtualMachine::<Testnet1>::new(ledger_root).unwrap();
+        let error = vm.prove(&request, &mut thread_rng()).unwrap_err();
+        assert!(error.to_string().contains("does not exist"), "{}", error);
+
+        // The factors function may not be evaluated as a function of another type.
+        let mut programs = Programs::<Testnet1>::new().unwrap();
+        programs.add(program, vec![verifying_key]).unwrap();
+        let vm = VirtualMachine::<Testnet1>::new_with_programs(ledger_root, Arc::new(programs)).unwrap();
+        let error = vm.prove(&mistyped_request, &mut thread_rng()).unwrap_err();
+        assert!(error.to_string().contains("is of type"), "{}", error);
     }
 }
@@ -0,0 +1,155 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+use snarkvm_dpc::{
+    testnet2::Testnet2,
+    Block,
+    BlockHeader,
+    LedgerProof,
+    Network,
+    PrivateKey,
+    Record,
+    Request,
+    Transaction,
+};
+use snarkvm_parameters::{testnet2::GenesisBlock, Genesis};
+use snarkvm_utilities::{FromBytes, ToBytes};
+
+use proptest::prelude::*;
+use rand::thread_rng;
+use std::{io::Cursor, str::FromStr};
+
+/// Returns the encoding of the genesis block header, and the genesis transaction.
+/// The encodings are sliced from the genesis block bytes, as decoding the genesis block
+/// requires the PoSW parameters, which may not be available.
+fn genesis_encodings() -> (Vec<u8>, Vec<u8>) {
+    let block_bytes = GenesisBlock::load_bytes();
+    let mut cursor = Cursor::new(&block_bytes[..]);
+    let _block_hash = <Testnet2 as Network>::BlockHash::read_le(&mut cursor).unwrap();
+    let _previous_block_hash = <Testnet2 as Network>::BlockHash::read_le(&mut cursor).unwrap();
+
+    let header_start = cursor.position() as usize;
+    let header_end = header_start + Testnet2::HEADER_SIZE_IN_BYTES;
+
+    // Skip the number of transactions, as the genesis block contains exactly one transaction.
+    let transactions_start = header_end + 2;
+    (
+        block_bytes[header_start..header_end].to_vec(),
+        block_bytes[transactions_start..].to_vec(),
+    )
+}
+
+fn transaction() -> Transaction<Testnet2> {
+    Transaction::read_le(&genesis_encodings().1[..]).unwrap()
+}
+
+fn record() -> Record<Testnet2> {
+    let rng = &mut thread_rng();
+    let owner = PrivateKey::<Testnet2>::new(rng).to_address();
+    Record::new_noop(owner, rng).unwrap()
+}
+
+fn request() -> Request<Testnet2> {
+    let ledger_proofs = vec![LedgerProof::default(); Testnet2::NUM_INPUT_RECORDS];
+    Request::new_noop(ledger_proofs, &mut thread_rng()).unwrap()
+}
+
+/// Returns a strategy that overwrites a few bytes of the given encoding, and may truncate it.
+fn mutations(bytes: Vec<u8>) -> impl Strategy<Value = Vec<u8>> {
+    let length = bytes.len();
+    (
+        prop::collection::vec((0..length, any::<u8>()), 1..4),
+        prop::option::of(0..length),
+    )
+        .prop_map(move |(edits, truncation)| {
+            let mut bytes = bytes.clone();
+            for (index, byte) in edits {
+                bytes[index] = byte;
+            }
+            if let Some(length) = truncation {
+                bytes.truncate(length);
+            }
+            bytes
+        })
+}
+
+/// Decodes the given bytes, and their string interpretation, as an object of type `T`.
+/// The decoded result is ignored, as the decoders are only required to not panic.
+fn decode<T: FromBytes + FromStr>(bytes: &[u8]) {
+    let _ = T::read_le(bytes);
+    let _ = T::from_str(&String::from_utf8_lossy(bytes));
+}
+
+#[test]
+fn test_decode_transaction_without_transitions() {
+    let transaction = transaction();
+    let mut bytes = transaction.inner_circuit_id().to_bytes_le().unwrap();
+    bytes.extend(transaction.ledger_root().to_bytes_le().unwrap());
+    bytes.extend(0u16.to_le_bytes());
+
+    assert!(Transaction::<Testnet2>::read_le(&bytes[..]).is_err());
+}
+
+proptest! {
+    #[test]
+    fn test_decode_arbitrary_bytes(bytes in prop::collection::vec(any::<u8>(), 0..4096)) {
+        decode::<Block<Testnet2>>(&bytes);
+        decode::<BlockHeader<Testnet2>>(&bytes);
+        decode::<Transaction<Testnet2>>(&bytes);
+        decode::<Record<Testnet2>>(&bytes);
+        decode::<Request<Testnet2>>(&bytes);
+    }
+
+    #[test]
+    fn test_decode_mutated_block(
+        bytes in mutations(GenesisBlock::load_bytes()),
+        string in mutations(Testnet2::genesis_block().to_string().into_bytes()),
+    ) {
+        decode::<Block<Testnet2>>(&bytes);
+        decode::<Block<Testnet2>>(&string);
+    }
+
+    #[test]
+    fn test_decode_mutated_block_header(bytes in mutations(genesis_encodings().0)) {
+        decode::<BlockHeader<Testnet2>>(&bytes);
+    }
+
+    #[test]
+    fn test_decode_mutated_transaction(
+        bytes in mutations(genesis_encodings().1),
+        string in mutations(transaction().to_string().into_bytes()),
+    ) {
+        decode::<Transaction<Testnet2>>(&bytes);
+        decode::<Transaction<Testnet2>>(&string);
+    }
+
+    #[test]
+    fn test_decode_mutated_record(
+        bytes in mutations(record().to_bytes_le().unwrap()),
+        string in mutations(record().to_string().into_bytes()),
+    ) {
+        decode::<Record<Testnet2>>(&bytes);
+        decode::<Record<Testnet2>>(&string);
+    }
+
+    #[test]
+    fn test_decode_mutated_request(
+        bytes in mutations(request().to_bytes_le().unwrap()),
+        string in mutations(request().to_string().into_bytes()),
+    ) {
+        decode::<Request<Testnet2>>(&bytes);
+        decode::<Request<Testnet2>>(&string);
+    }
+}
@@ -43,6 +43,7 @@ use std::{
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
     str::FromStr,
 };
+use zeroize::Zeroize;
 
 pub trait Fp256Parameters: FieldParameters<BigInteger = BigInteger> {}
 
@@ -430,6 +431,13 @@ impl<P: Fp256Parameters> FromBytes for Fp256<P> {
     }
 }
 
+impl<P: Fp256Parameters> Zeroize for Fp256<P> {
+    #[inline]
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
 /// `Fp` elements are ordered lexicographically.
 impl<P: Fp256Parameters> Ord for Fp256<P> {
     #[inline(always)]
@@ -43,6 +43,7 @@ use std::{
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
     str::FromStr,
 };
+use zeroize::Zeroize;
 
 pub trait Fp384Parameters: FieldParameters<BigInteger = BigInteger> {}
 
@@ -505,6 +506,13 @@ impl<P: Fp384Parameters> FromBytes for Fp384<P> {
     }
 }
 
+impl<P: Fp384Parameters> Zeroize for Fp384<P> {
+    #[inline]
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
 impl<P: Fp384Parameters> FromStr for Fp384<P> {
     type Err = FieldError;
 
@@ -43,6 +43,7 @@ use std::{
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
     str::FromStr,
 };
+use zeroize::Zeroize;
 
 pub trait Fp768Parameters: FieldParameters<BigInteger = BigInteger> {}
 
@@ -840,6 +841,13 @@ impl<P: Fp768Parameters> FromBytes for Fp768<P> {
     }
 }
 
+impl<P: Fp768Parameters> Zeroize for Fp768<P> {
+    #[inline]
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
 impl<P: Fp768Parameters> FromStr for Fp768<P> {
     type Err = FieldError;
 
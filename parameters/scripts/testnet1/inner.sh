@@ -2,7 +2,7 @@
 # and `.checksum` files to `params` folder under the `src` directory.
 # If the parameter size has changed, you will need to manually update these in each corresponding struct.
 
-RUST_BACKTRACE=1 cargo run --release --example setup inner testnet1 || exit
+RUST_BACKTRACE=1 cargo run --release --manifest-path ../../../Cargo.toml --features cli -- setup inner --network testnet1 || exit
 
 mv inner.metadata ../../src/testnet1/resources
 mv inner.proving* ~/.aleo/resources
@@ -2,7 +2,7 @@
 # and `.checksum` files to `params` folder under the `src` directory.
 # If the parameter size has changed, you will need to manually update these in each corresponding struct.
 
-RUST_BACKTRACE=1 cargo run --release --example setup outer testnet1 || exit
+RUST_BACKTRACE=1 cargo run --release --manifest-path ../../../Cargo.toml --features cli -- setup outer --network testnet1 || exit
 
 mv outer.metadata ../../src/testnet1/resources
 mv outer.proving* ~/.aleo/resources
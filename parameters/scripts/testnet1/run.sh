@@ -5,4 +5,7 @@
 
 ./posw.sh || exit
 
+# Ensures the generated files match their metadata.
+cargo run --release --manifest-path ../../../Cargo.toml --features cli -- setup verify --network testnet1 --directory ../../src/testnet1/resources --remote-directory ~/.aleo/resources || exit
+
 ./genesis.sh || exit
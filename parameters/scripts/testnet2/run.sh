@@ -6,4 +6,7 @@
 
 ./posw.sh || exit
 
+# Ensures the generated files match their metadata.
+cargo run --release --manifest-path ../../../Cargo.toml --features cli -- setup verify --network testnet2 --directory ../../src/testnet2/resources --remote-directory ~/.aleo/resources || exit
+
 ./genesis.sh || exit
@@ -2,7 +2,7 @@
 # and `.checksum` files to `params` folder under the `src` directory.
 # If the parameter size has changed, you will need to manually update these in each corresponding struct.
 
-cargo run --release --example setup universal testnet2 || exit
+cargo run --release --manifest-path ../../../Cargo.toml --features cli -- setup universal --network testnet2 || exit
 
 mv universal.metadata ../../src/testnet2/resources
 mv universal.srs* ~/.aleo/resources
@@ -27,6 +27,13 @@ pub enum ParameterError {
     #[error("{}", _0)]
     Message(String),
 
+    #[error(
+        "\"{}\" does not exist in {:?}, and remote fetch is disabled in offline mode",
+        _0,
+        _1
+    )]
+    MissingOffline(String, std::path::PathBuf),
+
     #[error("Remote fetch is disabled, enable compiler flag for feature")]
     RemoteFetchDisabled,
 
@@ -29,6 +29,9 @@ pub mod macros;
 pub mod errors;
 pub use errors::*;
 
+pub mod remote;
+pub use remote::*;
+
 pub mod testnet1;
 
 pub mod testnet2;
@@ -37,5 +40,5 @@ pub mod traits;
 pub use traits::*;
 
 pub mod prelude {
-    pub use crate::{errors::*, traits::*};
+    pub use crate::{errors::*, remote::*, traits::*};
 }
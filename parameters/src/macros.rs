@@ -87,6 +87,10 @@ macro_rules! impl_remote {
 
         impl $name {
             pub fn load_bytes() -> Result<Vec<u8>, crate::errors::ParameterError> {
+                Self::load_bytes_with(&crate::remote::RemoteConfig::from_env())
+            }
+
+            pub fn load_bytes_with(config: &crate::remote::RemoteConfig) -> Result<Vec<u8>, crate::errors::ParameterError> {
                 const METADATA: &'static str = include_str!(concat!($local_dir, $fname, ".metadata"));
 
                 let metadata: serde_json::Value = serde_json::from_str(METADATA).expect("Metadata was not well-formatted");
@@ -100,13 +104,13 @@ macro_rules! impl_remote {
                 };
 
                 // Compose the correct file path for the parameter file.
-                let mut file_path = aleo_std::aleo_dir();
-                file_path.push($local_dir);
-                file_path.push(&filename);
+                let file_path = config.file_path($local_dir, &filename);
 
                 let buffer = if file_path.exists() {
                     // Attempts to load the parameter file locally with an absolute path.
                     std::fs::read(file_path)?
+                } else if config.is_offline() {
+                    return Err(crate::errors::ParameterError::MissingOffline(filename, file_path));
                 } else {
                     // Downloads the missing parameters and stores it in the local directory for use.
                     eprintln!(
@@ -122,7 +126,7 @@ macro_rules! impl_remote {
 
 
                             let mut buffer = vec![];
-                            Self::remote_fetch(&mut buffer, &format!("{}/{}", $remote_url, filename))?;
+                            Self::remote_fetch(&mut buffer, &config.url($remote_url, &filename))?;
 
                             #[cfg(not(feature = "no_std_out"))]
                             println!("\n{} - Download complete", module_path!());
@@ -145,7 +149,7 @@ macro_rules! impl_remote {
                             }
                         } else if #[cfg(feature = "wasm")] {
                             let buffer = alloc::sync::Arc::new(parking_lot::RwLock::new(vec![]));
-                            let url = String::from($remote_url);
+                            let url = config.url($remote_url, &filename);
 
                             // NOTE(julesdesmit): I'm leaking memory here so that I can get a
                             // static reference to the url, which is needed to pass it into
@@ -263,6 +267,7 @@ macro_rules! impl_remote {
             #[cfg(test)]
             #[test]
             fn [< test_ $fname _ $ftype >]() {
+                let _env = crate::remote::lock_env();
                 assert!($name::load_bytes().is_ok());
             }
         }
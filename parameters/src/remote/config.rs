@@ -0,0 +1,320 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::path::{Path, PathBuf};
+
+/// The environment variable that overrides the directory of the remote parameter files.
+pub const PARAMETERS_DIRECTORY_ENV: &str = "SNARKVM_PARAMETERS_DIR";
+/// The environment variable that overrides the base URL of the remote parameter files.
+pub const PARAMETERS_REMOTE_URL_ENV: &str = "SNARKVM_PARAMETERS_URL";
+/// The environment variable that disables fetching missing remote parameter files, when set to `1` or `true`.
+pub const PARAMETERS_OFFLINE_ENV: &str = "SNARKVM_PARAMETERS_OFFLINE";
+
+/// The configuration used to load the remote parameter files.
+/// By default, files are stored in the Aleo directory, and missing files are fetched from the network's remote URL.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RemoteConfig {
+    /// The directory of the parameter files, overriding the resources directory in the Aleo directory.
+    directory: Option<PathBuf>,
+    /// The base URL of the parameter files, overriding the remote URL of the network.
+    remote_url: Option<String>,
+    /// If `true`, missing parameter files are not fetched from the remote URL.
+    offline: bool,
+}
+
+impl RemoteConfig {
+    ///
+    /// Initializes a new instance of `RemoteConfig` from the environment variables.
+    ///
+    pub fn from_env() -> Self {
+        let var = |key| std::env::var(key).ok().filter(|value: &String| !value.is_empty());
+
+        Self {
+            directory: var(PARAMETERS_DIRECTORY_ENV).map(PathBuf::from),
+            remote_url: var(PARAMETERS_REMOTE_URL_ENV),
+            offline: matches!(var(PARAMETERS_OFFLINE_ENV).as_deref(), Some("1") | Some("true")),
+        }
+    }
+
+    ///
+    /// Sets the directory of the parameter files.
+    ///
+    pub fn with_directory<P: AsRef<Path>>(mut self, directory: P) -> Self {
+        self.directory = Some(directory.as_ref().to_path_buf());
+        self
+    }
+
+    ///
+    /// Sets the base URL of the parameter files.
+    ///
+    pub fn with_remote_url(mut self, remote_url: &str) -> Self {
+        self.remote_url = Some(remote_url.to_string());
+        self
+    }
+
+    ///
+    /// Sets whether missing parameter files are fetched from the remote URL.
+    ///
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Returns `true` if missing parameter files are not fetched from the remote URL.
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Returns the path of the given parameter file, in the given local directory of the Aleo directory by default.
+    pub fn file_path(&self, local_dir: &str, filename: &str) -> PathBuf {
+        let mut file_path = match &self.directory {
+            Some(directory) => directory.clone(),
+            None => aleo_std::aleo_dir().join(local_dir),
+        };
+        file_path.push(filename);
+        file_path
+    }
+
+    /// Returns the URL of the given parameter file, under the given remote URL by default.
+    pub fn url(&self, remote_url: &str, filename: &str) -> String {
+        let remote_url = self.remote_url.as_deref().unwrap_or(remote_url);
+        format!("{}/{}", remote_url.trim_end_matches('/'), filename)
+    }
+}
+
+/// Locks the parameter environment variables, so tests that read or write them do not race.
+#[cfg(test)]
+pub(crate) fn lock_env() -> std::sync::MutexGuard<'static, ()> {
+    static ENV_LOCK: once_cell::sync::Lazy<std::sync::Mutex<()>> = once_cell::sync::Lazy::new(Default::default);
+    ENV_LOCK.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ParameterError;
+
+    use std::{env, fs, panic};
+
+    const REMOTE_URL: &str = "https://s3-us-west-1.amazonaws.com/aleo.parameters";
+    const RESOURCES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/resources/");
+    const TEST_FILENAME: &str = "test.bytes.ec2af72";
+
+    // The test file is stored in the local directory, so it is found without a directory override.
+    impl_remote!(
+        TestBytes,
+        REMOTE_URL,
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/resources/"),
+        "test",
+        "bytes"
+    );
+
+    fn test_bytes() -> Vec<u8> {
+        fs::read(Path::new(RESOURCES_DIR).join(TEST_FILENAME)).unwrap()
+    }
+
+    /// Runs the given function with the given parameter environment variables set, or unset if `None`,
+    /// and restores their previous values afterwards.
+    fn with_env<T>(vars: &[(&str, Option<&str>)], function: impl FnOnce() -> T) -> T {
+        let _env = lock_env();
+
+        let previous: Vec<_> = vars.iter().map(|(key, _)| (*key, env::var_os(key))).collect();
+        for (key, value) in vars {
+            match value {
+                Some(value) => env::set_var(key, value),
+                None => env::remove_var(key),
+            }
+        }
+
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(function));
+
+        for (key, value) in previous {
+            match value {
+                Some(value) => env::set_var(key, value),
+                None => env::remove_var(key),
+            }
+        }
+
+        result.unwrap_or_else(|error| panic::resume_unwind(error))
+    }
+
+    #[test]
+    fn test_remote_config() {
+        let config = RemoteConfig::default();
+        assert!(!config.is_offline());
+        assert_eq!(
+            config.file_path("./resources/", TEST_FILENAME),
+            aleo_std::aleo_dir().join("./resources/").join(TEST_FILENAME)
+        );
+        assert_eq!(
+            config.url(REMOTE_URL, TEST_FILENAME),
+            format!("{}/{}", REMOTE_URL, TEST_FILENAME)
+        );
+
+        let config = config
+            .with_directory("/parameters")
+            .with_remote_url("http://localhost:4000/")
+            .with_offline(true);
+        assert!(config.is_offline());
+        assert_eq!(
+            config.file_path("./resources/", TEST_FILENAME),
+            Path::new("/parameters").join(TEST_FILENAME)
+        );
+        assert_eq!(
+            config.url(REMOTE_URL, TEST_FILENAME),
+            format!("http://localhost:4000/{}", TEST_FILENAME)
+        );
+    }
+
+    #[test]
+    fn test_remote_config_from_env() {
+        let unset = [
+            (PARAMETERS_DIRECTORY_ENV, None),
+            (PARAMETERS_REMOTE_URL_ENV, None),
+            (PARAMETERS_OFFLINE_ENV, None),
+        ];
+        assert_eq!(with_env(&unset, RemoteConfig::from_env), RemoteConfig::default());
+
+        let set = [
+            (PARAMETERS_DIRECTORY_ENV, Some("/parameters")),
+            (PARAMETERS_REMOTE_URL_ENV, Some("http://localhost:4000")),
+            (PARAMETERS_OFFLINE_ENV, Some("true")),
+        ];
+        let expected = RemoteConfig::default()
+            .with_directory("/parameters")
+            .with_remote_url("http://localhost:4000")
+            .with_offline(true);
+        assert_eq!(with_env(&set, RemoteConfig::from_env), expected);
+
+        // Ensure empty variables are treated as unset.
+        let empty = [
+            (PARAMETERS_DIRECTORY_ENV, Some("")),
+            (PARAMETERS_REMOTE_URL_ENV, Some("")),
+            (PARAMETERS_OFFLINE_ENV, Some("")),
+        ];
+        assert_eq!(with_env(&empty, RemoteConfig::from_env), RemoteConfig::default());
+    }
+
+    #[test]
+    fn test_remote_config_from_env_offline() {
+        let values = [
+            ("1", true),
+            ("true", true),
+            ("0", false),
+            ("false", false),
+            ("yes", false),
+        ];
+        for (value, is_offline) in values.iter() {
+            let config = with_env(&[(PARAMETERS_OFFLINE_ENV, Some(value))], RemoteConfig::from_env);
+            assert_eq!(config.is_offline(), *is_offline, "Offline mode for {:?}", value);
+        }
+    }
+
+    #[test]
+    fn test_load_bytes_from_env_directory() {
+        let directory = tempfile::tempdir().unwrap();
+        fs::write(directory.path().join(TEST_FILENAME), test_bytes()).unwrap();
+
+        let vars = [
+            (PARAMETERS_DIRECTORY_ENV, directory.path().to_str()),
+            (PARAMETERS_REMOTE_URL_ENV, None),
+            (PARAMETERS_OFFLINE_ENV, Some("1")),
+        ];
+        assert_eq!(with_env(&vars, TestBytes::load_bytes).unwrap(), test_bytes());
+    }
+
+    #[test]
+    fn test_load_bytes_from_env_offline() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let vars = [
+            (PARAMETERS_DIRECTORY_ENV, directory.path().to_str()),
+            (PARAMETERS_REMOTE_URL_ENV, None),
+            (PARAMETERS_OFFLINE_ENV, Some("1")),
+        ];
+        match with_env(&vars, TestBytes::load_bytes) {
+            Err(ParameterError::MissingOffline(filename, file_path)) => {
+                assert_eq!(filename, TEST_FILENAME);
+                assert_eq!(file_path, directory.path().join(TEST_FILENAME));
+            }
+            result => panic!("Expected a missing file error in offline mode, found {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_load_bytes_from_directory() {
+        let directory = tempfile::tempdir().unwrap();
+        fs::write(directory.path().join(TEST_FILENAME), test_bytes()).unwrap();
+
+        let config = RemoteConfig::default()
+            .with_directory(directory.path())
+            .with_offline(true);
+        assert_eq!(TestBytes::load_bytes_with(&config).unwrap(), test_bytes());
+    }
+
+    #[test]
+    fn test_load_bytes_offline() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let config = RemoteConfig::default()
+            .with_directory(directory.path())
+            .with_offline(true);
+        match TestBytes::load_bytes_with(&config) {
+            Err(ParameterError::MissingOffline(filename, file_path)) => {
+                assert_eq!(filename, TEST_FILENAME);
+                assert_eq!(file_path, directory.path().join(TEST_FILENAME));
+            }
+            result => panic!("Expected a missing file error in offline mode, found {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_load_bytes_from_remote_url() {
+        // A local directory served over `file://` stands in for the remote file server.
+        let server = tempfile::tempdir().unwrap();
+        fs::write(server.path().join(TEST_FILENAME), test_bytes()).unwrap();
+        let directory = tempfile::tempdir().unwrap();
+
+        let config = RemoteConfig::default()
+            .with_directory(directory.path())
+            .with_remote_url(&format!("file://{}", server.path().display()));
+        assert_eq!(TestBytes::load_bytes_with(&config).unwrap(), test_bytes());
+
+        // Ensure the fetched file is stored in the directory.
+        assert_eq!(fs::read(directory.path().join(TEST_FILENAME)).unwrap(), test_bytes());
+    }
+
+    #[test]
+    fn test_load_bytes_from_remote_url_with_mismatched_checksum() {
+        let mut bytes = test_bytes();
+        bytes[0] = !bytes[0];
+
+        let server = tempfile::tempdir().unwrap();
+        fs::write(server.path().join(TEST_FILENAME), bytes).unwrap();
+        let directory = tempfile::tempdir().unwrap();
+
+        let config = RemoteConfig::default()
+            .with_directory(directory.path())
+            .with_remote_url(&format!("file://{}", server.path().display()));
+        assert!(matches!(
+            TestBytes::load_bytes_with(&config),
+            Err(ParameterError::ChecksumMismatch(..))
+        ));
+
+        // Ensure the mismatched file is not stored in the directory.
+        assert!(!directory.path().join(TEST_FILENAME).exists());
+    }
+}
@@ -0,0 +1,564 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    cli::NetworkName,
+    dpc::{
+        testnet1::Testnet1,
+        testnet2::Testnet2,
+        Account,
+        AccountScheme,
+        Address,
+        Mnemonic,
+        Network,
+        PrivateKey,
+        ViewKey,
+    },
+};
+
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use rand::thread_rng;
+use serde_json::Value;
+use std::{
+    io::{self, BufRead},
+    str::FromStr,
+};
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug, Copy, Clone)]
+pub struct AccountOptions {
+    /// The network of the account, either `testnet1` or `testnet2`
+    #[structopt(long, default_value = "testnet2")]
+    pub network: NetworkName,
+
+    /// Prints the output as JSON
+    #[structopt(long)]
+    pub json: bool,
+}
+
+#[derive(StructOpt, Debug)]
+pub enum AccountCommand {
+    /// Generates a new Aleo account
+    New {
+        /// Generates the account from a new mnemonic phrase
+        #[structopt(long)]
+        mnemonic: bool,
+
+        /// The number of words in the mnemonic phrase
+        #[structopt(long, default_value = "24")]
+        words: usize,
+
+        /// Reads a passphrase for the mnemonic phrase from stdin
+        #[structopt(long, requires = "mnemonic")]
+        passphrase: bool,
+
+        #[structopt(flatten)]
+        options: AccountOptions,
+    },
+    /// Imports an Aleo account from a private key read from stdin
+    Import {
+        /// Reads a mnemonic phrase from stdin, instead of a private key
+        #[structopt(long)]
+        mnemonic: bool,
+
+        /// Reads a passphrase for the mnemonic phrase from the next line of stdin
+        #[structopt(long, requires = "mnemonic")]
+        passphrase: bool,
+
+        #[structopt(flatten)]
+        options: AccountOptions,
+    },
+    /// Recovers an Aleo account from a mnemonic phrase read from stdin, as with `import --mnemonic`
+    Recover {
+        /// Reads a passphrase for the mnemonic phrase from the next line of stdin
+        #[structopt(long)]
+        passphrase: bool,
+
+        #[structopt(flatten)]
+        options: AccountOptions,
+    },
+    /// Shows the view key and address of a private key or view key read from stdin
+    Show {
+        #[structopt(flatten)]
+        options: AccountOptions,
+    },
+    /// Signs a message with a private key read from stdin
+    Sign {
+        /// The message to sign
+        #[structopt(long)]
+        message: String,
+
+        #[structopt(flatten)]
+        options: AccountOptions,
+    },
+    /// Verifies the signature of a message for an address
+    Verify {
+        /// The address of the signer
+        #[structopt(long)]
+        address: String,
+
+        /// The message that was signed
+        #[structopt(long)]
+        message: String,
+
+        /// The signature of the message
+        #[structopt(long)]
+        signature: String,
+
+        #[structopt(flatten)]
+        options: AccountOptions,
+    },
+}
+
+impl AccountCommand {
+    pub fn parse(self) -> Result<String> {
+        let stdin = io::stdin();
+        let mut stdin = stdin.lock();
+        self.parse_with(&mut stdin)
+    }
+
+    /// Returns the output of the command, reading any secrets from the given input.
+    fn parse_with<R: BufRead>(self, stdin: &mut R) -> Result<String> {
+        let options = *self.options();
+        let output = match options.network {
+            NetworkName::Testnet1 => self.execute::<Testnet1, _>(stdin)?,
+            NetworkName::Testnet2 => self.execute::<Testnet2, _>(stdin)?,
+        };
+        let is_rejected = output.contains(&("verified", Value::Bool(false)));
+
+        let output = match options.json {
+            true => serde_json::to_string_pretty(
+                &output
+                    .into_iter()
+                    .map(|(key, value)| (key.to_string(), value))
+                    .collect::<serde_json::Map<_, _>>(),
+            )?,
+            false => Self::format_output(output),
+        };
+
+        // A rejected signature is returned as an error, so that the command exits with a non-zero status.
+        match is_rejected {
+            true => Err(anyhow!(
+                "{}\nThe signature is invalid for the given address and message",
+                output
+            )),
+            false => Ok(output),
+        }
+    }
+
+    /// Returns the options of the command.
+    fn options(&self) -> &AccountOptions {
+        match self {
+            Self::New { options, .. }
+            | Self::Import { options, .. }
+            | Self::Recover { options, .. }
+            | Self::Show { options }
+            | Self::Sign { options, .. }
+            | Self::Verify { options, .. } => options,
+        }
+    }
+
+    /// Returns the named outputs of the command on the network `N`.
+    fn execute<N: Network, R: BufRead>(self, stdin: &mut R) -> Result<Vec<(&'static str, Value)>> {
+        match self {
+            Self::New {
+                mnemonic,
+                words,
+                passphrase,
+                ..
+            } => match mnemonic {
+                true => {
+                    let passphrase = match passphrase {
+                        true => Self::read_secrets(stdin, 1)?.remove(0),
+                        false => String::new(),
+                    };
+                    let mnemonic = Mnemonic::new(words, &mut thread_rng())?;
+                    let private_key = PrivateKey::<N>::from_mnemonic(&mnemonic, &passphrase)?;
+
+                    let mut output = vec![("mnemonic", mnemonic.to_string().into())];
+                    output.extend(Self::account_output(private_key.into()));
+                    Ok(output)
+                }
+                false => Ok(Self::account_output(Account::<N>::new(&mut thread_rng()))),
+            },
+            Self::Import {
+                mnemonic, passphrase, ..
+            } => {
+                let private_key = match mnemonic {
+                    true => Self::read_mnemonic_private_key(stdin, passphrase)?,
+                    false => PrivateKey::<N>::from_str(Self::read_secrets(stdin, 1)?[0].trim())?,
+                };
+                Ok(Self::account_output(private_key.into()))
+            }
+            Self::Recover { passphrase, .. } => {
+                let private_key = Self::read_mnemonic_private_key::<N, _>(stdin, passphrase)?;
+                Ok(Self::account_output(private_key.into()))
+            }
+            Self::Show { .. } => {
+                let key = Self::read_secrets(stdin, 1)?.remove(0);
+                let view_key = match PrivateKey::<N>::from_str(key.trim()) {
+                    Ok(private_key) => ViewKey::from_private_key(&private_key),
+                    Err(_) => ViewKey::<N>::from_str(key.trim())
+                        .map_err(|_| anyhow!("Expected a private key or a view key on stdin"))?,
+                };
+                Ok(vec![
+                    ("view_key", view_key.to_string().into()),
+                    ("address", Address::from_view_key(&view_key).to_string().into()),
+                ])
+            }
+            Self::Sign { message, .. } => {
+                let private_key = PrivateKey::<N>::from_str(Self::read_secrets(stdin, 1)?[0].trim())?;
+                let signature = private_key.sign(message.as_bytes(), &mut thread_rng())?;
+                Ok(vec![
+                    ("address", private_key.to_address().to_string().into()),
+                    ("signature", signature.to_string().into()),
+                ])
+            }
+            Self::Verify {
+                address,
+                message,
+                signature,
+                ..
+            } => {
+                let address = Address::<N>::from_str(&address)?;
+                let signature = N::AccountSignature::from_str(&signature)?;
+                let is_valid = address.verify_signature(message.as_bytes(), &signature)?;
+                Ok(vec![("verified", is_valid.into())])
+            }
+        }
+    }
+
+    /// Returns the keys of the given account.
+    fn account_output<N: Network>(account: Account<N>) -> Vec<(&'static str, Value)> {
+        vec![
+            ("private_key", account.private_key().to_string().into()),
+            ("view_key", account.view_key().to_string().into()),
+            ("address", account.address().to_string().into()),
+        ]
+    }
+
+    /// Returns the private key of a mnemonic phrase, and optionally its passphrase, read from stdin.
+    fn read_mnemonic_private_key<N: Network, R: BufRead>(stdin: &mut R, passphrase: bool) -> Result<PrivateKey<N>> {
+        let mut secrets = Self::read_secrets(stdin, 1 + passphrase as usize)?;
+        let passphrase = match passphrase {
+            true => secrets.remove(1),
+            false => String::new(),
+        };
+        let mnemonic = Mnemonic::from_str(&secrets[0])?;
+        Ok(PrivateKey::<N>::from_mnemonic(&mnemonic, &passphrase)?)
+    }
+
+    /// Reads the given number of lines from stdin, so that secrets are never passed through argv.
+    fn read_secrets<R: BufRead>(stdin: &mut R, num_lines: usize) -> Result<Vec<String>> {
+        let mut secrets = Vec::with_capacity(num_lines);
+        for line in stdin.lines().take(num_lines) {
+            secrets.push(line?.trim_end_matches('\r').to_string());
+        }
+
+        match secrets.len() == num_lines {
+            true => Ok(secrets),
+            false => Err(anyhow!(
+                "Expected {} line(s) on stdin, found {}",
+                num_lines,
+                secrets.len()
+            )),
+        }
+    }
+
+    /// Returns the given outputs, formatted for the terminal.
+    fn format_output(output: Vec<(&'static str, Value)>) -> String {
+        let mut formatted = String::from("\n");
+        for (key, value) in output {
+            let label = key
+                .split('_')
+                .map(|word| word[..1].to_uppercase() + &word[1..])
+                .collect::<Vec<_>>()
+                .join(" ");
+            let value = match value {
+                Value::String(value) => value,
+                value => value.to_string(),
+            };
+            formatted += &format!(" {:>12}  {}\n", label.cyan().bold(), value);
+        }
+        formatted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NETWORKS: [NetworkName; 2] = [NetworkName::Testnet1, NetworkName::Testnet2];
+
+    fn options(network: NetworkName) -> AccountOptions {
+        AccountOptions { network, json: true }
+    }
+
+    /// Returns the JSON output of the given command, reading its secrets from the given input.
+    fn parse(command: AccountCommand, stdin: &str) -> Result<Value> {
+        Ok(serde_json::from_str(&command.parse_with(&mut stdin.as_bytes())?)?)
+    }
+
+    /// Returns the private key, view key, and address of the given account output.
+    fn keys(output: &Value) -> (String, String, String) {
+        let key = |name: &str| output[name].as_str().unwrap().to_string();
+        (key("private_key"), key("view_key"), key("address"))
+    }
+
+    /// Ensures the view key and address of the given account output are derived from its private key.
+    fn check_account<N: Network>(output: &Value) {
+        let (private_key, view_key, address) = keys(output);
+        let private_key = PrivateKey::<N>::from_str(&private_key).unwrap();
+        assert_eq!(ViewKey::from_private_key(&private_key).to_string(), view_key);
+        assert_eq!(private_key.to_address().to_string(), address);
+    }
+
+    fn check_account_on(network: NetworkName, output: &Value) {
+        match network {
+            NetworkName::Testnet1 => check_account::<Testnet1>(output),
+            NetworkName::Testnet2 => check_account::<Testnet2>(output),
+        }
+    }
+
+    #[test]
+    fn test_network_name() {
+        for network in NETWORKS.iter() {
+            assert_eq!(*network, NetworkName::from_str(&network.to_string()).unwrap());
+        }
+        assert_eq!("testnet1", NetworkName::Testnet1.to_string());
+        assert_eq!("testnet2", NetworkName::Testnet2.to_string());
+        assert!(NetworkName::from_str("testnet3").is_err());
+        assert!(NetworkName::from_str("Testnet2").is_err());
+    }
+
+    #[test]
+    fn test_new() {
+        for network in NETWORKS.iter() {
+            let command = AccountCommand::New {
+                mnemonic: false,
+                words: 24,
+                passphrase: false,
+                options: options(*network),
+            };
+            let output = parse(command, "").unwrap();
+            assert!(output.get("mnemonic").is_none());
+            check_account_on(*network, &output);
+        }
+    }
+
+    #[test]
+    fn test_new_with_mnemonic() {
+        for network in NETWORKS.iter() {
+            let command = AccountCommand::New {
+                mnemonic: true,
+                words: 12,
+                passphrase: true,
+                options: options(*network),
+            };
+            let output = parse(command, "passphrase\n").unwrap();
+            check_account_on(*network, &output);
+
+            let mnemonic = output["mnemonic"].as_str().unwrap();
+            assert_eq!(12, mnemonic.split_whitespace().count());
+
+            // The account is recovered from its mnemonic phrase and passphrase.
+            let command = AccountCommand::Recover {
+                passphrase: true,
+                options: options(*network),
+            };
+            let recovered = parse(command, &format!("{}\npassphrase\n", mnemonic)).unwrap();
+            assert_eq!(keys(&output), keys(&recovered));
+        }
+
+        // The passphrase is required on stdin.
+        let command = AccountCommand::New {
+            mnemonic: true,
+            words: 12,
+            passphrase: true,
+            options: options(NetworkName::Testnet2),
+        };
+        assert!(parse(command, "").is_err());
+    }
+
+    #[test]
+    fn test_import() {
+        for network in NETWORKS.iter() {
+            let account = parse(
+                AccountCommand::New {
+                    mnemonic: false,
+                    words: 24,
+                    passphrase: false,
+                    options: options(*network),
+                },
+                "",
+            )
+            .unwrap();
+            let (private_key, ..) = keys(&account);
+
+            let command = AccountCommand::Import {
+                mnemonic: false,
+                passphrase: false,
+                options: options(*network),
+            };
+            let imported = parse(command, &format!("{}\r\n", private_key)).unwrap();
+            assert_eq!(keys(&account), keys(&imported));
+        }
+
+        // An invalid or missing private key is rejected.
+        for stdin in ["", "\n", "invalid\n"].iter() {
+            let command = AccountCommand::Import {
+                mnemonic: false,
+                passphrase: false,
+                options: options(NetworkName::Testnet2),
+            };
+            assert!(parse(command, stdin).is_err());
+        }
+    }
+
+    #[test]
+    fn test_import_and_recover_mnemonic() {
+        let mnemonic = Mnemonic::new(24, &mut thread_rng()).unwrap().to_string();
+
+        for network in NETWORKS.iter() {
+            for passphrase in [false, true].iter() {
+                let stdin = match passphrase {
+                    true => format!("{}\npassphrase\n", mnemonic),
+                    false => format!("{}\n", mnemonic),
+                };
+
+                let imported = parse(
+                    AccountCommand::Import {
+                        mnemonic: true,
+                        passphrase: *passphrase,
+                        options: options(*network),
+                    },
+                    &stdin,
+                )
+                .unwrap();
+                let recovered = parse(
+                    AccountCommand::Recover {
+                        passphrase: *passphrase,
+                        options: options(*network),
+                    },
+                    &stdin,
+                )
+                .unwrap();
+                check_account_on(*network, &imported);
+                assert_eq!(keys(&imported), keys(&recovered));
+            }
+        }
+
+        // A missing passphrase or an invalid mnemonic phrase is rejected.
+        for stdin in [format!("{}\n", mnemonic), "invalid\npassphrase\n".to_string()].iter() {
+            let command = AccountCommand::Recover {
+                passphrase: true,
+                options: options(NetworkName::Testnet2),
+            };
+            assert!(parse(command, stdin).is_err());
+        }
+    }
+
+    #[test]
+    fn test_show() {
+        let account = Account::<Testnet2>::new(&mut thread_rng());
+        let expected = (account.view_key().to_string(), account.address().to_string());
+
+        for key in [account.private_key().to_string(), account.view_key().to_string()].iter() {
+            let command = AccountCommand::Show {
+                options: options(NetworkName::Testnet2),
+            };
+            let output = parse(command, key).unwrap();
+            assert_eq!(
+                expected,
+                (
+                    output["view_key"].as_str().unwrap().to_string(),
+                    output["address"].as_str().unwrap().to_string()
+                )
+            );
+        }
+
+        let command = AccountCommand::Show {
+            options: options(NetworkName::Testnet2),
+        };
+        assert!(parse(command, "invalid").is_err());
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        for network in NETWORKS.iter() {
+            let account = parse(
+                AccountCommand::New {
+                    mnemonic: false,
+                    words: 24,
+                    passphrase: false,
+                    options: options(*network),
+                },
+                "",
+            )
+            .unwrap();
+            let (private_key, _, address) = keys(&account);
+
+            let command = AccountCommand::Sign {
+                message: "message".to_string(),
+                options: options(*network),
+            };
+            let output = parse(command, &private_key).unwrap();
+            assert_eq!(address, output["address"]);
+            let signature = output["signature"].as_str().unwrap().to_string();
+
+            let verify = |message: &str| {
+                parse(
+                    AccountCommand::Verify {
+                        address: address.clone(),
+                        message: message.to_string(),
+                        signature: signature.clone(),
+                        options: options(*network),
+                    },
+                    "",
+                )
+            };
+            assert_eq!(true, verify("message").unwrap()["verified"]);
+
+            // A signature of a different message is rejected with an error.
+            let error = verify("other message").unwrap_err().to_string();
+            assert!(error.contains("\"verified\": false"), "{}", error);
+            assert!(error.ends_with("The signature is invalid for the given address and message"));
+        }
+
+        // A missing private key is rejected.
+        let command = AccountCommand::Sign {
+            message: "message".to_string(),
+            options: options(NetworkName::Testnet2),
+        };
+        assert!(parse(command, "").is_err());
+    }
+
+    #[test]
+    fn test_format_output() {
+        let command = AccountCommand::Show {
+            options: AccountOptions {
+                network: NetworkName::Testnet2,
+                json: false,
+            },
+        };
+        let view_key = ViewKey::from_private_key(&PrivateKey::<Testnet2>::new(&mut thread_rng()));
+        let output = command.parse_with(&mut view_key.to_string().as_bytes()).unwrap();
+        assert!(output.contains("View Key"), "{}", output);
+        assert!(output.contains(&view_key.to_string()), "{}", output);
+        assert!(serde_json::from_str::<Value>(&output).is_err());
+    }
+}
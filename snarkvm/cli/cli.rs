@@ -14,6 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::cli::{AccountCommand, InspectCommand, LedgerCommand, SetupCommand};
+
 use structopt::StructOpt;
 
 #[derive(StructOpt, Debug)]
@@ -33,6 +35,18 @@ pub struct CLI {
 
 #[derive(StructOpt, Debug)]
 pub enum Command {
+    /// Manage Aleo accounts
+    Account(AccountCommand),
+
+    /// Decode and inspect Aleo objects
+    Inspect(InspectCommand),
+
+    /// Run a local chain
+    Ledger(LedgerCommand),
+
+    /// Generate and verify the circuit parameters
+    Setup(SetupCommand),
+
     /// Update snarkVM to the latest version
     Update {
         /// Lists all available versions of snarkVM
@@ -18,6 +18,10 @@ use crate::cli::{Command, Updater};
 
 pub fn parse(command: Command) -> anyhow::Result<String> {
     match command {
+        Command::Account(command) => command.parse(),
+        Command::Inspect(command) => command.parse(),
+        Command::Ledger(command) => command.parse(),
+        Command::Setup(command) => command.parse(),
         Command::Update { list, quiet } => match list {
             true => match Updater::show_available_releases() {
                 Ok(output) => Ok(output),
@@ -0,0 +1,53 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::utilities::FromBytes;
+
+use anyhow::{anyhow, Result};
+use std::{
+    io::{self, Read},
+    str::FromStr,
+};
+
+/// Returns the given input, or the input read from stdin if it is omitted.
+pub(crate) fn read_input(input: Option<String>) -> Result<String> {
+    match input {
+        Some(input) => Ok(input.trim().to_string()),
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input.trim().to_string())
+        }
+    }
+}
+
+/// Decodes the given input with `FromBytes` if it is hex, and with `FromStr` otherwise.
+pub(crate) fn decode_object<T: FromBytes + FromStr>(input: &str) -> Result<T>
+where
+    T::Err: Into<anyhow::Error>,
+{
+    match hex::decode(input) {
+        Ok(bytes) => {
+            let mut reader = &bytes[..];
+            let object = T::read_le(&mut reader)?;
+            match reader.is_empty() {
+                true => Ok(object),
+                false => Err(anyhow!("Found {} trailing bytes after the object", reader.len())),
+            }
+        }
+        Err(_) => T::from_str(input).map_err(Into::into),
+    }
+}
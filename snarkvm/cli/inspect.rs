@@ -0,0 +1,366 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    cli::{decode_object, read_input, NetworkName},
+    dpc::{testnet1::Testnet1, testnet2::Testnet2, Block, BlockHeader, Network, Record, Transaction, ViewKey},
+};
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Map, Value};
+use std::{fmt, str::FromStr};
+use structopt::StructOpt;
+
+/// The type of an object that may be inspected.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ObjectType {
+    Block,
+    BlockHeader,
+    Transaction,
+    Record,
+    Ciphertext,
+    OuterProof,
+}
+
+impl ObjectType {
+    /// The object types that are attempted when the type is not given.
+    const ALL: [ObjectType; 6] = [
+        Self::Block,
+        Self::BlockHeader,
+        Self::Transaction,
+        Self::Record,
+        Self::Ciphertext,
+        Self::OuterProof,
+    ];
+}
+
+impl FromStr for ObjectType {
+    type Err = anyhow::Error;
+
+    fn from_str(object_type: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|candidate| candidate.to_string() == object_type)
+            .copied()
+            .ok_or_else(|| anyhow!("Unknown object type {}", object_type))
+    }
+}
+
+impl fmt::Display for ObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Block => write!(f, "block"),
+            Self::BlockHeader => write!(f, "block_header"),
+            Self::Transaction => write!(f, "transaction"),
+            Self::Record => write!(f, "record"),
+            Self::Ciphertext => write!(f, "ciphertext"),
+            Self::OuterProof => write!(f, "outer_proof"),
+        }
+    }
+}
+
+/// A decoded object.
+enum Object<N: Network> {
+    Block(Block<N>),
+    BlockHeader(BlockHeader<N>),
+    Transaction(Transaction<N>),
+    Record(Record<N>),
+    Ciphertext(N::RecordCiphertext),
+    OuterProof(N::OuterProof),
+}
+
+#[derive(StructOpt, Debug)]
+pub struct InspectCommand {
+    /// The hex encoding or string encoding of the object, read from stdin if omitted
+    pub object: Option<String>,
+
+    /// The type of the object, one of `block`, `block_header`, `transaction`, `record`, `ciphertext` or `outer_proof`
+    #[structopt(long = "type")]
+    pub object_type: Option<ObjectType>,
+
+    /// The account view key used to decrypt the records of a ciphertext, transaction or block
+    #[structopt(long)]
+    pub view_key: Option<String>,
+
+    /// The network of the object, either `testnet1` or `testnet2`
+    #[structopt(long, default_value = "testnet2")]
+    pub network: NetworkName,
+}
+
+impl InspectCommand {
+    pub fn parse(self) -> Result<String> {
+        let input = read_input(self.object.clone())?;
+
+        let output = match self.network {
+            NetworkName::Testnet1 => self.inspect::<Testnet1>(&input)?,
+            NetworkName::Testnet2 => self.inspect::<Testnet2>(&input)?,
+        };
+        Ok(serde_json::to_string_pretty(&output)?)
+    }
+
+    /// Returns the fields of the given object on the network `N`, and the results of its checks.
+    fn inspect<N: Network>(&self, input: &str) -> Result<Value> {
+        let view_key = match &self.view_key {
+            Some(view_key) => Some(ViewKey::<N>::from_str(view_key)?),
+            None => None,
+        };
+
+        let (object_type, object) = match self.object_type {
+            Some(object_type) => (object_type, Self::decode::<N>(input, object_type)?),
+            None => {
+                let mut candidates = ObjectType::ALL
+                    .iter()
+                    .filter_map(|object_type| Some((*object_type, Self::decode::<N>(input, *object_type).ok()?)))
+                    .collect::<Vec<_>>();
+
+                match candidates.len() {
+                    0 => {
+                        return Err(anyhow!(
+                            "Failed to decode the object as any known {} object",
+                            N::NETWORK_NAME
+                        ))
+                    }
+                    1 => candidates.remove(0),
+                    _ => {
+                        let object_types = candidates.iter().map(|(object_type, _)| object_type.to_string());
+                        return Err(anyhow!(
+                            "The object decodes as any of {}, specify one with --type",
+                            object_types.collect::<Vec<_>>().join(", ")
+                        ));
+                    }
+                }
+            }
+        };
+
+        let mut output = Map::new();
+        output.insert("network".to_string(), N::NETWORK_NAME.into());
+        output.insert("type".to_string(), object_type.to_string().into());
+
+        match object {
+            Object::Block(block) => {
+                output.insert("object".to_string(), serde_json::to_value(&block)?);
+                output.insert("is_valid".to_string(), block.is_valid().into());
+                if let Some(view_key) = &view_key {
+                    let records = block
+                        .transactions()
+                        .iter()
+                        .flat_map(|transaction| transaction.to_decrypted_records(view_key))
+                        .collect::<Vec<_>>();
+                    output.insert("records".to_string(), serde_json::to_value(&records)?);
+                }
+            }
+            Object::BlockHeader(header) => {
+                output.insert("object".to_string(), serde_json::to_value(&header)?);
+                output.insert("is_valid".to_string(), header.is_valid().into());
+            }
+            Object::Transaction(transaction) => {
+                output.insert("object".to_string(), serde_json::to_value(&transaction)?);
+                output.insert("is_valid".to_string(), transaction.is_valid().into());
+                if let Some(view_key) = &view_key {
+                    let records = transaction.to_decrypted_records(view_key);
+                    output.insert("records".to_string(), serde_json::to_value(&records)?);
+                }
+            }
+            Object::Record(record) => {
+                output.insert("object".to_string(), serde_json::to_value(&record)?);
+            }
+            Object::Ciphertext(ciphertext) => {
+                output.insert(
+                    "object".to_string(),
+                    json!({
+                        "ciphertext": ciphertext.to_string(),
+                        "commitment": ciphertext.commitment(),
+                        "randomizer": ciphertext.randomizer(),
+                        "record_view_key_commitment": ciphertext.record_view_key_commitment(),
+                    }),
+                );
+                if let Some(view_key) = &view_key {
+                    let record = match ciphertext.is_owner(view_key) {
+                        true => Some(Record::from_account_view_key(view_key, &ciphertext)?),
+                        false => None,
+                    };
+                    output.insert("record".to_string(), serde_json::to_value(&record)?);
+                }
+            }
+            Object::OuterProof(proof) => {
+                output.insert("object".to_string(), serde_json::to_value(&proof)?);
+            }
+        }
+
+        Ok(Value::Object(output))
+    }
+
+    /// Decodes the given hex encoding or string encoding as an object of the given type.
+    fn decode<N: Network>(input: &str, object_type: ObjectType) -> Result<Object<N>> {
+        match object_type {
+            ObjectType::Block => Ok(Object::Block(decode_object(input)?)),
+            ObjectType::BlockHeader => Ok(Object::BlockHeader(decode_object(input)?)),
+            ObjectType::Transaction => Ok(Object::Transaction(decode_object(input)?)),
+            ObjectType::Record => Ok(Object::Record(decode_object(input)?)),
+            ObjectType::Ciphertext => Ok(Object::Ciphertext(decode_object(input)?)),
+            ObjectType::OuterProof => Ok(Object::OuterProof(decode_object(input)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dpc::{Account, AccountScheme, AleoAmount, Payload},
+        utilities::ToBytes,
+    };
+
+    use rand::thread_rng;
+
+    fn inspect(object: String, object_type: Option<ObjectType>, view_key: Option<String>) -> Value {
+        let command = InspectCommand {
+            object: Some(object),
+            object_type,
+            view_key,
+            network: NetworkName::Testnet2,
+        };
+        serde_json::from_str(&command.parse().unwrap()).unwrap()
+    }
+
+    /// Returns a new record owned by the given account.
+    fn sample_record(account: &Account<Testnet2>) -> Record<Testnet2> {
+        Record::new(
+            account.address(),
+            AleoAmount(1234),
+            Payload::default(),
+            *Testnet2::noop_program_id(),
+            &mut thread_rng(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_inspect_block() {
+        let block = Testnet2::genesis_block();
+
+        // The block is decoded from its hex encoding and its string encoding, with and without the type.
+        for object in [hex::encode(block.to_bytes_le().unwrap()), block.to_string()] {
+            for object_type in [None, Some(ObjectType::Block)] {
+                let output = inspect(object.clone(), object_type, None);
+                assert_eq!("testnet2", output["network"]);
+                assert_eq!("block", output["type"]);
+                assert_eq!(true, output["is_valid"]);
+
+                // The inspected block round-trips to the original block.
+                let candidate: Block<Testnet2> = serde_json::from_value(output["object"].clone()).unwrap();
+                assert_eq!(block.hash(), candidate.hash());
+                assert_eq!(block.to_bytes_le().unwrap(), candidate.to_bytes_le().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_inspect_block_header_is_not_a_block() {
+        let header = Testnet2::genesis_block().header();
+
+        let output = inspect(hex::encode(header.to_bytes_le().unwrap()), None, None);
+        assert_eq!("block_header", output["type"]);
+    }
+
+    #[test]
+    fn test_inspect_transaction() {
+        let transaction = Testnet2::genesis_block().to_coinbase_transaction().unwrap();
+
+        for object in [hex::encode(transaction.to_bytes_le().unwrap()), transaction.to_string()] {
+            let output = inspect(object, Some(ObjectType::Transaction), None);
+            assert_eq!("transaction", output["type"]);
+            assert_eq!(true, output["is_valid"]);
+
+            // The inspected transaction round-trips to the original transaction.
+            let candidate: Transaction<Testnet2> = serde_json::from_value(output["object"].clone()).unwrap();
+            assert_eq!(transaction, candidate);
+        }
+    }
+
+    #[test]
+    fn test_inspect_record() {
+        let record = sample_record(&Account::new(&mut thread_rng()));
+
+        for object in [hex::encode(record.to_bytes_le().unwrap()), record.to_string()] {
+            let output = inspect(object, Some(ObjectType::Record), None);
+            assert_eq!("record", output["type"]);
+
+            // The inspected record round-trips to the original record.
+            let candidate: Record<Testnet2> = serde_json::from_value(output["object"].clone()).unwrap();
+            assert_eq!(record, candidate);
+        }
+    }
+
+    #[test]
+    fn test_inspect_ciphertext() {
+        let record = sample_record(&Account::new(&mut thread_rng()));
+        let ciphertext = record.ciphertext();
+
+        let output = inspect(
+            hex::encode(ciphertext.to_bytes_le().unwrap()),
+            Some(ObjectType::Ciphertext),
+            None,
+        );
+        assert_eq!("ciphertext", output["type"]);
+        assert!(output.get("record").is_none());
+
+        // The inspected ciphertext round-trips to the original ciphertext.
+        let candidate =
+            <Testnet2 as Network>::RecordCiphertext::from_str(output["object"]["ciphertext"].as_str().unwrap())
+                .unwrap();
+        assert_eq!(*ciphertext, candidate);
+        assert_eq!(
+            serde_json::to_value(record.commitment()).unwrap(),
+            output["object"]["commitment"]
+        );
+    }
+
+    #[test]
+    fn test_inspect_ciphertext_with_view_key() {
+        let account = Account::<Testnet2>::new(&mut thread_rng());
+        let record = sample_record(&account);
+        let object = hex::encode(record.ciphertext().to_bytes_le().unwrap());
+
+        // The view key of the owner decrypts the record.
+        let view_key = account.view_key().to_string();
+        let output = inspect(object.clone(), Some(ObjectType::Ciphertext), Some(view_key));
+        let candidate: Record<Testnet2> = serde_json::from_value(output["record"].clone()).unwrap();
+        assert_eq!(record, candidate);
+
+        // The view key of another account does not decrypt the record.
+        let view_key = Account::<Testnet2>::new(&mut thread_rng()).view_key().to_string();
+        let output = inspect(object, Some(ObjectType::Ciphertext), Some(view_key));
+        assert!(output["record"].is_null());
+    }
+
+    #[test]
+    fn test_inspect_outer_proof() {
+        let transaction = Testnet2::genesis_block().to_coinbase_transaction().unwrap();
+        let proof = transaction.transitions()[0].proof();
+
+        let output = inspect(
+            hex::encode(proof.to_bytes_le().unwrap()),
+            Some(ObjectType::OuterProof),
+            None,
+        );
+        assert_eq!("outer_proof", output["type"]);
+
+        // The inspected proof round-trips to the original proof.
+        let candidate: <Testnet2 as Network>::OuterProof = serde_json::from_value(output["object"].clone()).unwrap();
+        assert_eq!(*proof, candidate);
+    }
+}
@@ -0,0 +1,397 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    cli::{decode_object, read_input},
+    dpc::{testnet2::Testnet2, Address, Ledger, Network, Transaction},
+};
+
+use anyhow::{anyhow, Result};
+use rand::thread_rng;
+use serde_json::{json, Value};
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::atomic::AtomicBool,
+};
+use structopt::StructOpt;
+
+/// The file name of the ledger configuration in the ledger directory.
+const CONFIG_FILE_NAME: &str = "config.json";
+
+/// The file name of the submitted, unconfirmed transactions in the ledger directory.
+const MEMORY_POOL_FILE_NAME: &str = "memory_pool.json";
+
+#[derive(StructOpt, Debug)]
+pub struct LedgerOptions {
+    /// The directory of the ledger
+    #[structopt(long, default_value = "ledger", parse(from_os_str))]
+    pub directory: PathBuf,
+}
+
+#[derive(StructOpt, Debug)]
+pub enum LedgerCommand {
+    /// Initializes a new local chain in the ledger directory
+    Init {
+        /// Spaces the block timestamps by the block time to pin the difficulty target at the genesis target.
+        /// This only avoids retries of the PoSW proof, as every block still requires a full PoSW proof
+        #[structopt(long)]
+        pinned_difficulty: bool,
+
+        #[structopt(flatten)]
+        options: LedgerOptions,
+    },
+    /// Mines blocks with the unconfirmed transactions, paying the coinbase rewards to the given address
+    Mine {
+        /// The address of the coinbase recipient
+        #[structopt(long)]
+        to: String,
+
+        /// The number of blocks to mine
+        #[structopt(long, default_value = "1")]
+        blocks: u32,
+
+        /// Publishes the coinbase records in plaintext
+        #[structopt(long)]
+        public: bool,
+
+        #[structopt(flatten)]
+        options: LedgerOptions,
+    },
+    /// Submits a transaction to the memory pool
+    Submit {
+        /// The hex encoding or JSON encoding of the transaction, read from stdin if omitted
+        transaction: Option<String>,
+
+        #[structopt(flatten)]
+        options: LedgerOptions,
+    },
+    /// Shows the latest block and the size of the memory pool
+    Status {
+        #[structopt(flatten)]
+        options: LedgerOptions,
+    },
+    /// Exports the canon blocks as JSON
+    Export {
+        /// The height of the first block to export
+        #[structopt(long, default_value = "0")]
+        start: u32,
+
+        /// The height of the last block to export, the latest block if omitted
+        #[structopt(long)]
+        end: Option<u32>,
+
+        #[structopt(flatten)]
+        options: LedgerOptions,
+    },
+}
+
+impl LedgerCommand {
+    pub fn parse(self) -> Result<String> {
+        let output = match self {
+            Self::Init {
+                pinned_difficulty,
+                options,
+            } => {
+                let config_path = options.directory.join(CONFIG_FILE_NAME);
+                if config_path.exists() {
+                    return Err(anyhow!("A ledger already exists in {}", options.directory.display()));
+                }
+
+                let ledger = Ledger::<Testnet2>::open(&options.directory)?;
+                let config = json!({ "network": Testnet2::NETWORK_NAME, "pinned_difficulty": pinned_difficulty });
+                Self::write_file(&config_path, serde_json::to_string_pretty(&config)?.as_bytes())?;
+
+                Self::status(&ledger, pinned_difficulty)?
+            }
+            Self::Mine {
+                to,
+                blocks,
+                public,
+                options,
+            } => {
+                let recipient = Address::<Testnet2>::from_str(&to)?;
+                let pinned_difficulty = Self::load_config(&options.directory)?;
+                let mut ledger = Self::open(&options.directory)?;
+
+                let terminator = AtomicBool::new(false);
+                let rng = &mut thread_rng();
+
+                let mut mined_blocks = Vec::with_capacity(blocks as usize);
+                for _ in 0..blocks {
+                    let coinbase_record = match pinned_difficulty {
+                        true => {
+                            let timestamp = ledger.latest_block_timestamp()? + Testnet2::ALEO_BLOCK_TIME_IN_SECS;
+                            ledger.mine_next_block_with_timestamp(recipient, public, timestamp, &terminator, rng)?
+                        }
+                        false => ledger.mine_next_block(recipient, public, &terminator, rng)?,
+                    };
+                    Self::save_memory_pool(&options.directory, &ledger)?;
+
+                    mined_blocks.push(json!({
+                        "height": ledger.latest_block_height(),
+                        "block_hash": ledger.latest_block_hash(),
+                        "coinbase_record": coinbase_record,
+                    }));
+                }
+
+                Value::Array(mined_blocks)
+            }
+            Self::Submit { transaction, options } => {
+                let transaction: Transaction<Testnet2> = decode_object(&read_input(transaction)?)?;
+                let mut ledger = Self::open(&options.directory)?;
+
+                let evicted = ledger.add_unconfirmed_transaction(&transaction)?;
+                Self::save_memory_pool(&options.directory, &ledger)?;
+
+                json!({
+                    "transaction_id": transaction.transaction_id(),
+                    "evicted": evicted.iter().map(Transaction::transaction_id).collect::<Vec<_>>(),
+                    "memory_pool_size": ledger.memory_pool().num_transactions(),
+                })
+            }
+            Self::Status { options } => {
+                let pinned_difficulty = Self::load_config(&options.directory)?;
+                Self::status(&Self::open(&options.directory)?, pinned_difficulty)?
+            }
+            Self::Export { start, end, options } => {
+                let ledger = Self::open(&options.directory)?;
+                let end = end.unwrap_or_else(|| ledger.latest_block_height());
+                if start > end || end > ledger.latest_block_height() {
+                    return Err(anyhow!(
+                        "Invalid block range {}..={}, the latest block height is {}",
+                        start,
+                        end,
+                        ledger.latest_block_height()
+                    ));
+                }
+
+                let blocks = (start..=end)
+                    .map(|height| ledger.get_block(height))
+                    .collect::<Result<Vec<_>>>()?;
+                serde_json::to_value(&blocks)?
+            }
+        };
+
+        Ok(serde_json::to_string_pretty(&output)?)
+    }
+
+    /// Returns the latest block and the size of the memory pool of the given ledger.
+    fn status(ledger: &Ledger<Testnet2>, pinned_difficulty: bool) -> Result<Value> {
+        Ok(json!({
+            "network": Testnet2::NETWORK_NAME,
+            "pinned_difficulty": pinned_difficulty,
+            "latest_block_height": ledger.latest_block_height(),
+            "latest_block_hash": ledger.latest_block_hash(),
+            "latest_block_timestamp": ledger.latest_block_timestamp()?,
+            "latest_ledger_root": ledger.latest_ledger_root(),
+            "difficulty_target": ledger.latest_block_difficulty_target()?,
+            "cumulative_weight": ledger.latest_cumulative_weight()?.to_string(),
+            "memory_pool_size": ledger.memory_pool().num_transactions(),
+        }))
+    }
+
+    /// Returns `true` if the ledger in the given directory pins its difficulty target.
+    fn load_config(directory: &Path) -> Result<bool> {
+        let config_path = directory.join(CONFIG_FILE_NAME);
+        if !config_path.exists() {
+            return Err(anyhow!(
+                "No ledger found in {}, run `snarkvm ledger init` first",
+                directory.display()
+            ));
+        }
+
+        let config: Value = serde_json::from_str(&fs::read_to_string(config_path)?)?;
+        match config["network"].as_str() == Some(Testnet2::NETWORK_NAME) {
+            true => Ok(config["pinned_difficulty"].as_bool().unwrap_or(false)),
+            false => Err(anyhow!(
+                "The ledger in {} is not a {} ledger",
+                directory.display(),
+                Testnet2::NETWORK_NAME
+            )),
+        }
+    }
+
+    /// Opens the ledger in the given directory, and restores its memory pool.
+    fn open(directory: &Path) -> Result<Ledger<Testnet2>> {
+        Self::load_config(directory)?;
+        let mut ledger = Ledger::<Testnet2>::open(directory)?;
+
+        let memory_pool_path = directory.join(MEMORY_POOL_FILE_NAME);
+        if memory_pool_path.exists() {
+            let transactions: Vec<Transaction<Testnet2>> =
+                serde_json::from_str(&fs::read_to_string(memory_pool_path)?)?;
+
+            // Transactions that are no longer valid, such as expired transactions, are dropped.
+            for transaction in transactions {
+                if let Err(error) = ledger.add_unconfirmed_transaction(&transaction) {
+                    eprintln!("Dropping transaction {}: {}", transaction.transaction_id(), error);
+                }
+            }
+        }
+
+        Ok(ledger)
+    }
+
+    /// Saves the memory pool of the given ledger to the given directory.
+    fn save_memory_pool(directory: &Path, ledger: &Ledger<Testnet2>) -> Result<()> {
+        let transactions = ledger.memory_pool().transactions();
+        Self::write_file(
+            &directory.join(MEMORY_POOL_FILE_NAME),
+            serde_json::to_string(&transactions)?.as_bytes(),
+        )
+    }
+
+    /// Writes the given contents to a temporary file, and atomically replaces the file at the given path,
+    /// so that an interrupted command never leaves a partially written file behind.
+    fn write_file(path: &Path, contents: &[u8]) -> Result<()> {
+        let temporary_path = path.with_extension("tmp");
+        {
+            let mut file = File::create(&temporary_path)?;
+            file.write_all(contents)?;
+            file.sync_all()?;
+        }
+        fs::rename(&temporary_path, path)?;
+
+        // Ensure the rename itself is persisted.
+        #[cfg(unix)]
+        if let Some(directory) = path.parent() {
+            File::open(directory)?.sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dpc::{Account, AccountScheme, Block};
+
+    fn ledger_options(directory: &Path) -> LedgerOptions {
+        LedgerOptions {
+            directory: directory.to_path_buf(),
+        }
+    }
+
+    fn parse(command: LedgerCommand) -> Value {
+        serde_json::from_str(&command.parse().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_init_status_and_export() {
+        let directory = tempfile::tempdir().unwrap();
+        let genesis_block = Testnet2::genesis_block();
+
+        let status = parse(LedgerCommand::Init {
+            pinned_difficulty: true,
+            options: ledger_options(directory.path()),
+        });
+        assert_eq!(true, status["pinned_difficulty"]);
+        assert_eq!(0, status["latest_block_height"]);
+        assert_eq!(0, status["memory_pool_size"]);
+
+        // A ledger may only be initialized once in a directory.
+        let command = LedgerCommand::Init {
+            pinned_difficulty: false,
+            options: ledger_options(directory.path()),
+        };
+        assert!(command.parse().is_err());
+
+        // The status is restored from the ledger directory.
+        let status = parse(LedgerCommand::Status {
+            options: ledger_options(directory.path()),
+        });
+        assert_eq!(true, status["pinned_difficulty"]);
+        assert_eq!(
+            serde_json::to_value(genesis_block.hash()).unwrap(),
+            status["latest_block_hash"]
+        );
+
+        // The genesis block is exported, and blocks beyond the latest block are rejected.
+        let blocks: Vec<Block<Testnet2>> = serde_json::from_value(parse(LedgerCommand::Export {
+            start: 0,
+            end: None,
+            options: ledger_options(directory.path()),
+        }))
+        .unwrap();
+        assert_eq!(vec![genesis_block.clone()], blocks);
+
+        let command = LedgerCommand::Export {
+            start: 0,
+            end: Some(1),
+            options: ledger_options(directory.path()),
+        };
+        assert!(command.parse().is_err());
+    }
+
+    #[test]
+    fn test_open_without_init() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let command = LedgerCommand::Status {
+            options: ledger_options(directory.path()),
+        };
+        assert!(command.parse().is_err());
+    }
+
+    #[test]
+    fn test_save_memory_pool() {
+        let directory = tempfile::tempdir().unwrap();
+        parse(LedgerCommand::Init {
+            pinned_difficulty: false,
+            options: ledger_options(directory.path()),
+        });
+
+        let ledger = LedgerCommand::open(directory.path()).unwrap();
+        LedgerCommand::save_memory_pool(directory.path(), &ledger).unwrap();
+
+        // The memory pool replaces the previous file, and no temporary file is left behind.
+        let memory_pool_path = directory.path().join(MEMORY_POOL_FILE_NAME);
+        let transactions: Vec<Transaction<Testnet2>> =
+            serde_json::from_str(&fs::read_to_string(&memory_pool_path).unwrap()).unwrap();
+        assert!(transactions.is_empty());
+        assert!(!memory_pool_path.with_extension("tmp").exists());
+    }
+
+    #[test]
+    fn test_mine_with_pinned_difficulty() {
+        let directory = tempfile::tempdir().unwrap();
+        let recipient = Account::<Testnet2>::new(&mut thread_rng());
+        parse(LedgerCommand::Init {
+            pinned_difficulty: true,
+            options: ledger_options(directory.path()),
+        });
+        let genesis_difficulty_target = Testnet2::genesis_block().difficulty_target();
+
+        let mined_blocks = parse(LedgerCommand::Mine {
+            to: recipient.address().to_string(),
+            blocks: 2,
+            public: false,
+            options: ledger_options(directory.path()),
+        });
+        assert_eq!(2, mined_blocks.as_array().unwrap().len());
+
+        // The chain keeps the genesis difficulty target, so every block is mined in a single PoSW attempt.
+        let ledger = LedgerCommand::open(directory.path()).unwrap();
+        assert_eq!(2, ledger.latest_block_height());
+        assert_eq!(
+            genesis_difficulty_target,
+            ledger.latest_block_difficulty_target().unwrap()
+        );
+    }
+}
@@ -25,7 +25,7 @@ fn main() -> anyhow::Result<()> {
         println!("\n{:#?}\n", cli);
     }
 
-    println!("{}", Updater::print_cli());
+    eprintln!("{}", Updater::print_cli());
 
     println!("{}", parse(cli.command)?);
 
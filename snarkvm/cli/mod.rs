@@ -14,6 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod account;
+pub use account::*;
+
 pub mod cli;
 pub use cli::*;
 
@@ -23,5 +26,20 @@ pub use commands::*;
 pub mod errors;
 pub use errors::*;
 
+mod helpers;
+pub(crate) use helpers::*;
+
+pub mod inspect;
+pub use inspect::*;
+
+pub mod ledger;
+pub use ledger::*;
+
+pub mod network;
+pub use network::*;
+
+pub mod setup;
+pub use setup::*;
+
 pub mod updater;
 pub use updater::*;
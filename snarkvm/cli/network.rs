@@ -0,0 +1,53 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::dpc::{testnet1::Testnet1, testnet2::Testnet2, Network};
+
+use anyhow::anyhow;
+use std::{fmt, str::FromStr};
+
+/// The network that a command is run against.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NetworkName {
+    Testnet1,
+    Testnet2,
+}
+
+impl FromStr for NetworkName {
+    type Err = anyhow::Error;
+
+    fn from_str(network: &str) -> Result<Self, Self::Err> {
+        match network {
+            _ if network == Testnet1::NETWORK_NAME => Ok(Self::Testnet1),
+            _ if network == Testnet2::NETWORK_NAME => Ok(Self::Testnet2),
+            _ => Err(anyhow!(
+                "Unknown network {}, expected {} or {}",
+                network,
+                Testnet1::NETWORK_NAME,
+                Testnet2::NETWORK_NAME
+            )),
+        }
+    }
+}
+
+impl fmt::Display for NetworkName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Testnet1 => write!(f, "{}", Testnet1::NETWORK_NAME),
+            Self::Testnet2 => write!(f, "{}", Testnet2::NETWORK_NAME),
+        }
+    }
+}
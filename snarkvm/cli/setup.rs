@@ -0,0 +1,524 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    algorithms::{crh::sha256::sha256, CRH, SNARK, SRS},
+    cli::NetworkName,
+    dpc::{
+        testnet1::Testnet1,
+        testnet2::Testnet2,
+        Execution,
+        Function,
+        InnerCircuit,
+        Network,
+        Noop,
+        NoopPrivateVariables,
+        OuterCircuit,
+        PoSWScheme,
+        ProgramPrivateVariables,
+        ProgramPublicVariables,
+        SynthesizedCircuit,
+    },
+    utilities::{FromBytes, ToBytes, ToMinimalBits},
+};
+
+use anyhow::{anyhow, Result};
+use rand::{prelude::ThreadRng, thread_rng};
+use serde_json::{json, Map, Value};
+use snarkvm_marlin::ahp::AHPForR1CS;
+use std::{
+    fmt,
+    fs,
+    path::{Path, PathBuf},
+};
+use structopt::StructOpt;
+
+/// The parameters that are generated by a setup.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Parameter {
+    Universal,
+    Noop,
+    Inner,
+    Outer,
+    PoSW,
+}
+
+impl Parameter {
+    /// The parameters, in the order they are generated in.
+    pub const ALL: [Self; 5] = [Self::Universal, Self::Noop, Self::Inner, Self::Outer, Self::PoSW];
+
+    /// Returns the file types of the parameter, and whether each file is stored remotely.
+    /// Remote files are versioned with the first 7 characters of their checksum.
+    pub fn file_types(&self) -> &'static [(&'static str, bool)] {
+        match self {
+            Self::Universal => &[("srs", true)],
+            Self::Noop => &[("proving", false), ("verifying", false)],
+            Self::Inner | Self::Outer | Self::PoSW => &[("proving", true), ("verifying", false)],
+        }
+    }
+
+    /// Returns `true` if the parameter is used by the given network.
+    pub fn is_supported(&self, network: NetworkName) -> bool {
+        !(*self == Self::Universal && network == NetworkName::Testnet1)
+    }
+}
+
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Universal => write!(f, "universal"),
+            Self::Noop => write!(f, "noop"),
+            Self::Inner => write!(f, "inner"),
+            Self::Outer => write!(f, "outer"),
+            Self::PoSW => write!(f, "posw"),
+        }
+    }
+}
+
+#[derive(StructOpt, Debug)]
+pub struct SetupOptions {
+    /// The network of the parameters
+    #[structopt(long, default_value = "testnet2")]
+    pub network: NetworkName,
+
+    /// The directory of the parameter and metadata files
+    #[structopt(long, default_value = ".", parse(from_os_str))]
+    pub directory: PathBuf,
+}
+
+#[derive(StructOpt, Debug)]
+pub enum SetupCommand {
+    /// Generates the universal SRS
+    Universal {
+        #[structopt(flatten)]
+        options: SetupOptions,
+    },
+    /// Generates the noop circuit proving and verifying keys, using the universal SRS in the directory if it
+    /// was generated there, and the universal SRS of this build otherwise
+    Noop {
+        #[structopt(flatten)]
+        options: SetupOptions,
+    },
+    /// Generates the inner circuit proving and verifying keys
+    Inner {
+        #[structopt(flatten)]
+        options: SetupOptions,
+    },
+    /// Generates the outer circuit proving and verifying keys, using the inner keys in the directory if they
+    /// were generated there, and the inner keys of this build otherwise
+    Outer {
+        #[structopt(flatten)]
+        options: SetupOptions,
+    },
+    /// Generates the PoSW circuit proving and verifying keys
+    Posw {
+        #[structopt(flatten)]
+        options: SetupOptions,
+    },
+    /// Recomputes the checksums and sizes of the parameter files, and reports any drift from their metadata
+    Verify {
+        /// The directory of the remote parameter files, if they are not stored with their metadata
+        #[structopt(long, parse(from_os_str))]
+        remote_directory: Option<PathBuf>,
+
+        #[structopt(flatten)]
+        options: SetupOptions,
+    },
+}
+
+impl SetupCommand {
+    pub fn parse(self) -> Result<String> {
+        let (parameter, options) = match self {
+            Self::Universal { options } => (Parameter::Universal, options),
+            Self::Noop { options } => (Parameter::Noop, options),
+            Self::Inner { options } => (Parameter::Inner, options),
+            Self::Outer { options } => (Parameter::Outer, options),
+            Self::Posw { options } => (Parameter::PoSW, options),
+            Self::Verify {
+                remote_directory,
+                options,
+            } => return Self::verify(&options, remote_directory.as_deref()),
+        };
+
+        if !parameter.is_supported(options.network) {
+            return Err(anyhow!("{} does not use a {} setup", options.network, parameter));
+        }
+
+        let (files, circuit_id) = match options.network {
+            NetworkName::Testnet1 => Self::setup::<Testnet1>(parameter, &options.directory)?,
+            NetworkName::Testnet2 => Self::setup::<Testnet2>(parameter, &options.directory)?,
+        };
+        let (metadata, paths) = Self::write_files(&options.directory, parameter, files, circuit_id)?;
+
+        Ok(serde_json::to_string_pretty(&json!({
+            "network": options.network.to_string(),
+            "parameter": parameter.to_string(),
+            "metadata": metadata,
+            "files": paths,
+        }))?)
+    }
+
+    /// Returns the file name of the given parameter file.
+    fn filename(parameter: Parameter, file_type: &str, is_remote: bool, checksum: &str) -> String {
+        match (is_remote, checksum.get(0..7)) {
+            (true, Some(sum)) => format!("{}.{}.{}", parameter, file_type, sum),
+            _ => format!("{}.{}", parameter, file_type),
+        }
+    }
+
+    /// Runs the setup of the given parameter, returning the bytes of each of its files,
+    /// and the circuit ID if the parameter is a circuit that is identified by one.
+    fn setup<N: Network>(parameter: Parameter, directory: &Path) -> Result<(Vec<Vec<u8>>, Option<String>)> {
+        match parameter {
+            Parameter::Universal => {
+                let max_degree = AHPForR1CS::<N::InnerScalarField>::max_degree(2000000, 4000000, 8000000)
+                    .map_err(|error| anyhow!("Failed to compute the maximum degree: {:?}", error))?;
+                let universal_srs = <N::ProgramSNARK as SNARK>::universal_setup(&max_degree, &mut thread_rng())?;
+
+                Ok((vec![universal_srs.to_bytes_le()?], None))
+            }
+            Parameter::Noop => {
+                // The universal SRS of this build does not match a fresh universal setup,
+                // so the universal SRS from a preceding universal setup in the directory takes precedence.
+                let noop_circuit = SynthesizedCircuit::<N>::Noop(Default::default());
+                let (proving_key, verifying_key) = match Self::read_files(directory, Parameter::Universal)? {
+                    Some(files) => <N::ProgramSNARK as SNARK>::setup(
+                        &noop_circuit,
+                        &mut SRS::<ThreadRng, _>::Universal(&FromBytes::read_le(&files[0][..])?),
+                    )?,
+                    None => <N::ProgramSNARK as SNARK>::setup(
+                        &noop_circuit,
+                        &mut *N::program_srs(&mut thread_rng()).borrow_mut(),
+                    )?,
+                };
+                let function_id = hex::encode(N::function_id(&verifying_key)?.to_bytes_le()?);
+
+                Ok((
+                    vec![proving_key.to_bytes_le()?, verifying_key.to_bytes_le()?],
+                    Some(function_id),
+                ))
+            }
+            Parameter::Inner => {
+                let (proving_key, verifying_key) = N::InnerSNARK::setup(
+                    &InnerCircuit::<N>::blank(),
+                    &mut SRS::CircuitSpecific(&mut thread_rng()),
+                )?;
+                let inner_circuit_id = hex::encode(
+                    N::inner_circuit_id_crh()
+                        .hash_bits(&verifying_key.to_minimal_bits())?
+                        .to_bytes_le()?,
+                );
+
+                Ok((
+                    vec![proving_key.to_bytes_le()?, verifying_key.to_bytes_le()?],
+                    Some(inner_circuit_id),
+                ))
+            }
+            Parameter::Outer => {
+                // The inner keys of this build do not match a modified inner circuit,
+                // so the inner keys from a preceding inner setup in the directory take precedence.
+                let (inner_proving_key, inner_verifying_key) = match Self::read_files(directory, Parameter::Inner)? {
+                    Some(files) => (FromBytes::read_le(&files[0][..])?, FromBytes::read_le(&files[1][..])?),
+                    None => (N::inner_proving_key().clone(), N::inner_verifying_key().clone()),
+                };
+                let inner_proof =
+                    N::InnerSNARK::prove(&inner_proving_key, &InnerCircuit::<N>::blank(), &mut thread_rng())?;
+
+                let (proving_key, verifying_key) = N::OuterSNARK::setup(
+                    &OuterCircuit::<N>::blank(
+                        inner_verifying_key,
+                        inner_proof.into(),
+                        Execution {
+                            program_id: *N::noop_program_id(),
+                            program_path: N::noop_program_path().clone(),
+                            verifying_key: N::noop_circuit_verifying_key().clone(),
+                            proof: Noop::<N>::new().execute(
+                                ProgramPublicVariables::blank(),
+                                &NoopPrivateVariables::<N>::new_blank()?,
+                            )?,
+                        },
+                    ),
+                    &mut SRS::CircuitSpecific(&mut thread_rng()),
+                )?;
+
+                Ok((vec![proving_key.to_bytes_le()?, verifying_key.to_bytes_le()?], None))
+            }
+            Parameter::PoSW => {
+                let max_degree = AHPForR1CS::<N::InnerScalarField>::max_degree(40000, 40000, 60000)
+                    .map_err(|error| anyhow!("Failed to compute the maximum degree: {:?}", error))?;
+                let universal_srs = <N::PoSWSNARK as SNARK>::universal_setup(&max_degree, &mut thread_rng())?;
+                let srs_bytes = universal_srs.to_bytes_le()?;
+
+                let posw = <N::PoSW as PoSWScheme<N>>::setup::<ThreadRng>(&mut SRS::<ThreadRng, _>::Universal(
+                    &FromBytes::read_le(&srs_bytes[..])?,
+                ))?;
+                let proving_key = posw
+                    .proving_key()
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("The PoSW setup is missing its proving key"))?;
+
+                Ok((
+                    vec![proving_key.to_bytes_le()?, posw.verifying_key().to_bytes_le()?],
+                    None,
+                ))
+            }
+        }
+    }
+
+    /// Writes the given files of the parameter and their metadata to the given directory,
+    /// returning the metadata and the paths of the written files.
+    fn write_files(
+        directory: &Path,
+        parameter: Parameter,
+        files: Vec<Vec<u8>>,
+        circuit_id: Option<String>,
+    ) -> Result<(Map<String, Value>, Vec<PathBuf>)> {
+        let mut metadata = Map::new();
+        let mut paths = Vec::with_capacity(files.len() + 1);
+        for ((file_type, is_remote), bytes) in parameter.file_types().iter().zip(files) {
+            let checksum = hex::encode(sha256(&bytes));
+            let path = directory.join(Self::filename(parameter, file_type, *is_remote, &checksum));
+
+            fs::write(&path, &bytes)?;
+            paths.push(path);
+
+            metadata.insert(format!("{}_checksum", file_type), Value::from(checksum));
+            metadata.insert(format!("{}_size", file_type), Value::from(bytes.len()));
+        }
+        if let Some(circuit_id) = circuit_id {
+            metadata.insert("circuit_id".to_string(), Value::from(circuit_id));
+        }
+
+        let metadata_path = directory.join(format!("{}.metadata", parameter));
+        fs::write(&metadata_path, serde_json::to_vec_pretty(&metadata)?)?;
+        paths.push(metadata_path);
+
+        Ok((metadata, paths))
+    }
+
+    /// Returns the bytes of each file of the given parameter in the given directory,
+    /// or `None` if the directory does not contain the metadata of the parameter.
+    fn read_files(directory: &Path, parameter: Parameter) -> Result<Option<Vec<Vec<u8>>>> {
+        let metadata_path = directory.join(format!("{}.metadata", parameter));
+        if !metadata_path.exists() {
+            return Ok(None);
+        }
+        let metadata: Value = serde_json::from_str(&fs::read_to_string(&metadata_path)?)?;
+
+        let mut files = Vec::with_capacity(parameter.file_types().len());
+        for (file_type, is_remote) in parameter.file_types() {
+            let checksum = metadata[format!("{}_checksum", file_type)]
+                .as_str()
+                .ok_or_else(|| anyhow!("{} is missing the {} checksum", metadata_path.display(), file_type))?;
+
+            let path = directory.join(Self::filename(parameter, file_type, *is_remote, checksum));
+            let bytes = fs::read(&path)?;
+            if hex::encode(sha256(&bytes)) != checksum {
+                return Err(anyhow!(
+                    "{} does not match its checksum in its metadata",
+                    path.display()
+                ));
+            }
+            files.push(bytes);
+        }
+        Ok(Some(files))
+    }
+
+    /// Recomputes the checksum and size of each parameter file of the network, and compares them to the metadata.
+    /// Returns an error listing the report if any file has drifted from its metadata.
+    fn verify(options: &SetupOptions, remote_directory: Option<&Path>) -> Result<String> {
+        let mut report = Vec::new();
+        let mut num_drifted = 0;
+
+        for parameter in Parameter::ALL.iter().filter(|p| p.is_supported(options.network)) {
+            let metadata_path = options.directory.join(format!("{}.metadata", parameter));
+            if !metadata_path.exists() {
+                report.push(json!({ "file": format!("{}.metadata", parameter), "status": "missing" }));
+                continue;
+            }
+            let metadata: Value = serde_json::from_str(&fs::read_to_string(&metadata_path)?)?;
+
+            for (file_type, is_remote) in parameter.file_types() {
+                let expected_checksum = metadata[format!("{}_checksum", file_type)]
+                    .as_str()
+                    .ok_or_else(|| anyhow!("{} is missing the {} checksum", metadata_path.display(), file_type))?;
+                let expected_size = metadata[format!("{}_size", file_type)]
+                    .as_u64()
+                    .ok_or_else(|| anyhow!("{} is missing the {} size", metadata_path.display(), file_type))?;
+
+                let filename = Self::filename(*parameter, file_type, *is_remote, expected_checksum);
+                let path = match remote_directory {
+                    Some(remote_directory) if *is_remote => remote_directory.join(&filename),
+                    _ => options.directory.join(&filename),
+                };
+                if !path.exists() {
+                    report.push(json!({ "file": filename, "path": path, "status": "missing" }));
+                    continue;
+                }
+
+                let bytes = fs::read(&path)?;
+                let checksum = hex::encode(sha256(&bytes));
+                let is_drifted = checksum != expected_checksum || bytes.len() as u64 != expected_size;
+                num_drifted += is_drifted as usize;
+
+                report.push(json!({
+                    "file": filename,
+                    "path": path,
+                    "status": if is_drifted { "drifted" } else { "ok" },
+                    "expected_checksum": expected_checksum,
+                    "checksum": checksum,
+                    "expected_size": expected_size,
+                    "size": bytes.len(),
+                }));
+            }
+        }
+
+        let report = serde_json::to_string_pretty(&report)?;
+        match num_drifted {
+            0 => Ok(report),
+            _ => Err(anyhow!(
+                "{}\n{} parameter files have drifted from their metadata",
+                report,
+                num_drifted
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes a file of distinct bytes for each file type of each parameter of testnet1, with their metadata.
+    fn write_sample_files(directory: &Path) {
+        for parameter in Parameter::ALL.iter().filter(|p| p.is_supported(NetworkName::Testnet1)) {
+            let files = parameter
+                .file_types()
+                .iter()
+                .map(|(file_type, _)| format!("{}.{}", parameter, file_type).into_bytes())
+                .collect();
+            SetupCommand::write_files(directory, *parameter, files, None).unwrap();
+        }
+    }
+
+    fn verify(directory: &Path, remote_directory: Option<&Path>) -> Result<String> {
+        let options = SetupOptions {
+            network: NetworkName::Testnet1,
+            directory: directory.to_path_buf(),
+        };
+        SetupCommand::verify(&options, remote_directory)
+    }
+
+    fn statuses(report: &str) -> Vec<String> {
+        let report: Vec<Value> = serde_json::from_str(report).unwrap();
+        report
+            .iter()
+            .map(|file| file["status"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_verify() {
+        let directory = tempfile::tempdir().unwrap();
+
+        // The metadata of every parameter is missing.
+        assert_eq!(vec!["missing"; 4], statuses(&verify(directory.path(), None).unwrap()));
+
+        // Every file matches its metadata.
+        write_sample_files(directory.path());
+        assert_eq!(vec!["ok"; 8], statuses(&verify(directory.path(), None).unwrap()));
+
+        // A modified file has drifted from its metadata.
+        fs::write(directory.path().join("noop.verifying"), b"modified").unwrap();
+        let error = verify(directory.path(), None).unwrap_err().to_string();
+        assert!(error.contains("\"drifted\""), "{}", error);
+        assert!(
+            error.ends_with("1 parameter files have drifted from their metadata"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_verify_with_remote_directory() {
+        let directory = tempfile::tempdir().unwrap();
+        let remote_directory = tempfile::tempdir().unwrap();
+        write_sample_files(directory.path());
+
+        // Move the remote files to the remote directory.
+        for entry in fs::read_dir(directory.path()).unwrap() {
+            let path = entry.unwrap().path();
+            if path.file_name().unwrap().to_str().unwrap().contains(".proving.") {
+                fs::rename(&path, remote_directory.path().join(path.file_name().unwrap())).unwrap();
+            }
+        }
+
+        // The remote files are missing from the directory, and are found in the remote directory.
+        let statuses_without_remote = statuses(&verify(directory.path(), None).unwrap());
+        assert_eq!(
+            3,
+            statuses_without_remote
+                .iter()
+                .filter(|status| *status == "missing")
+                .count()
+        );
+        assert_eq!(
+            vec!["ok"; 8],
+            statuses(&verify(directory.path(), Some(remote_directory.path())).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_noop_setup_with_universal_srs() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let max_degree =
+            AHPForR1CS::<<Testnet2 as Network>::InnerScalarField>::max_degree(10000, 10000, 20000).unwrap();
+        let universal_srs =
+            <<Testnet2 as Network>::ProgramSNARK as SNARK>::universal_setup(&max_degree, &mut thread_rng()).unwrap();
+        let files = vec![universal_srs.to_bytes_le().unwrap()];
+        SetupCommand::write_files(directory.path(), Parameter::Universal, files, None).unwrap();
+
+        // The noop keys are derived from the universal SRS in the directory.
+        let (files, _) = SetupCommand::setup::<Testnet2>(Parameter::Noop, directory.path()).unwrap();
+        let (_, verifying_key) = <<Testnet2 as Network>::ProgramSNARK as SNARK>::setup(
+            &SynthesizedCircuit::<Testnet2>::Noop(Default::default()),
+            &mut SRS::<ThreadRng, _>::Universal(&universal_srs),
+        )
+        .unwrap();
+        assert_eq!(verifying_key.to_bytes_le().unwrap(), files[1]);
+    }
+
+    #[test]
+    fn test_read_files() {
+        let directory = tempfile::tempdir().unwrap();
+
+        // The inner keys are only read from a directory with an inner setup.
+        assert!(SetupCommand::read_files(directory.path(), Parameter::Inner)
+            .unwrap()
+            .is_none());
+
+        write_sample_files(directory.path());
+        let files = SetupCommand::read_files(directory.path(), Parameter::Inner)
+            .unwrap()
+            .unwrap();
+        assert_eq!(vec![b"inner.proving".to_vec(), b"inner.verifying".to_vec()], files);
+
+        // A modified inner key is rejected.
+        fs::write(directory.path().join("inner.verifying"), b"modified").unwrap();
+        assert!(SetupCommand::read_files(directory.path(), Parameter::Inner).is_err());
+    }
+}
@@ -30,6 +30,7 @@ use rand::{
     Rng,
 };
 use std::fmt::{Debug, Display};
+use zeroize::Zeroize;
 
 biginteger!(BigInteger64, 1);
 biginteger!(BigInteger128, 2);
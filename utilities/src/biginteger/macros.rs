@@ -247,6 +247,13 @@ macro_rules! biginteger {
             }
         }
 
+        impl Zeroize for $name {
+            #[inline]
+            fn zeroize(&mut self) {
+                self.0.zeroize();
+            }
+        }
+
         impl Debug for $name {
             fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
                 for i in self.0.iter().rev() {
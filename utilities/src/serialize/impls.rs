@@ -302,7 +302,7 @@ impl<T: CanonicalDeserialize> CanonicalDeserialize for Vec<T> {
     #[inline]
     fn deserialize<R: Read>(reader: &mut R) -> Result<Self, SerializationError> {
         let len = u64::deserialize(reader)?;
-        let mut values = Vec::with_capacity(len as usize);
+        let mut values = Vec::new();
         for _ in 0..len {
             values.push(T::deserialize(reader)?);
         }
@@ -312,7 +312,7 @@ impl<T: CanonicalDeserialize> CanonicalDeserialize for Vec<T> {
     #[inline]
     fn deserialize_uncompressed<R: Read>(reader: &mut R) -> Result<Self, SerializationError> {
         let len = u64::deserialize(reader)?;
-        let mut values = Vec::with_capacity(len as usize);
+        let mut values = Vec::new();
         for _ in 0..len {
             values.push(T::deserialize_uncompressed(reader)?);
         }